@@ -1,32 +1,152 @@
 use std::sync::Arc;
 
+use crate::config::Config;
+use crate::store::FileContentPolicyReader;
 #[cfg(not(feature = "sqlite"))]
 use crate::store::InMemoryStore;
+use crate::store::LocalBlobStore;
 #[cfg(feature = "sqlite")]
 use crate::store::SqliteStore;
 use crate::store::Store;
 
 #[derive(Clone)]
 pub struct AppState {
+    blob_store: Arc<dyn crate::port::BlobStore + Send + Sync>,
+    config: Config,
+    content_policy_reader: FileContentPolicyReader,
     store: Arc<dyn Store + Send + Sync>,
 }
 
 impl AppState {
     #[cfg(feature = "sqlite")]
-    pub async fn new() -> Self {
+    pub async fn new(
+        config: Config,
+        blob_dir: std::path::PathBuf,
+        content_policy_path: Option<std::path::PathBuf>,
+    ) -> Self {
         AppState {
+            blob_store: Arc::new(LocalBlobStore::new(blob_dir)),
+            config,
+            content_policy_reader: FileContentPolicyReader::new(content_policy_path),
             store: Arc::new(SqliteStore::new().await),
         }
     }
 
     #[cfg(not(feature = "sqlite"))]
-    pub async fn new() -> Self {
+    pub async fn new(
+        config: Config,
+        blob_dir: std::path::PathBuf,
+        content_policy_path: Option<std::path::PathBuf>,
+    ) -> Self {
         AppState {
+            blob_store: Arc::new(LocalBlobStore::new(blob_dir)),
+            config,
+            content_policy_reader: FileContentPolicyReader::new(content_policy_path),
             store: Arc::new(InMemoryStore::new()),
         }
     }
 }
 
+impl axum::extract::FromRef<AppState> for Config {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BlobReferenceReader for AppState {
+    async fn is_blob_referenced(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<bool, crate::port::BlobReferenceReaderError> {
+        self.store.is_blob_referenced(hash).await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BlobStore for AppState {
+    async fn get_blob(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<Option<Vec<u8>>, crate::port::BlobStoreError> {
+        self.blob_store.get_blob(hash).await
+    }
+
+    async fn put_blob(
+        &self,
+        bytes: &[u8],
+    ) -> Result<crate::model::shared::id::BlobHash, crate::port::BlobStoreError> {
+        self.blob_store.put_blob(bytes).await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BoardReader for AppState {
+    async fn get_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::read::Board>, crate::port::BoardReaderError> {
+        self.store.get_board(id).await
+    }
+
+    async fn list_boards(
+        &self,
+    ) -> Result<Vec<crate::model::read::Board>, crate::port::BoardReaderError> {
+        self.store.list_boards().await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BoardRepository for AppState {
+    async fn find_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::write::Board>, crate::port::BoardRepositoryError> {
+        self.store.find_board(id).await
+    }
+
+    async fn store_board(
+        &self,
+        board: &crate::model::write::Board,
+    ) -> Result<(), crate::port::BoardRepositoryError> {
+        self.store.store_board(board).await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::ContentPolicyReader for AppState {
+    async fn get_content_policy(
+        &self,
+    ) -> Result<Arc<crate::model::write::ContentPolicy>, crate::port::ContentPolicyReaderError>
+    {
+        self.content_policy_reader.get_content_policy().await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::PostedMessageReader for AppState {
+    async fn list_posted_messages(
+        &self,
+        fingerprint: &crate::model::write::ContentFingerprint,
+        since: crate::utils::date_time::DateTime,
+    ) -> Result<Vec<crate::model::write::PostedMessage>, crate::port::PostedMessageReaderError>
+    {
+        self.store.list_posted_messages(fingerprint, since).await
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::RateLimiter for AppState {
+    async fn consume_rate_limit(
+        &self,
+        action: crate::model::write::RateLimitAction,
+        client: &crate::model::write::ClientKey,
+        limit: &crate::model::write::RateLimit,
+    ) -> Result<(), crate::port::RateLimiterError> {
+        self.store.consume_rate_limit(action, client, limit).await
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::port::ThreadRepository for AppState {
     async fn find(
@@ -39,10 +159,20 @@ impl crate::port::ThreadRepository for AppState {
     async fn store(
         &self,
         version: Option<crate::model::write::Version>,
-        events: &[crate::model::shared::event::ThreadEvent],
+        events: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
     ) -> Result<(), crate::port::ThreadRepositoryError> {
         self.store.store(version, events).await
     }
+
+    async fn store_all(
+        &self,
+        streams: &[(
+            Option<crate::model::write::Version>,
+            &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+        )],
+    ) -> Result<(), crate::port::ThreadRepositoryError> {
+        self.store.store_all(streams).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -54,10 +184,18 @@ impl crate::port::ThreadReader for AppState {
         self.store.get_thread(id).await
     }
 
+    async fn list_tags(
+        &self,
+    ) -> Result<Vec<crate::model::read::ThreadTag>, crate::port::ThreadReaderError> {
+        self.store.list_tags().await
+    }
+
     async fn list_threads(
         &self,
+        board_id: Option<&crate::model::shared::id::BoardId>,
+        tag: Option<&crate::model::write::ThreadTag>,
     ) -> Result<Vec<crate::model::read::ThreadWithoutMessages>, crate::port::ThreadReaderError>
     {
-        self.store.list_threads().await
+        self.store.list_threads(board_id, tag).await
     }
 }
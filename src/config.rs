@@ -0,0 +1,52 @@
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The number of threads that a client can create per window
+    pub create_thread_rate_limit: crate::model::write::RateLimit,
+    /// The rules to reject duplicated and cross-posted messages
+    pub duplicate_policy: crate::model::write::DuplicatePolicy,
+    /// The server secret with which the client and voter keys are derived from the IP addresses
+    ///
+    /// This must stay the same across restarts and deployments. Changing it gives every client a
+    /// new key, so the clients lose their messages and rate limits and can vote again.
+    pub key_secret: String,
+    /// The duration after `created_at` during which a message can be edited
+    pub message_edit_window: std::time::Duration,
+    /// The maximum number of graphemes in a message content
+    ///
+    /// Only new contents are checked, so the stored messages remain readable when this is lowered.
+    pub max_message_content_length: usize,
+    /// The number of messages at which a thread is filled and no longer accepts replies
+    pub max_thread_messages: u16,
+    /// The password that moderators send with HTTP Basic authentication
+    ///
+    /// Moderation is disabled when this is not set.
+    pub moderator_token: Option<String>,
+    /// The number of replies that a client can post per window
+    pub reply_rate_limit: crate::model::write::RateLimit,
+    /// The header in which the reverse proxy sends the client IP address (e.g. `X-Forwarded-For`)
+    ///
+    /// The header is ignored when this is not set, because clients could forge it.
+    pub trusted_proxy_header: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            create_thread_rate_limit: crate::model::write::RateLimit::new(
+                3,
+                std::time::Duration::from_secs(10 * 60),
+            ),
+            duplicate_policy: crate::model::write::DuplicatePolicy::default(),
+            key_secret: String::default(),
+            message_edit_window: std::time::Duration::from_secs(5 * 60),
+            max_message_content_length: 255,
+            max_thread_messages: 1000,
+            moderator_token: None,
+            reply_rate_limit: crate::model::write::RateLimit::new(
+                10,
+                std::time::Duration::from_secs(60),
+            ),
+            trusted_proxy_header: None,
+        }
+    }
+}
@@ -1,11 +1,38 @@
+mod attachments;
+pub mod blobs;
+pub mod boards;
+mod client_ip;
+mod markup;
+mod moderator;
+mod request_metadata;
+mod requester;
 pub mod root;
+pub mod tags;
 pub mod threads;
 
 pub fn router<
-    S: Clone + crate::port::ThreadRepository + crate::port::ThreadReader + Send + Sync + 'static,
->() -> axum::Router<S> {
+    S: Clone
+        + crate::port::BlobReferenceReader
+        + crate::port::BlobStore
+        + crate::port::BoardReader
+        + crate::port::BoardRepository
+        + crate::port::ContentPolicyReader
+        + crate::port::PostedMessageReader
+        + crate::port::RateLimiter
+        + crate::port::ThreadReader
+        + crate::port::ThreadRepository
+        + Send
+        + Sync
+        + 'static,
+>() -> axum::Router<S>
+where
+    crate::config::Config: axum::extract::FromRef<S>,
+{
     axum::Router::new()
+        .merge(self::blobs::router::<S>())
+        .merge(self::boards::router::<S>())
         .merge(self::root::router::<S>())
+        .merge(self::tags::router::<S>())
         .merge(self::threads::router::<S>())
 }
 
@@ -40,6 +67,10 @@ mod tests {
         router: axum::Router<()>,
         request: axum::http::Request<axum::body::Body>,
     ) -> anyhow::Result<Response> {
+        // as if served with `into_make_service_with_connect_info`
+        let router = router.layer(axum::extract::connect_info::MockConnectInfo(
+            std::net::SocketAddr::from(([127, 0, 0, 1], 12345)),
+        ));
         let response = tower::ServiceExt::oneshot(router, request).await?;
         Ok(response)
     }
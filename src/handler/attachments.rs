@@ -0,0 +1,251 @@
+use axum::extract::{Form, FromRequest, Multipart};
+
+/// A form sent as `application/x-www-form-urlencoded`, or as `multipart/form-data` with images
+pub struct FormWithAttachments<T> {
+    /// The contents of the non-empty files of the `attachments` fields
+    pub attachments: Vec<axum::body::Bytes>,
+    pub form: T,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FormWithAttachmentsRejection {
+    #[error("deserialize")]
+    Deserialize(#[source] serde_urlencoded::de::Error),
+    #[error("form")]
+    Form(#[source] axum::extract::rejection::FormRejection),
+    #[error("multipart")]
+    Multipart(#[source] axum::extract::multipart::MultipartRejection),
+    #[error("multipart field")]
+    MultipartField(#[source] axum::extract::multipart::MultipartError),
+}
+
+impl axum::response::IntoResponse for FormWithAttachmentsRejection {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            FormWithAttachmentsRejection::Deserialize(_) => {
+                axum::http::StatusCode::UNPROCESSABLE_ENTITY.into_response()
+            }
+            FormWithAttachmentsRejection::Form(e) => e.into_response(),
+            FormWithAttachmentsRejection::Multipart(e) => e.into_response(),
+            FormWithAttachmentsRejection::MultipartField(e) => e.into_response(),
+        }
+    }
+}
+
+impl<T: serde::de::DeserializeOwned, S: Send + Sync> FromRequest<S> for FormWithAttachments<T> {
+    type Rejection = FormWithAttachmentsRejection;
+
+    async fn from_request(req: axum::extract::Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_multipart = req
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|it| it.to_str().ok())
+            .is_some_and(|it| it.starts_with("multipart/form-data"));
+        if !is_multipart {
+            let Form(form) = Form::<T>::from_request(req, state)
+                .await
+                .map_err(FormWithAttachmentsRejection::Form)?;
+            return Ok(Self {
+                attachments: vec![],
+                form,
+            });
+        }
+
+        let mut multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(FormWithAttachmentsRejection::Multipart)?;
+        let mut attachments = vec![];
+        let mut fields = vec![];
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(FormWithAttachmentsRejection::MultipartField)?
+        {
+            let name = field.name().unwrap_or_default().to_owned();
+            if field.file_name().is_some() {
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(FormWithAttachmentsRejection::MultipartField)?;
+                // browsers send an empty file when no file is selected
+                if name == "attachments" && !bytes.is_empty() {
+                    attachments.push(bytes);
+                }
+            } else {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(FormWithAttachmentsRejection::MultipartField)?;
+                fields.push((name, value));
+            }
+        }
+        // deserialize the text fields in the same way as `Form`
+        let query = serde_urlencoded::to_string(&fields).expect("text fields to be serializable");
+        let form = serde_urlencoded::from_str::<T>(&query)
+            .map_err(FormWithAttachmentsRejection::Deserialize)?;
+        Ok(Self { attachments, form })
+    }
+}
+
+/// An image that has been checked and has a thumbnail, but has not been stored yet
+pub struct PreparedAttachment {
+    pub attachment: crate::model::write::MessageAttachment,
+    bytes: axum::body::Bytes,
+    thumbnail: Vec<u8>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PrepareAttachmentsError {
+    #[error("decode image")]
+    DecodeImage(#[source] image::ImageError),
+    #[error("generate thumbnail")]
+    GenerateThumbnail(#[source] tokio::task::JoinError),
+    #[error("invalid attachment")]
+    InvalidAttachment(#[source] crate::model::write::MessageAttachmentError),
+}
+
+impl axum::response::IntoResponse for PrepareAttachmentsError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            PrepareAttachmentsError::DecodeImage(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            PrepareAttachmentsError::GenerateThumbnail(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            PrepareAttachmentsError::InvalidAttachment(e) => match e {
+                crate::model::write::MessageAttachmentError::Empty => {
+                    axum::http::StatusCode::BAD_REQUEST.into_response()
+                }
+                crate::model::write::MessageAttachmentError::TooLarge(_) => {
+                    axum::http::StatusCode::PAYLOAD_TOO_LARGE.into_response()
+                }
+                crate::model::write::MessageAttachmentError::UnsupportedMime => {
+                    axum::http::StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response()
+                }
+            },
+        }
+    }
+}
+
+/// The request body limit of the forms with attachments
+pub const BODY_LIMIT: usize = crate::model::write::Thread::MAX_ATTACHMENTS
+    * crate::model::write::MessageAttachment::MAX_SIZE
+    + 64 * 1024;
+
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+const THUMBNAIL_SIZE: u32 = 200;
+
+/// Checks the size and the type of the images and generates their thumbnails
+///
+/// Nothing is stored, so that the images of a rejected message do not remain in the blob store.
+pub async fn prepare_attachments(
+    files: Vec<axum::body::Bytes>,
+) -> Result<Vec<PreparedAttachment>, PrepareAttachmentsError> {
+    let mut prepared = vec![];
+    for bytes in files {
+        crate::model::write::MessageAttachment::check(&bytes)
+            .map_err(PrepareAttachmentsError::InvalidAttachment)?;
+        let thumbnail = tokio::task::spawn_blocking({
+            let bytes = bytes.clone();
+            move || generate_thumbnail(&bytes)
+        })
+        .await
+        .map_err(PrepareAttachmentsError::GenerateThumbnail)?
+        .map_err(PrepareAttachmentsError::DecodeImage)?;
+        let attachment = crate::model::write::MessageAttachment::new(&bytes, &thumbnail)
+            .map_err(PrepareAttachmentsError::InvalidAttachment)?;
+        prepared.push(PreparedAttachment {
+            attachment,
+            bytes,
+            thumbnail,
+        });
+    }
+    Ok(prepared)
+}
+
+/// Stores the images and their thumbnails before the events referencing them are stored
+pub async fn store_attachments<S: crate::port::BlobStore>(
+    state: &S,
+    prepared: &[PreparedAttachment],
+) -> Result<(), crate::port::BlobStoreError> {
+    for it in prepared {
+        state.put_blob(&it.bytes).await?;
+        state.put_blob(&it.thumbnail).await?;
+    }
+    Ok(())
+}
+
+fn generate_thumbnail(bytes: &[u8]) -> Result<Vec<u8>, image::ImageError> {
+    let mut reader = image::ImageReader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(image::ImageError::IoError)?;
+    let mut limits = image::Limits::default();
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+    let mut image = reader.decode()?;
+    // `thumbnail` also enlarges smaller images
+    if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    }
+    let mut thumbnail = std::io::Cursor::new(vec![]);
+    image.write_to(&mut thumbnail, image::ImageFormat::Png)?;
+    Ok(thumbnail.into_inner())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A 2x1 PNG image
+    pub(crate) fn png_for_testing() -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(vec![]);
+        image::RgbImage::from_pixel(2, 1, image::Rgb([255, 0, 0]))
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .expect("png encoding to be successful");
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_generate_thumbnail() -> anyhow::Result<()> {
+        let thumbnail = generate_thumbnail(&png_for_testing())?;
+        let image = image::load_from_memory_with_format(&thumbnail, image::ImageFormat::Png)?;
+        assert_eq!((image.width(), image.height()), (2, 1));
+
+        let mut bytes = std::io::Cursor::new(vec![]);
+        image::RgbImage::new(400, 100).write_to(&mut bytes, image::ImageFormat::Png)?;
+        let thumbnail = generate_thumbnail(&bytes.into_inner())?;
+        let image = image::load_from_memory_with_format(&thumbnail, image::ImageFormat::Png)?;
+        assert_eq!((image.width(), image.height()), (200, 50));
+
+        assert!(generate_thumbnail(b"\x89PNG\r\n\x1A\nbroken").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepare_attachments() -> anyhow::Result<()> {
+        let png = axum::body::Bytes::from(png_for_testing());
+        let prepared = prepare_attachments(vec![png.clone()]).await?;
+        assert_eq!(prepared.len(), 1);
+        assert_eq!(
+            prepared[0].attachment.hash,
+            crate::model::shared::id::BlobHash::of(&png)
+        );
+        assert_eq!(
+            prepared[0].attachment.mime,
+            crate::model::write::MessageAttachmentMime::Png
+        );
+
+        assert!(matches!(
+            prepare_attachments(vec![axum::body::Bytes::from_static(b"<svg></svg>")]).await,
+            Err(PrepareAttachmentsError::InvalidAttachment(_))
+        ));
+        assert!(matches!(
+            prepare_attachments(vec![axum::body::Bytes::from_static(b"GIF89a")]).await,
+            Err(PrepareAttachmentsError::DecodeImage(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+mod get;
+
+pub fn router<
+    S: Clone + crate::port::BlobReferenceReader + crate::port::BlobStore + Send + Sync + 'static,
+>() -> axum::Router<S> {
+    axum::Router::new().route("/blobs/{hash}", axum::routing::get(self::get::handler::<S>))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::handler::tests::send_request;
+
+    use super::*;
+
+    /// The stored blobs and the hashes of the blobs attached to the messages
+    #[derive(Clone)]
+    struct AppState(Vec<Vec<u8>>, Vec<crate::model::shared::id::BlobHash>);
+
+    impl AppState {
+        /// The state in which all the stored blobs are attached to the messages
+        fn referenced(blobs: Vec<Vec<u8>>) -> Self {
+            let hashes = blobs
+                .iter()
+                .map(|it| crate::model::shared::id::BlobHash::of(it))
+                .collect();
+            Self(blobs, hashes)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl crate::port::BlobReferenceReader for AppState {
+        async fn is_blob_referenced(
+            &self,
+            hash: &crate::model::shared::id::BlobHash,
+        ) -> Result<bool, crate::port::BlobReferenceReaderError> {
+            Ok(self.1.contains(hash))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl crate::port::BlobStore for AppState {
+        async fn get_blob(
+            &self,
+            hash: &crate::model::shared::id::BlobHash,
+        ) -> Result<Option<Vec<u8>>, crate::port::BlobStoreError> {
+            Ok(self
+                .0
+                .iter()
+                .find(|it| &crate::model::shared::id::BlobHash::of(it) == hash)
+                .cloned())
+        }
+
+        async fn put_blob(
+            &self,
+            _bytes: &[u8],
+        ) -> Result<crate::model::shared::id::BlobHash, crate::port::BlobStoreError> {
+            Err(crate::port::BlobStoreError(
+                "the blob store of the tests is read-only".into(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get() -> anyhow::Result<()> {
+        let png = crate::handler::attachments::tests::png_for_testing();
+        let router = router().with_state(AppState::referenced(vec![
+            png.clone(),
+            b"<svg></svg>".to_vec(),
+        ]));
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(format!(
+                "/blobs/{}",
+                crate::model::shared::id::BlobHash::of(&png)
+            ))
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router.clone(), request).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_TYPE),
+            Some(&axum::http::HeaderValue::from_static("image/png"))
+        );
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(format!(
+                "/blobs/{}",
+                crate::model::shared::id::BlobHash::of(b"<svg></svg>")
+            ))
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router.clone(), request).await?;
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_TYPE),
+            Some(&axum::http::HeaderValue::from_static(
+                "application/octet-stream"
+            ))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_not_found() -> anyhow::Result<()> {
+        let router = router().with_state(AppState::referenced(vec![]));
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(format!(
+                "/blobs/{}",
+                crate::model::shared::id::BlobHash::of(b"missing")
+            ))
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router.clone(), request).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri("/blobs/..%2Fsecret")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router, request).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_unreferenced() -> anyhow::Result<()> {
+        // e.g. the image of a deleted message
+        let png = crate::handler::attachments::tests::png_for_testing();
+        let router = router().with_state(AppState(vec![png.clone()], vec![]));
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri(format!(
+                "/blobs/{}",
+                crate::model::shared::id::BlobHash::of(&png)
+            ))
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router, request).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::str::FromStr as _;
+
+use axum::extract::{Path, State};
+
+use crate::port::BlobReferenceReader;
+use crate::port::BlobStore;
+
+pub struct BlobGetResponse {
+    pub bytes: Vec<u8>,
+}
+
+impl axum::response::IntoResponse for BlobGetResponse {
+    fn into_response(self) -> axum::response::Response {
+        // the content type is sniffed again so that only images are ever served inline
+        let content_type = crate::model::write::MessageAttachmentMime::sniff(&self.bytes)
+            .map(|it| it.to_string())
+            .unwrap_or_else(|| "application/octet-stream".to_owned());
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .header(axum::http::header::CONTENT_TYPE, content_type)
+            .header(
+                axum::http::header::CACHE_CONTROL,
+                "public, max-age=31536000, immutable",
+            )
+            .header(axum::http::header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+            .body(axum::body::Body::from(self.bytes))
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BlobGetError {
+    #[error("get blob")]
+    GetBlob(#[source] crate::port::BlobStoreError),
+    #[error("is blob referenced")]
+    IsBlobReferenced(#[source] crate::port::BlobReferenceReaderError),
+    #[error("invalid blob hash")]
+    InvalidBlobHash(#[source] crate::model::shared::id::BlobHashError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::BlobHash),
+}
+
+impl axum::response::IntoResponse for BlobGetError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            BlobGetError::GetBlob(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            BlobGetError::IsBlobReferenced(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            BlobGetError::InvalidBlobHash(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            BlobGetError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+}
+
+pub async fn handler<S: BlobReferenceReader + BlobStore>(
+    Path((hash,)): Path<(String,)>,
+    State(state): State<S>,
+) -> Result<BlobGetResponse, BlobGetError> {
+    let hash = crate::model::shared::id::BlobHash::from_str(&hash)
+        .map_err(BlobGetError::InvalidBlobHash)?;
+    // the blobs of the deleted messages are kept in the store but no longer served
+    if !state
+        .is_blob_referenced(&hash)
+        .await
+        .map_err(BlobGetError::IsBlobReferenced)?
+    {
+        return Err(BlobGetError::NotFound(hash));
+    }
+    let bytes = state
+        .get_blob(&hash)
+        .await
+        .map_err(BlobGetError::GetBlob)?
+        .ok_or_else(|| BlobGetError::NotFound(hash))?;
+    Ok(BlobGetResponse { bytes })
+}
@@ -0,0 +1,108 @@
+use std::str::FromStr as _;
+
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::moderator::Moderator;
+use crate::port::BoardRepository;
+use crate::port::BoardRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct BoardCreateRequestBody {
+    #[serde(default)]
+    pub description: String,
+    pub id: String,
+    pub max_content_length: u16,
+    pub max_threads: u16,
+    pub name: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct BoardCreateResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for BoardCreateResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/boards/{}/threads", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BoardCreateError {
+    #[error("invalid board")]
+    InvalidBoard(#[source] crate::model::write::BoardError),
+    #[error("invalid board id")]
+    InvalidBoardId(#[source] crate::model::shared::id::BoardIdError),
+    #[error("max content length too large: {0}")]
+    MaxContentLengthTooLarge(u16),
+    #[error("store")]
+    Store(#[source] BoardRepositoryError),
+}
+
+impl axum::response::IntoResponse for BoardCreateError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            BoardCreateError::InvalidBoard(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            BoardCreateError::InvalidBoardId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            BoardCreateError::MaxContentLengthTooLarge(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            BoardCreateError::Store(e) => match e {
+                BoardRepositoryError::AlreadyExists(_) => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+                BoardRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: BoardRepository>(
+    _: Moderator,
+    State(state): State<S>,
+    State(config): State<Config>,
+    Form(BoardCreateRequestBody {
+        description,
+        id,
+        max_content_length,
+        max_threads,
+        name,
+    }): Form<BoardCreateRequestBody>,
+) -> Result<BoardCreateResponseBody, BoardCreateError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let id = crate::model::shared::id::BoardId::from_str(&id)
+        .map_err(BoardCreateError::InvalidBoardId)?;
+    if usize::from(max_content_length) > config.max_message_content_length {
+        return Err(BoardCreateError::MaxContentLengthTooLarge(
+            max_content_length,
+        ));
+    }
+    let board =
+        crate::model::write::Board::new(id, name, description, max_content_length, max_threads)
+            .map_err(BoardCreateError::InvalidBoard)?;
+    BoardRepository::store_board(&state, &board)
+        .await
+        .map_err(BoardCreateError::Store)?;
+
+    Ok(BoardCreateResponseBody {
+        id: board.id().to_string(),
+    })
+}
@@ -0,0 +1,56 @@
+use axum::extract::State;
+
+use crate::config::Config;
+use crate::handler::AskamaTemplateExt;
+use crate::port::BoardReader;
+
+#[derive(askama::Template)]
+#[template(path = "boards/index.html")]
+pub struct BoardListResponse {
+    pub boards: Vec<crate::model::read::Board>,
+    pub max_content_length: usize,
+}
+
+impl AskamaTemplateExt for BoardListResponse {}
+
+impl axum::response::IntoResponse for BoardListResponse {
+    fn into_response(self) -> axum::response::Response {
+        self.to_response()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BoardListError {
+    #[error("list boards")]
+    ListBoards(#[source] crate::port::BoardReaderError),
+}
+
+impl axum::response::IntoResponse for BoardListError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            BoardListError::ListBoards(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+pub async fn handler<S: BoardReader>(
+    State(state): State<S>,
+    State(config): State<Config>,
+) -> Result<BoardListResponse, BoardListError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let boards = state
+        .list_boards()
+        .await
+        .map_err(BoardListError::ListBoards)?
+        .into_iter()
+        .map(|board| board.with_max_content_length(config.max_message_content_length))
+        .collect::<Vec<crate::model::read::Board>>();
+    Ok(BoardListResponse {
+        boards,
+        max_content_length: config.max_message_content_length,
+    })
+}
@@ -0,0 +1,76 @@
+use std::str::FromStr as _;
+
+use axum::extract::{Path, State};
+
+use crate::config::Config;
+use crate::handler::AskamaTemplateExt;
+use crate::port::BoardReader;
+use crate::port::ThreadReader;
+
+#[derive(askama::Template)]
+#[template(path = "boards/[id]/threads.html")]
+pub struct BoardListThreadsResponse {
+    pub board: crate::model::read::Board,
+    pub threads: Vec<crate::model::read::ThreadWithoutMessages>,
+}
+
+impl AskamaTemplateExt for BoardListThreadsResponse {}
+
+impl axum::response::IntoResponse for BoardListThreadsResponse {
+    fn into_response(self) -> axum::response::Response {
+        self.to_response()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BoardListThreadsError {
+    #[error("get board")]
+    GetBoard(#[source] crate::port::BoardReaderError),
+    #[error("invalid board id")]
+    InvalidId(#[source] crate::model::shared::id::BoardIdError),
+    #[error("list threads")]
+    ListThreads(#[source] crate::port::ThreadReaderError),
+    #[error("not found")]
+    NotFound,
+}
+
+impl axum::response::IntoResponse for BoardListThreadsError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            BoardListThreadsError::GetBoard(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            BoardListThreadsError::InvalidId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            BoardListThreadsError::ListThreads(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            BoardListThreadsError::NotFound => axum::http::StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+}
+
+pub async fn handler<S: BoardReader + ThreadReader>(
+    State(state): State<S>,
+    State(config): State<Config>,
+    Path((id,)): Path<(String,)>,
+) -> Result<BoardListThreadsResponse, BoardListThreadsError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let id = crate::model::shared::id::BoardId::from_str(&id)
+        .map_err(BoardListThreadsError::InvalidId)?;
+    let board = state
+        .get_board(&id)
+        .await
+        .map_err(BoardListThreadsError::GetBoard)?
+        .ok_or(BoardListThreadsError::NotFound)?
+        .with_max_content_length(config.max_message_content_length);
+    let mut threads = state
+        .list_threads(Some(&id), None)
+        .await
+        .map_err(BoardListThreadsError::ListThreads)?;
+    threads.truncate(usize::from(board.max_threads));
+    Ok(BoardListThreadsResponse { board, threads })
+}
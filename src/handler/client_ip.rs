@@ -0,0 +1,81 @@
+use crate::config::Config;
+
+/// The IP address of the client
+///
+/// When the trusted proxy header is configured, the last address in the header (the one appended
+/// by the proxy) is used. Otherwise, or when the header is missing or invalid, the address of the
+/// peer is used.
+pub struct ClientIp(pub std::net::IpAddr);
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientIpRejection {
+    #[error("unknown")]
+    Unknown,
+}
+
+impl axum::response::IntoResponse for ClientIpRejection {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ClientIpRejection::Unknown => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for ClientIp
+where
+    Config: axum::extract::FromRef<S>,
+{
+    type Rejection = ClientIpRejection;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let config = <Config as axum::extract::FromRef<S>>::from_ref(state);
+        if let Some(header) = config.trusted_proxy_header
+            && let Some(ip) = parts
+                .headers
+                .get_all(header.as_str())
+                .iter()
+                .next_back()
+                .and_then(|it| it.to_str().ok())
+                .and_then(last_forwarded_ip)
+        {
+            return Ok(Self(ip));
+        }
+        let axum::extract::ConnectInfo(addr) =
+            axum::extract::ConnectInfo::<std::net::SocketAddr>::from_request_parts(parts, state)
+                .await
+                .map_err(|_| ClientIpRejection::Unknown)?;
+        Ok(Self(addr.ip()))
+    }
+}
+
+fn last_forwarded_ip(value: &str) -> Option<std::net::IpAddr> {
+    let last = value.rsplit(',').next()?.trim();
+    last.parse::<std::net::IpAddr>()
+        .ok()
+        .or_else(|| last.parse::<std::net::SocketAddr>().ok().map(|it| it.ip()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_forwarded_ip() {
+        let ip = |s: &str| s.parse::<std::net::IpAddr>().ok();
+        assert_eq!(last_forwarded_ip("192.0.2.1"), ip("192.0.2.1"));
+        assert_eq!(
+            last_forwarded_ip("198.51.100.1, 192.0.2.1"),
+            ip("192.0.2.1")
+        );
+        assert_eq!(last_forwarded_ip("192.0.2.1:1234"), ip("192.0.2.1"));
+        assert_eq!(last_forwarded_ip("2001:db8::1"), ip("2001:db8::1"));
+        assert_eq!(last_forwarded_ip("[2001:db8::1]:1234"), ip("2001:db8::1"));
+        assert_eq!(last_forwarded_ip("192.0.2.1, unknown"), None);
+        assert_eq!(last_forwarded_ip(""), None);
+    }
+}
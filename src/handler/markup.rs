@@ -0,0 +1,243 @@
+/// Renders the message content into HTML with a limited markup
+///
+/// - fenced code blocks (lines between "```" lines)
+/// - quote lines starting with `>`
+/// - inline code (`` `code` ``)
+/// - spoilers (`||spoiler||`)
+/// - autolinked `http://` and `https://` URLs
+/// - anchors to the messages before the message numbered `number` (`>>1`, `>>1-3`)
+///
+/// Anchors to the message itself or to later messages are left as plain text. Every character of
+/// the content is escaped, and only the fixed tags and attributes above are emitted, so the result
+/// can be embedded into the page as is.
+pub fn render(content: &str, number: u16) -> String {
+    let mut html = String::new();
+    let mut paragraph = vec![];
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("```") {
+            render_paragraph(&paragraph, number, &mut html);
+            paragraph.clear();
+            // an unclosed code block continues until the end of the content
+            let code = lines
+                .by_ref()
+                .take_while(|it| it.trim_end() != "```")
+                .collect::<Vec<&str>>()
+                .join("\n");
+            html.push_str("<pre><code>");
+            escape(&code, &mut html);
+            html.push_str("</code></pre>");
+        } else if line.trim().is_empty() {
+            render_paragraph(&paragraph, number, &mut html);
+            paragraph.clear();
+        } else {
+            paragraph.push(line);
+        }
+    }
+    render_paragraph(&paragraph, number, &mut html);
+    html
+}
+
+fn render_paragraph(lines: &[&str], number: u16, html: &mut String) {
+    if lines.is_empty() {
+        return;
+    }
+    html.push_str("<p>");
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            html.push_str("<br />");
+        }
+        if is_quote(line) {
+            html.push_str(r#"<span class="quote">"#);
+            render_inline(line, true, number, html);
+            html.push_str("</span>");
+        } else {
+            render_inline(line, true, number, html);
+        }
+    }
+    html.push_str("</p>");
+}
+
+fn render_inline(s: &str, allow_spoiler: bool, number: u16, html: &mut String) {
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if let Some((code, after)) = enclosed(rest, "`") {
+            html.push_str("<code>");
+            escape(code, html);
+            html.push_str("</code>");
+            rest = after;
+            continue;
+        }
+        if allow_spoiler && let Some((spoiler, after)) = enclosed(rest, "||") {
+            html.push_str(r#"<span class="spoiler">"#);
+            render_inline(spoiler, false, number, html);
+            html.push_str("</span>");
+            rest = after;
+            continue;
+        }
+        if let Some(len) = url_len(rest) {
+            let (url, after) = rest.split_at(len);
+            html.push_str(r#"<a href=""#);
+            escape(url, html);
+            html.push_str(r#"" rel="nofollow noopener noreferrer">"#);
+            escape(url, html);
+            html.push_str("</a>");
+            rest = after;
+            continue;
+        }
+        if let Some((anchored, len)) = anchor(rest) {
+            let (anchor, after) = rest.split_at(len);
+            // as in `parse_anchors`, only the earlier messages can be anchored
+            if anchored < u32::from(number) {
+                html.push_str(&format!(r##"<a href="#message-{anchored}">"##));
+                escape(anchor, html);
+                html.push_str("</a>");
+            } else {
+                escape(anchor, html);
+            }
+            rest = after;
+            continue;
+        }
+        escape(&rest[..c.len_utf8()], html);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/// Returns the anchored message number and the length of `>>N` or `>>N-M` at the start of `s`
+fn anchor(s: &str) -> Option<(u32, usize)> {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+    let after = s.strip_prefix(">>")?;
+    let start_len = digits(after);
+    let number = after[..start_len].parse::<u32>().ok()?;
+    if number == 0 {
+        return None;
+    }
+    let end_len = match after[start_len..].strip_prefix('-').map(digits) {
+        Some(len) if len > 0 => 1 + len,
+        _ => 0,
+    };
+    Some((number, 2 + start_len + end_len))
+}
+
+/// Returns the non-empty text between `delimiter`s at the start of `s` and the text after it
+fn enclosed<'a>(s: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let after = s.strip_prefix(delimiter)?;
+    let end = after.find(delimiter)?;
+    if end == 0 {
+        return None;
+    }
+    Some((&after[..end], &after[end + delimiter.len()..]))
+}
+
+fn escape(s: &str, html: &mut String) {
+    for c in s.chars() {
+        match c {
+            '"' => html.push_str("&quot;"),
+            '&' => html.push_str("&amp;"),
+            '\'' => html.push_str("&#39;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            _ => html.push(c),
+        }
+    }
+}
+
+fn is_quote(line: &str) -> bool {
+    line.starts_with('>') && anchor(line).is_none()
+}
+
+/// Returns the length of the `http://` or `https://` URL at the start of `s`
+fn url_len(s: &str) -> Option<usize> {
+    let scheme_len = ["http://", "https://"]
+        .into_iter()
+        .find(|it| s.starts_with(it))?
+        .len();
+    let len = s
+        .find(|c: char| c.is_whitespace() || matches!(c, '"' | '<' | '>' | '`'))
+        .unwrap_or(s.len());
+    // trailing punctuation is more likely a part of the sentence than of the URL
+    let len = s[..len]
+        .trim_end_matches(['!', '\'', ')', ',', '.', ':', ';', '?'])
+        .len();
+    (len > scheme_len).then_some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render("", 10), "");
+        assert_eq!(render("Hello", 10), "<p>Hello</p>");
+        assert_eq!(
+            render("line 1\r\nline 2\n\nline 3", 10),
+            "<p>line 1<br />line 2</p><p>line 3</p>"
+        );
+        assert_eq!(
+            render("before\n```rust\nfn main() {}\n\n```\nafter", 10),
+            "<p>before</p><pre><code>fn main() {}\n</code></pre><p>after</p>"
+        );
+        assert_eq!(
+            render("```\nunclosed <b>", 10),
+            "<pre><code>unclosed &lt;b&gt;</code></pre>"
+        );
+        assert_eq!(
+            render("> quote\nreply", 10),
+            r#"<p><span class="quote">&gt; quote</span><br />reply</p>"#
+        );
+        assert_eq!(
+            render(">>1 reply", 10),
+            r##"<p><a href="#message-1">&gt;&gt;1</a> reply</p>"##
+        );
+        assert_eq!(
+            render("see >>2-3, >>0 and >>x", 10),
+            r##"<p>see <a href="#message-2">&gt;&gt;2-3</a>, &gt;&gt;0 and &gt;&gt;x</p>"##
+        );
+        assert_eq!(
+            render("use `a < b` and ``", 10),
+            "<p>use <code>a &lt; b</code> and ``</p>"
+        );
+        assert_eq!(
+            render("||secret `code`|| and ||||", 10),
+            r#"<p><span class="spoiler">secret <code>code</code></span> and ||||</p>"#
+        );
+        assert_eq!(
+            render("see https://example.com/a?b=1&c=2.", 10),
+            r#"<p>see <a href="https://example.com/a?b=1&amp;c=2" rel="nofollow noopener noreferrer">https://example.com/a?b=1&amp;c=2</a>.</p>"#
+        );
+        assert_eq!(render("http:// ftp://x", 10), "<p>http:// ftp://x</p>");
+    }
+
+    #[test]
+    fn test_render_anchors_only_earlier_messages() {
+        assert_eq!(
+            render(">>2 >>3 >>4", 3),
+            r##"<p><a href="#message-2">&gt;&gt;2</a> &gt;&gt;3 &gt;&gt;4</p>"##
+        );
+        assert_eq!(render(">>1", 1), "<p>&gt;&gt;1</p>");
+        // an anchor is never a quote, even if it links to nothing
+        assert_eq!(render(">>5 later", 3), "<p>&gt;&gt;5 later</p>");
+    }
+
+    #[test]
+    fn test_render_escapes_html() {
+        assert_eq!(
+            render(r#"<script>alert("x")</script>"#, 10),
+            "<p>&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;</p>"
+        );
+        assert_eq!(
+            render(r#"https://example.com/"onmouseover="alert(1)"#, 10),
+            r#"<p><a href="https://example.com/" rel="nofollow noopener noreferrer">https://example.com/</a>&quot;onmouseover=&quot;alert(1)</p>"#
+        );
+        assert_eq!(
+            render("https://example.com/'><img src=x onerror=alert(1)>", 10),
+            r#"<p><a href="https://example.com/" rel="nofollow noopener noreferrer">https://example.com/</a>&#39;&gt;&lt;img src=x onerror=alert(1)&gt;</p>"#
+        );
+        assert_eq!(
+            render("javascript:alert(1) ||<i>||", 10),
+            r#"<p>javascript:alert(1) <span class="spoiler">&lt;i&gt;</span></p>"#
+        );
+        assert_eq!(render("a & b", 10), "<p>a &amp; b</p>");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::config::Config;
+
+/// A request authenticated with the moderator token
+///
+/// The token is sent as the password of HTTP Basic authentication. The user name is ignored.
+pub struct Moderator;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ModeratorRejection {
+    #[error("disabled")]
+    Disabled,
+    #[error("unauthorized")]
+    Unauthorized,
+}
+
+impl axum::response::IntoResponse for ModeratorRejection {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ModeratorRejection::Disabled => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ModeratorRejection::Unauthorized => (
+                axum::http::StatusCode::UNAUTHORIZED,
+                [(
+                    axum::http::header::WWW_AUTHENTICATE,
+                    r#"Basic realm="bbbs moderators""#,
+                )],
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for Moderator
+where
+    Config: axum::extract::FromRef<S>,
+{
+    type Rejection = ModeratorRejection;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let config = <Config as axum::extract::FromRef<S>>::from_ref(state);
+        let token = config.moderator_token.ok_or(ModeratorRejection::Disabled)?;
+        let password = parts
+            .headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|it| it.to_str().ok())
+            .and_then(basic_password)
+            .ok_or(ModeratorRejection::Unauthorized)?;
+        if !constant_time_eq(password.as_bytes(), token.as_bytes()) {
+            return Err(ModeratorRejection::Unauthorized);
+        }
+        Ok(Self)
+    }
+}
+
+fn basic_password(authorization: &str) -> Option<String> {
+    use base64::Engine as _;
+    let encoded = authorization.strip_prefix("Basic ")?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (_, password) = decoded.split_once(':')?;
+    Some(password.to_owned())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0_u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_password() {
+        // "mod:secret"
+        assert_eq!(
+            basic_password("Basic bW9kOnNlY3JldA=="),
+            Some("secret".to_owned())
+        );
+        assert_eq!(basic_password("Bearer secret"), None);
+        assert_eq!(basic_password("Basic !"), None);
+        // "secret"
+        assert_eq!(basic_password("Basic c2VjcmV0"), None);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::config::Config;
+use crate::handler::client_ip::{ClientIp, ClientIpRejection};
+use crate::handler::moderator::Moderator;
+use crate::model::shared::event_envelope::{ThreadEventActor, ThreadEventMetadata};
+
+/// The metadata of the events caused by the request
+///
+/// The request, correlation and causation IDs are taken from the `x-request-id`,
+/// `x-correlation-id` and `x-causation-id` headers when they are valid. Otherwise a new request ID
+/// is generated and used as the others. The client IP address is only kept as the client key.
+pub struct RequestMetadata(pub ThreadEventMetadata);
+
+impl RequestMetadata {
+    const MAX_ID_LEN: usize = 128;
+    const MAX_USER_AGENT_LEN: usize = 255;
+}
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for RequestMetadata
+where
+    Config: axum::extract::FromRef<S>,
+{
+    type Rejection = ClientIpRejection;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let ClientIp(client_ip) = ClientIp::from_request_parts(parts, state).await?;
+        let config = <Config as axum::extract::FromRef<S>>::from_ref(state);
+        let client_key =
+            crate::model::write::ClientKey::from_ip(&client_ip, &config.key_secret).to_string();
+        let actor = match Moderator::from_request_parts(parts, state).await {
+            Ok(Moderator) => ThreadEventActor::Moderator,
+            Err(_) => ThreadEventActor::Anonymous {
+                id: client_key.clone(),
+            },
+        };
+        let header = |name: &str, max_len: usize| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|it| it.to_str().ok())
+                .map(str::trim)
+                .filter(|it| !it.is_empty())
+                .map(|it| it.chars().take(max_len).collect::<String>())
+        };
+        let id = |name: &str| {
+            header(name, Self::MAX_ID_LEN).filter(|it| {
+                it.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            })
+        };
+        let request_id = id("x-request-id").unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        Ok(Self(ThreadEventMetadata {
+            actor: Some(actor),
+            causation_id: Some(id("x-causation-id").unwrap_or_else(|| request_id.clone())),
+            client_ip_hash: Some(client_key),
+            correlation_id: Some(id("x-correlation-id").unwrap_or_else(|| request_id.clone())),
+            request_id: Some(request_id),
+            user_agent: header(
+                axum::http::header::USER_AGENT.as_str(),
+                Self::MAX_USER_AGENT_LEN,
+            ),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::FromRequestParts as _;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct State;
+
+    impl axum::extract::FromRef<State> for Config {
+        fn from_ref(_: &State) -> Self {
+            Config {
+                key_secret: "secret".to_owned(),
+                moderator_token: Some("secret".to_owned()),
+                trusted_proxy_header: Some("x-forwarded-for".to_owned()),
+                ..Default::default()
+            }
+        }
+    }
+
+    async fn extract(request: axum::http::Request<()>) -> anyhow::Result<ThreadEventMetadata> {
+        let (mut parts, _) = request.into_parts();
+        let RequestMetadata(metadata) = RequestMetadata::from_request_parts(&mut parts, &State)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(metadata)
+    }
+
+    #[tokio::test]
+    async fn test_from_request_parts() -> anyhow::Result<()> {
+        let client_key = crate::model::write::ClientKey::from_ip(
+            &std::net::IpAddr::from([192, 0, 2, 1]),
+            "secret",
+        )
+        .to_string();
+
+        let metadata = extract(
+            axum::http::Request::builder()
+                .header("x-forwarded-for", "192.0.2.1")
+                .header("x-request-id", "req-1")
+                .header("x-correlation-id", "corr-1")
+                .header("user-agent", "curl/8.0")
+                .body(())?,
+        )
+        .await?;
+        assert_eq!(
+            metadata,
+            ThreadEventMetadata {
+                actor: Some(ThreadEventActor::Anonymous {
+                    id: client_key.clone()
+                }),
+                causation_id: Some("req-1".to_owned()),
+                client_ip_hash: Some(client_key.clone()),
+                correlation_id: Some("corr-1".to_owned()),
+                request_id: Some("req-1".to_owned()),
+                user_agent: Some("curl/8.0".to_owned()),
+            }
+        );
+
+        // "mod:secret"
+        let metadata = extract(
+            axum::http::Request::builder()
+                .header("x-forwarded-for", "192.0.2.1")
+                .header("x-request-id", "invalid id")
+                .header("authorization", "Basic bW9kOnNlY3JldA==")
+                .body(())?,
+        )
+        .await?;
+        assert_eq!(metadata.actor, Some(ThreadEventActor::Moderator));
+        assert_eq!(metadata.client_ip_hash, Some(client_key));
+        assert_ne!(metadata.request_id.as_deref(), Some("invalid id"));
+        assert_eq!(metadata.correlation_id, metadata.request_id);
+        assert_eq!(metadata.causation_id, metadata.request_id);
+        assert_eq!(metadata.user_agent, None);
+        Ok(())
+    }
+}
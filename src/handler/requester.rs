@@ -0,0 +1,53 @@
+use crate::config::Config;
+use crate::handler::client_ip::{ClientIp, ClientIpRejection};
+use crate::handler::moderator::Moderator;
+
+/// The one who sends the request, used to authorize the commands on existing threads
+///
+/// A request with the moderator token is a moderator. Any other request is an anonymous client,
+/// identified by its client key.
+pub enum Requester {
+    Client(crate::model::write::ClientKey),
+    Moderator,
+}
+
+impl Requester {
+    /// Returns whether the requester may act for the poster of the message
+    ///
+    /// Moderators may act for anyone.
+    pub fn is_poster_of(&self, thread: &crate::model::write::Thread, number: u16) -> bool {
+        match self {
+            Requester::Client(client_key) => thread.is_posted_by(number, client_key),
+            Requester::Moderator => true,
+        }
+    }
+
+    /// Returns whether the requester may manage the thread (e.g. change the title or close it)
+    ///
+    /// Only moderators and the creator of the thread (the poster of the first message) may.
+    pub fn is_owner_of(&self, thread: &crate::model::write::Thread) -> bool {
+        self.is_poster_of(thread, 1)
+    }
+}
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for Requester
+where
+    Config: axum::extract::FromRef<S>,
+{
+    type Rejection = ClientIpRejection;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        if let Ok(Moderator) = Moderator::from_request_parts(parts, state).await {
+            return Ok(Self::Moderator);
+        }
+        let ClientIp(client_ip) = ClientIp::from_request_parts(parts, state).await?;
+        let config = <Config as axum::extract::FromRef<S>>::from_ref(state);
+        Ok(Self::Client(crate::model::write::ClientKey::from_ip(
+            &client_ip,
+            &config.key_secret,
+        )))
+    }
+}
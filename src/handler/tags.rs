@@ -0,0 +1,84 @@
+mod list;
+
+pub fn router<S: Clone + crate::port::ThreadReader + Send + Sync + 'static>() -> axum::Router<S> {
+    axum::Router::new().route("/tags", axum::routing::get(self::list::handler::<S>))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::handler::tests::ResponseExt;
+    use crate::handler::tests::send_request;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct AppState(Vec<crate::model::read::ThreadTag>);
+
+    #[async_trait::async_trait]
+    impl crate::port::ThreadReader for AppState {
+        async fn get_thread(
+            &self,
+            _id: &crate::model::shared::id::ThreadId,
+        ) -> Result<Option<crate::model::read::Thread>, crate::port::ThreadReaderError> {
+            Ok(None)
+        }
+
+        async fn list_tags(
+            &self,
+        ) -> Result<Vec<crate::model::read::ThreadTag>, crate::port::ThreadReaderError> {
+            Ok(self.0.clone())
+        }
+
+        async fn list_threads(
+            &self,
+            _board_id: Option<&crate::model::shared::id::BoardId>,
+            _tag: Option<&crate::model::write::ThreadTag>,
+        ) -> Result<Vec<crate::model::read::ThreadWithoutMessages>, crate::port::ThreadReaderError>
+        {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list() -> anyhow::Result<()> {
+        let router = router().with_state(AppState(vec![
+            crate::model::read::ThreadTag {
+                count: 2,
+                name: "help".to_owned(),
+            },
+            crate::model::read::ThreadTag {
+                count: 1,
+                name: "rust".to_owned(),
+            },
+        ]));
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri("/tags")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router, request).await?;
+
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = response.into_body_string().await?;
+        assert!(body.contains(r#"<a href="/threads?tag=help">help</a>"#));
+        assert!(body.contains(r#"<a href="/threads?tag=rust">rust</a>"#));
+        assert!(body.contains("<td>2</td>"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_empty() -> anyhow::Result<()> {
+        let router = router().with_state(AppState(vec![]));
+
+        let request = axum::http::Request::builder()
+            .method(axum::http::Method::GET)
+            .uri("/tags")
+            .body(axum::body::Body::empty())?;
+        let response = send_request(router, request).await?;
+
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = response.into_body_string().await?;
+        assert!(body.contains("There are no tags."));
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use axum::extract::State;
+
+use crate::handler::AskamaTemplateExt;
+use crate::port::ThreadReader;
+
+#[derive(askama::Template)]
+#[template(path = "tags/index.html")]
+pub struct TagListResponse {
+    pub tags: Vec<crate::model::read::ThreadTag>,
+}
+
+impl AskamaTemplateExt for TagListResponse {}
+
+impl axum::response::IntoResponse for TagListResponse {
+    fn into_response(self) -> axum::response::Response {
+        self.to_response()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TagListError {
+    #[error("list tags")]
+    ListTags(#[source] crate::port::ThreadReaderError),
+}
+
+impl axum::response::IntoResponse for TagListError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            TagListError::ListTags(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+pub async fn handler<S: ThreadReader>(
+    State(state): State<S>,
+) -> Result<TagListResponse, TagListError> {
+    Ok(TagListResponse {
+        tags: state.list_tags().await.map_err(TagListError::ListTags)?,
+    })
+}
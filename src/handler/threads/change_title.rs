@@ -0,0 +1,124 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::handler::requester::Requester;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadChangeTitleRequestBody {
+    pub title: String,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadChangeTitleResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadChangeTitleResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadChangeTitleError {
+    #[error("change title")]
+    ChangeTitle(#[source] crate::model::write::ThreadError),
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("invalid thread title")]
+    InvalidThreadTitle(#[source] crate::model::write::ThreadTitleError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadChangeTitleError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadChangeTitleError::ChangeTitle(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadChangeTitleError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadChangeTitleError::Forbidden => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ThreadChangeTitleError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadChangeTitleError::InvalidThreadTitle(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadChangeTitleError::NotFound(_) => {
+                axum::http::StatusCode::NOT_FOUND.into_response()
+            }
+            ThreadChangeTitleError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    requester: Requester,
+    Form(ThreadChangeTitleRequestBody { title, version }): Form<ThreadChangeTitleRequestBody>,
+) -> Result<ThreadChangeTitleResponseBody, ThreadChangeTitleError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let title = crate::model::write::ThreadTitle::try_from(title)
+        .map_err(ThreadChangeTitleError::InvalidThreadTitle)?;
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadChangeTitleError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadChangeTitleError::Find)?
+        .ok_or_else(|| ThreadChangeTitleError::NotFound(thread_id))?;
+    if !requester.is_owner_of(&thread) {
+        return Err(ThreadChangeTitleError::Forbidden);
+    }
+    let (_, events) = thread
+        .change_title(title)
+        .map_err(ThreadChangeTitleError::ChangeTitle)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadChangeTitleError::Store)?;
+
+    Ok(ThreadChangeTitleResponseBody {
+        id: thread.id().to_string(),
+    })
+}
@@ -0,0 +1,110 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::handler::requester::Requester;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadCloseRequestBody {
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadCloseResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadCloseResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadCloseError {
+    #[error("close")]
+    Close(#[source] crate::model::write::ThreadError),
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadCloseError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadCloseError::Close(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            ThreadCloseError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadCloseError::Forbidden => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ThreadCloseError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadCloseError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadCloseError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    requester: Requester,
+    Form(ThreadCloseRequestBody { version }): Form<ThreadCloseRequestBody>,
+) -> Result<ThreadCloseResponseBody, ThreadCloseError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadCloseError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadCloseError::Find)?
+        .ok_or_else(|| ThreadCloseError::NotFound(thread_id))?;
+    if !requester.is_owner_of(&thread) {
+        return Err(ThreadCloseError::Forbidden);
+    }
+    let (_, events) = thread.close().map_err(ThreadCloseError::Close)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadCloseError::Store)?;
+
+    Ok(ThreadCloseResponseBody {
+        id: thread.id().to_string(),
+    })
+}
@@ -1,12 +1,43 @@
-use axum::extract::{Form, State};
+use std::str::FromStr as _;
 
+use axum::extract::State;
+
+use crate::config::Config;
+use crate::handler::attachments::FormWithAttachments;
+use crate::handler::client_ip::ClientIp;
+use crate::handler::request_metadata::RequestMetadata;
 use crate::model::write::Thread;
+use crate::port::BlobStore;
+use crate::port::BoardRepository;
+use crate::port::ContentPolicyReader;
+use crate::port::PostedMessageReader;
+use crate::port::RateLimiter;
+use crate::port::RateLimiterError;
 use crate::port::ThreadRepository;
 use crate::port::ThreadRepositoryError;
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct ThreadCreateRequestBody {
+    /// The default board if not specified
+    #[serde(default)]
+    pub board_id: Option<String>,
     pub content: String,
+    /// `name` or `name#secret`
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The number of hours until the poll closes, or never if not specified
+    #[serde(default)]
+    pub poll_closes_in_hours: Option<String>,
+    /// One option per line
+    #[serde(default)]
+    pub poll_options: String,
+    /// No poll if empty
+    #[serde(default)]
+    pub poll_question: String,
+    /// Comma or whitespace separated tags
+    #[serde(default)]
+    pub tags: String,
+    pub title: String,
 }
 
 #[derive(serde::Serialize)]
@@ -31,22 +62,111 @@ impl axum::response::IntoResponse for ThreadCreateResponseBody {
 
 #[derive(Debug, thiserror::Error)]
 pub enum MessageCreateError {
+    #[error("board not found {0:?}")]
+    BoardNotFound(crate::model::shared::id::BoardId),
+    #[error("check message")]
+    CheckMessage(#[source] crate::model::write::BoardError),
+    #[error("content policy")]
+    ContentPolicy(#[source] crate::model::write::ContentPolicyViolation),
     #[error("create")]
     Create(#[source] crate::model::write::ThreadError),
+    #[error("duplicate message")]
+    DuplicateMessage(#[source] crate::model::write::DuplicateMessage),
+    #[error("find board")]
+    FindBoard(#[source] crate::port::BoardRepositoryError),
+    #[error("get content policy")]
+    GetContentPolicy(#[source] crate::port::ContentPolicyReaderError),
+    #[error("invalid board id")]
+    InvalidBoardId(#[source] crate::model::shared::id::BoardIdError),
     #[error("invalid message content")]
     InvalidMessageContent(#[source] crate::model::write::MessageContentError),
+    #[error("invalid poll")]
+    InvalidPoll(#[source] crate::model::write::PollError),
+    #[error("invalid poll closes in hours")]
+    InvalidPollClosesInHours(#[source] std::num::ParseIntError),
+    #[error("invalid poster")]
+    InvalidPoster(#[source] crate::model::write::PosterError),
+    #[error("invalid thread tags")]
+    InvalidThreadTags(#[source] crate::model::write::ThreadTagError),
+    #[error("invalid thread title")]
+    InvalidThreadTitle(#[source] crate::model::write::ThreadTitleError),
+    #[error("list posted messages")]
+    ListPostedMessages(#[source] crate::port::PostedMessageReaderError),
+    #[error("prepare attachments")]
+    PrepareAttachments(#[source] crate::handler::attachments::PrepareAttachmentsError),
+    #[error("rate limit")]
+    RateLimit(#[source] RateLimiterError),
     #[error("store")]
     Store(#[source] ThreadRepositoryError),
+    #[error("store attachments")]
+    StoreAttachments(#[source] crate::port::BlobStoreError),
 }
 
 impl axum::response::IntoResponse for MessageCreateError {
     fn into_response(self) -> axum::response::Response {
         match self {
+            MessageCreateError::BoardNotFound(_) => {
+                axum::http::StatusCode::NOT_FOUND.into_response()
+            }
+            MessageCreateError::CheckMessage(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            MessageCreateError::ContentPolicy(e) => {
+                (axum::http::StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            }
             MessageCreateError::Create(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            MessageCreateError::DuplicateMessage(e) => {
+                (axum::http::StatusCode::CONFLICT, e.to_string()).into_response()
+            }
+            MessageCreateError::FindBoard(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            MessageCreateError::GetContentPolicy(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            MessageCreateError::InvalidBoardId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
             MessageCreateError::InvalidMessageContent(_) => {
                 axum::http::StatusCode::BAD_REQUEST.into_response()
             }
+            MessageCreateError::InvalidPoll(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            MessageCreateError::InvalidPollClosesInHours(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            MessageCreateError::InvalidPoster(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            MessageCreateError::InvalidThreadTags(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            MessageCreateError::InvalidThreadTitle(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            MessageCreateError::ListPostedMessages(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            MessageCreateError::PrepareAttachments(e) => e.into_response(),
+            MessageCreateError::RateLimit(e) => match e {
+                RateLimiterError::Exceeded(e) => (
+                    axum::http::StatusCode::TOO_MANY_REQUESTS,
+                    [(
+                        axum::http::header::RETRY_AFTER,
+                        e.retry_after.as_secs().to_string(),
+                    )],
+                    "too many messages, please wait and try again",
+                )
+                    .into_response(),
+                RateLimiterError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+            },
             MessageCreateError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
                 ThreadRepositoryError::InternalError(_) => {
                     axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
                 }
@@ -57,20 +177,137 @@ impl axum::response::IntoResponse for MessageCreateError {
                     axum::http::StatusCode::CONFLICT.into_response()
                 }
             },
+            MessageCreateError::StoreAttachments(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
         }
     }
 }
 
-pub async fn handler<S: ThreadRepository>(
+pub async fn handler<
+    S: BlobStore
+        + BoardRepository
+        + ContentPolicyReader
+        + PostedMessageReader
+        + RateLimiter
+        + ThreadRepository,
+>(
     State(state): State<S>,
-    Form(ThreadCreateRequestBody { content }): Form<ThreadCreateRequestBody>,
-) -> Result<ThreadCreateResponseBody, MessageCreateError> {
-    let content = crate::model::write::MessageContent::try_from(content)
-        .map_err(MessageCreateError::InvalidMessageContent)?;
-    let message = crate::model::write::Message::create(content);
+    State(config): State<Config>,
+    ClientIp(client_ip): ClientIp,
+    RequestMetadata(metadata): RequestMetadata,
+    FormWithAttachments {
+        attachments,
+        form:
+            ThreadCreateRequestBody {
+                board_id,
+                content,
+                name,
+                poll_closes_in_hours,
+                poll_options,
+                poll_question,
+                tags,
+                title,
+            },
+    }: FormWithAttachments<ThreadCreateRequestBody>,
+) -> Result<ThreadCreateResponseBody, MessageCreateError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let board_id = board_id
+        .filter(|it| !it.is_empty())
+        .map(|it| crate::model::shared::id::BoardId::from_str(&it))
+        .transpose()
+        .map_err(MessageCreateError::InvalidBoardId)?
+        .unwrap_or_default();
+    let content =
+        crate::model::write::MessageContent::new(content, config.max_message_content_length)
+            .map_err(MessageCreateError::InvalidMessageContent)?;
+    let tags = crate::model::write::ThreadTag::parse_list(&tags)
+        .map_err(MessageCreateError::InvalidThreadTags)?;
+    let title = crate::model::write::ThreadTitle::try_from(title)
+        .map_err(MessageCreateError::InvalidThreadTitle)?;
+    let poster = name
+        .filter(|it| !it.trim().is_empty())
+        .map(crate::model::write::Poster::try_from)
+        .transpose()
+        .map_err(MessageCreateError::InvalidPoster)?;
+    ContentPolicyReader::get_content_policy(&state)
+        .await
+        .map_err(MessageCreateError::GetContentPolicy)?
+        .check(&content)
+        .map_err(MessageCreateError::ContentPolicy)?;
+    let client_key = crate::model::write::ClientKey::from_ip(&client_ip, &config.key_secret);
+    let now = crate::utils::date_time::DateTime::now();
+    let posted = PostedMessageReader::list_posted_messages(
+        &state,
+        &crate::model::write::ContentFingerprint::of(&content),
+        config.duplicate_policy.since(now),
+    )
+    .await
+    .map_err(MessageCreateError::ListPostedMessages)?;
+    config
+        .duplicate_policy
+        .check(&content, &posted, &client_key, None, now)
+        .map_err(MessageCreateError::DuplicateMessage)?;
+    let attachments = crate::handler::attachments::prepare_attachments(attachments)
+        .await
+        .map_err(MessageCreateError::PrepareAttachments)?;
+    let message = crate::model::write::Message::create(
+        content,
+        poster,
+        attachments.iter().map(|it| it.attachment.clone()).collect(),
+        Some(client_key.clone()),
+    );
+    let poll = if poll_question.trim().is_empty() {
+        None
+    } else {
+        let closes_at = poll_closes_in_hours
+            .filter(|it| !it.trim().is_empty())
+            .map(|it| u16::from_str(it.trim()))
+            .transpose()
+            .map_err(MessageCreateError::InvalidPollClosesInHours)?
+            .map(|hours| {
+                crate::utils::date_time::DateTime::from_unix_timestamp_millis(
+                    crate::utils::date_time::DateTime::now().to_unix_timestamp_millis()
+                        + i64::from(hours) * 60 * 60 * 1000,
+                )
+            });
+        let options = poll_options
+            .lines()
+            .map(str::trim)
+            .filter(|it| !it.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<String>>();
+        Some(
+            crate::model::write::Poll::new(poll_question.trim().to_owned(), options, closes_at)
+                .map_err(MessageCreateError::InvalidPoll)?,
+        )
+    };
+
+    let board = BoardRepository::find_board(&state, &board_id)
+        .await
+        .map_err(MessageCreateError::FindBoard)?
+        .ok_or_else(|| MessageCreateError::BoardNotFound(board_id))?;
+    board
+        .check_message(&message)
+        .map_err(MessageCreateError::CheckMessage)?;
 
-    let (thread, events) = Thread::create(message.clone()).map_err(MessageCreateError::Create)?;
-    ThreadRepository::store(&state, None, &events)
+    let (thread, events) = Thread::create(board.id().clone(), title, message.clone(), tags, poll)
+        .map_err(MessageCreateError::Create)?;
+    // only the threads that pass the validations count toward the limit
+    RateLimiter::consume_rate_limit(
+        &state,
+        crate::model::write::RateLimitAction::CreateThread,
+        &client_key,
+        &config.create_thread_rate_limit,
+    )
+    .await
+    .map_err(MessageCreateError::RateLimit)?;
+    crate::handler::attachments::store_attachments(&state, &attachments)
+        .await
+        .map_err(MessageCreateError::StoreAttachments)?;
+    ThreadRepository::store(&state, None, &metadata.envelop(&events))
         .await
         .map_err(MessageCreateError::Store)?;
 
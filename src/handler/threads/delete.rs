@@ -0,0 +1,131 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::handler::requester::Requester;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadDeleteRequestBody {
+    pub reason: String,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadDeleteResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadDeleteResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadDeleteError {
+    #[error("delete message")]
+    DeleteMessage(#[source] crate::model::write::ThreadError),
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid message deletion reason")]
+    InvalidMessageDeletionReason(#[source] crate::model::write::MessageDeletionReasonError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadDeleteError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadDeleteError::DeleteMessage(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadDeleteError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadDeleteError::Forbidden => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ThreadDeleteError::InvalidMessageDeletionReason(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadDeleteError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadDeleteError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadDeleteError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id, number)): Path<(String, u16)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    requester: Requester,
+    Form(ThreadDeleteRequestBody { reason, version }): Form<ThreadDeleteRequestBody>,
+) -> Result<ThreadDeleteResponseBody, ThreadDeleteError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let reason = crate::model::write::MessageDeletionReason::from_str(&reason)
+        .map_err(ThreadDeleteError::InvalidMessageDeletionReason)?;
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadDeleteError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadDeleteError::Find)?
+        .ok_or_else(|| ThreadDeleteError::NotFound(thread_id))?;
+    // the reason must match the requester: authors delete their own messages, moderators any
+    let authorized = match (&requester, reason) {
+        (
+            Requester::Client(client_key),
+            crate::model::write::MessageDeletionReason::AuthorRequest,
+        ) => thread.is_posted_by(number, client_key),
+        (Requester::Moderator, crate::model::write::MessageDeletionReason::Moderator) => true,
+        _ => false,
+    };
+    if !authorized {
+        return Err(ThreadDeleteError::Forbidden);
+    }
+    let (_, events) = thread
+        .delete_message(number, reason)
+        .map_err(ThreadDeleteError::DeleteMessage)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadDeleteError::Store)?;
+
+    Ok(ThreadDeleteResponseBody {
+        id: thread.id().to_string(),
+    })
+}
@@ -0,0 +1,166 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::handler::requester::Requester;
+use crate::port::BoardRepository;
+use crate::port::ContentPolicyReader;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadEditRequestBody {
+    pub content: String,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadEditResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadEditResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadEditError {
+    #[error("board not found {0:?}")]
+    BoardNotFound(crate::model::shared::id::BoardId),
+    #[error("check message")]
+    CheckMessage(#[source] crate::model::write::BoardError),
+    #[error("content policy")]
+    ContentPolicy(#[source] crate::model::write::ContentPolicyViolation),
+    #[error("edit message")]
+    EditMessage(#[source] crate::model::write::ThreadError),
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("find board")]
+    FindBoard(#[source] crate::port::BoardRepositoryError),
+    #[error("forbidden")]
+    Forbidden,
+    #[error("get content policy")]
+    GetContentPolicy(#[source] crate::port::ContentPolicyReaderError),
+    #[error("invalid message content")]
+    InvalidMessageContent(#[source] crate::model::write::MessageContentError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadEditError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadEditError::BoardNotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadEditError::CheckMessage(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            ThreadEditError::ContentPolicy(e) => {
+                (axum::http::StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            }
+            ThreadEditError::EditMessage(e) => match e {
+                crate::model::write::ThreadError::Closed => (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "the thread is closed and its messages can no longer be edited",
+                )
+                    .into_response(),
+                _ => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            },
+            ThreadEditError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadEditError::FindBoard(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            ThreadEditError::Forbidden => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ThreadEditError::GetContentPolicy(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            ThreadEditError::InvalidMessageContent(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadEditError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadEditError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadEditError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: BoardRepository + ContentPolicyReader + ThreadRepository>(
+    Path((thread_id, number)): Path<(String, u16)>,
+    State(state): State<S>,
+    State(config): State<Config>,
+    RequestMetadata(metadata): RequestMetadata,
+    requester: Requester,
+    Form(ThreadEditRequestBody { content, version }): Form<ThreadEditRequestBody>,
+) -> Result<ThreadEditResponseBody, ThreadEditError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let content =
+        crate::model::write::MessageContent::new(content, config.max_message_content_length)
+            .map_err(ThreadEditError::InvalidMessageContent)?;
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadEditError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+    ContentPolicyReader::get_content_policy(&state)
+        .await
+        .map_err(ThreadEditError::GetContentPolicy)?
+        .check(&content)
+        .map_err(ThreadEditError::ContentPolicy)?;
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadEditError::Find)?
+        .ok_or_else(|| ThreadEditError::NotFound(thread_id))?;
+    if !requester.is_poster_of(&thread, number) {
+        return Err(ThreadEditError::Forbidden);
+    }
+    let (edited, events) = thread
+        .edit_message(number, content, config.message_edit_window)
+        .map_err(ThreadEditError::EditMessage)?;
+    let board = BoardRepository::find_board(&state, thread.board_id())
+        .await
+        .map_err(ThreadEditError::FindBoard)?
+        .ok_or_else(|| ThreadEditError::BoardNotFound(thread.board_id().clone()))?;
+    if let Some(message) = edited.message(number) {
+        board
+            .check_message(message)
+            .map_err(ThreadEditError::CheckMessage)?;
+    }
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadEditError::Store)?;
+
+    Ok(ThreadEditResponseBody {
+        id: thread.id().to_string(),
+    })
+}
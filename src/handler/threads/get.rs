@@ -2,12 +2,23 @@ use std::str::FromStr as _;
 
 use axum::extract::{Path, State};
 
+use crate::config::Config;
 use crate::handler::AskamaTemplateExt;
+use crate::handler::requester::Requester;
 use crate::port::ThreadReader;
+use crate::port::ThreadRepository;
 
 #[derive(askama::Template)]
 #[template(path = "threads/[id].html")]
 pub struct ThreadGetResponse {
+    /// The rendered contents of `thread.messages`, in the same order
+    pub contents: Vec<String>,
+    /// Whether the requester may edit each of `thread.messages`, in the same order
+    pub editable: Vec<bool>,
+    /// Whether the requester may manage the thread (close, reopen, retitle and tag it)
+    pub owner: bool,
+    /// Whether the poll of the thread no longer accepts votes
+    pub poll_closed: bool,
     pub thread: crate::model::read::Thread,
 }
 
@@ -21,6 +32,8 @@ impl axum::response::IntoResponse for ThreadGetResponse {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ThreadGetError {
+    #[error("find")]
+    Find(#[source] crate::port::ThreadRepositoryError),
     #[error("get thread")]
     GetThread(#[source] crate::port::ThreadReaderError),
     #[error("invalid thread id")]
@@ -32,6 +45,9 @@ pub enum ThreadGetError {
 impl axum::response::IntoResponse for ThreadGetError {
     fn into_response(self) -> axum::response::Response {
         match self {
+            ThreadGetError::Find(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
             ThreadGetError::GetThread(_) => {
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
             }
@@ -41,16 +57,41 @@ impl axum::response::IntoResponse for ThreadGetError {
     }
 }
 
-pub async fn handler<S: ThreadReader>(
+pub async fn handler<S: ThreadReader + ThreadRepository>(
     State(state): State<S>,
     Path((id,)): Path<(String,)>,
-) -> Result<ThreadGetResponse, ThreadGetError> {
+    requester: Requester,
+) -> Result<ThreadGetResponse, ThreadGetError>
+where
+    Config: axum::extract::FromRef<S>,
+{
     let id =
         crate::model::shared::id::ThreadId::from_str(&id).map_err(ThreadGetError::InvalidId)?;
-    state
+    let thread = state
         .get_thread(&id)
         .await
         .map_err(ThreadGetError::GetThread)?
-        .map(|thread| ThreadGetResponse { thread })
-        .ok_or_else(|| ThreadGetError::NotFound)
+        .ok_or_else(|| ThreadGetError::NotFound)?;
+    // the read model does not know the posters, so ask the write model who may edit what
+    let written = ThreadRepository::find(&state, &id)
+        .await
+        .map_err(ThreadGetError::Find)?
+        .ok_or_else(|| ThreadGetError::NotFound)?;
+    Ok(ThreadGetResponse {
+        contents: thread
+            .messages
+            .iter()
+            .map(|it| crate::handler::markup::render(&it.content, it.number))
+            .collect(),
+        editable: thread
+            .messages
+            .iter()
+            .map(|it| requester.is_poster_of(&written, it.number))
+            .collect(),
+        owner: requester.is_owner_of(&written),
+        poll_closed: written
+            .poll()
+            .is_some_and(|it| it.is_closed_at(crate::utils::date_time::DateTime::now())),
+        thread,
+    })
 }
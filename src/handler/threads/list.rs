@@ -1,11 +1,20 @@
-use axum::extract::State;
+use std::str::FromStr as _;
+
+use axum::extract::{Query, State};
 
 use crate::handler::AskamaTemplateExt;
 use crate::port::ThreadReader;
 
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadListQuery {
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
 #[derive(askama::Template)]
 #[template(path = "threads/index.html")]
 pub struct ThreadListResponse {
+    pub tag: Option<String>,
     pub threads: Vec<crate::model::read::ThreadWithoutMessages>,
 }
 
@@ -19,6 +28,8 @@ impl axum::response::IntoResponse for ThreadListResponse {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ThreadListError {
+    #[error("invalid thread tag")]
+    InvalidThreadTag(#[source] crate::model::write::ThreadTagError),
     #[error("find")]
     ListThreads(#[source] crate::port::ThreadReaderError),
 }
@@ -26,6 +37,9 @@ pub enum ThreadListError {
 impl axum::response::IntoResponse for ThreadListError {
     fn into_response(self) -> axum::response::Response {
         match self {
+            ThreadListError::InvalidThreadTag(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
             ThreadListError::ListThreads(_) => {
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
             }
@@ -35,11 +49,18 @@ impl axum::response::IntoResponse for ThreadListError {
 
 pub async fn handler<S: ThreadReader>(
     State(state): State<S>,
+    Query(ThreadListQuery { tag }): Query<ThreadListQuery>,
 ) -> Result<ThreadListResponse, ThreadListError> {
+    let tag = tag
+        .filter(|it| !it.is_empty())
+        .map(|it| crate::model::write::ThreadTag::from_str(&it))
+        .transpose()
+        .map_err(ThreadListError::InvalidThreadTag)?;
     Ok(ThreadListResponse {
         threads: state
-            .list_threads()
+            .list_threads(None, tag.as_ref())
             .await
             .map_err(ThreadListError::ListThreads)?,
+        tag: tag.as_ref().map(ToString::to_string),
     })
 }
@@ -0,0 +1,104 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::moderator::Moderator;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadPinRequestBody {
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadPinResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadPinResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadPinError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("pin")]
+    Pin(#[source] crate::model::write::ThreadError),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadPinError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadPinError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadPinError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadPinError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadPinError::Pin(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            ThreadPinError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    _: Moderator,
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    Form(ThreadPinRequestBody { version }): Form<ThreadPinRequestBody>,
+) -> Result<ThreadPinResponseBody, ThreadPinError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadPinError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadPinError::Find)?
+        .ok_or_else(|| ThreadPinError::NotFound(thread_id))?;
+    let (_, events) = thread.pin().map_err(ThreadPinError::Pin)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadPinError::Store)?;
+
+    Ok(ThreadPinResponseBody {
+        id: thread.id().to_string(),
+    })
+}
@@ -0,0 +1,126 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::client_ip::ClientIp;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadReactRequestBody {
+    pub reaction: String,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadReactResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadReactResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadReactError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("invalid message reaction")]
+    InvalidMessageReaction(#[source] crate::model::write::MessageReactionError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("react")]
+    React(#[source] crate::model::write::ThreadError),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadReactError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadReactError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadReactError::InvalidMessageReaction(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadReactError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadReactError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadReactError::React(e) => match e {
+                crate::model::write::ThreadError::Closed => (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "the thread is closed and no longer accepts reactions",
+                )
+                    .into_response(),
+                _ => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            },
+            ThreadReactError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id, number)): Path<(String, u16)>,
+    State(state): State<S>,
+    State(config): State<Config>,
+    ClientIp(client_ip): ClientIp,
+    RequestMetadata(metadata): RequestMetadata,
+    Form(ThreadReactRequestBody { reaction, version }): Form<ThreadReactRequestBody>,
+) -> Result<ThreadReactResponseBody, ThreadReactError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let reaction = crate::model::write::MessageReaction::from_str(&reaction)
+        .map_err(ThreadReactError::InvalidMessageReaction)?;
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadReactError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadReactError::Find)?
+        .ok_or_else(|| ThreadReactError::NotFound(thread_id))?;
+    let (_, events) = thread
+        .react(
+            number,
+            reaction,
+            crate::model::write::ClientKey::from_ip(&client_ip, &config.key_secret),
+        )
+        .map_err(ThreadReactError::React)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadReactError::Store)?;
+
+    Ok(ThreadReactResponseBody {
+        id: thread.id().to_string(),
+    })
+}
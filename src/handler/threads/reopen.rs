@@ -0,0 +1,110 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::handler::requester::Requester;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadReopenRequestBody {
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadReopenResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadReopenResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadReopenError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("reopen")]
+    Reopen(#[source] crate::model::write::ThreadError),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadReopenError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadReopenError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadReopenError::Forbidden => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ThreadReopenError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadReopenError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadReopenError::Reopen(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            ThreadReopenError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    requester: Requester,
+    Form(ThreadReopenRequestBody { version }): Form<ThreadReopenRequestBody>,
+) -> Result<ThreadReopenResponseBody, ThreadReopenError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadReopenError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadReopenError::Find)?
+        .ok_or_else(|| ThreadReopenError::NotFound(thread_id))?;
+    if !requester.is_owner_of(&thread) {
+        return Err(ThreadReopenError::Forbidden);
+    }
+    let (_, events) = thread.reopen().map_err(ThreadReopenError::Reopen)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadReopenError::Store)?;
+
+    Ok(ThreadReopenResponseBody {
+        id: thread.id().to_string(),
+    })
+}
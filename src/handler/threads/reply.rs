@@ -1,14 +1,30 @@
 use std::str::FromStr as _;
 
 use axum::extract::Path;
-use axum::extract::{Form, State};
+use axum::extract::State;
 
+use crate::config::Config;
+use crate::handler::attachments::FormWithAttachments;
+use crate::handler::client_ip::ClientIp;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::port::BlobStore;
+use crate::port::BoardRepository;
+use crate::port::ContentPolicyReader;
+use crate::port::PostedMessageReader;
+use crate::port::RateLimiter;
+use crate::port::RateLimiterError;
 use crate::port::ThreadRepository;
 use crate::port::ThreadRepositoryError;
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct ThreadReplyRequestBody {
     pub content: String,
+    /// `name` or `name#secret`
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Replies without bumping the thread
+    #[serde(default)]
+    pub sage: bool,
     pub version: u32,
 }
 
@@ -34,33 +50,105 @@ impl axum::response::IntoResponse for ThreadReplyResponseBody {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ThreadReplyError {
+    #[error("board not found {0:?}")]
+    BoardNotFound(crate::model::shared::id::BoardId),
+    #[error("check message")]
+    CheckMessage(#[source] crate::model::write::BoardError),
+    #[error("content policy")]
+    ContentPolicy(#[source] crate::model::write::ContentPolicyViolation),
+    #[error("continue")]
+    Continue(#[source] crate::model::write::ThreadError),
+    #[error("duplicate message")]
+    DuplicateMessage(#[source] crate::model::write::DuplicateMessage),
     #[error("find")]
     Find(#[source] ThreadRepositoryError),
+    #[error("find board")]
+    FindBoard(#[source] crate::port::BoardRepositoryError),
+    #[error("get content policy")]
+    GetContentPolicy(#[source] crate::port::ContentPolicyReaderError),
     #[error("invalid message content")]
     InvalidMessageContent(#[source] crate::model::write::MessageContentError),
+    #[error("invalid poster")]
+    InvalidPoster(#[source] crate::model::write::PosterError),
     #[error("invalid thread id")]
     InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("list posted messages")]
+    ListPostedMessages(#[source] crate::port::PostedMessageReaderError),
     #[error("not found {0:?}")]
     NotFound(crate::model::shared::id::ThreadId),
+    #[error("prepare attachments")]
+    PrepareAttachments(#[source] crate::handler::attachments::PrepareAttachmentsError),
+    #[error("rate limit")]
+    RateLimit(#[source] RateLimiterError),
     #[error("reply")]
     Reply(#[source] crate::model::write::ThreadError),
     #[error("store")]
     Store(#[source] ThreadRepositoryError),
+    #[error("store attachments")]
+    StoreAttachments(#[source] crate::port::BlobStoreError),
 }
 
 impl axum::response::IntoResponse for ThreadReplyError {
     fn into_response(self) -> axum::response::Response {
         match self {
+            ThreadReplyError::BoardNotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadReplyError::CheckMessage(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadReplyError::ContentPolicy(e) => {
+                (axum::http::StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            }
+            ThreadReplyError::Continue(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            ThreadReplyError::DuplicateMessage(e) => {
+                (axum::http::StatusCode::CONFLICT, e.to_string()).into_response()
+            }
             ThreadReplyError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadReplyError::FindBoard(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            ThreadReplyError::GetContentPolicy(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
             ThreadReplyError::InvalidMessageContent(_) => {
                 axum::http::StatusCode::BAD_REQUEST.into_response()
             }
+            ThreadReplyError::InvalidPoster(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
             ThreadReplyError::InvalidThreadId(_) => {
                 axum::http::StatusCode::BAD_REQUEST.into_response()
             }
+            ThreadReplyError::ListPostedMessages(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
             ThreadReplyError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
-            ThreadReplyError::Reply(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            ThreadReplyError::PrepareAttachments(e) => e.into_response(),
+            ThreadReplyError::RateLimit(e) => match e {
+                RateLimiterError::Exceeded(e) => (
+                    axum::http::StatusCode::TOO_MANY_REQUESTS,
+                    [(
+                        axum::http::header::RETRY_AFTER,
+                        e.retry_after.as_secs().to_string(),
+                    )],
+                    "too many messages, please wait and try again",
+                )
+                    .into_response(),
+                RateLimiterError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+            },
+            ThreadReplyError::Reply(e) => match e {
+                crate::model::write::ThreadError::Closed => (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "the thread is closed and no longer accepts replies",
+                )
+                    .into_response(),
+                _ => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            },
             ThreadReplyError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
                 ThreadRepositoryError::InternalError(_) => {
                     axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
                 }
@@ -71,30 +159,120 @@ impl axum::response::IntoResponse for ThreadReplyError {
                     axum::http::StatusCode::CONFLICT.into_response()
                 }
             },
+            ThreadReplyError::StoreAttachments(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
         }
     }
 }
 
-pub async fn handler<S: ThreadRepository>(
+pub async fn handler<
+    S: BlobStore
+        + BoardRepository
+        + ContentPolicyReader
+        + PostedMessageReader
+        + RateLimiter
+        + ThreadRepository,
+>(
     Path((thread_id,)): Path<(String,)>,
     State(state): State<S>,
-    Form(ThreadReplyRequestBody { content, version }): Form<ThreadReplyRequestBody>,
-) -> Result<ThreadReplyResponseBody, ThreadReplyError> {
-    let content = crate::model::write::MessageContent::try_from(content)
-        .map_err(ThreadReplyError::InvalidMessageContent)?;
+    State(config): State<Config>,
+    ClientIp(client_ip): ClientIp,
+    RequestMetadata(metadata): RequestMetadata,
+    FormWithAttachments {
+        attachments,
+        form:
+            ThreadReplyRequestBody {
+                content,
+                name,
+                sage,
+                version,
+            },
+    }: FormWithAttachments<ThreadReplyRequestBody>,
+) -> Result<ThreadReplyResponseBody, ThreadReplyError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let content =
+        crate::model::write::MessageContent::new(content, config.max_message_content_length)
+            .map_err(ThreadReplyError::InvalidMessageContent)?;
     let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
         .map_err(ThreadReplyError::InvalidThreadId)?;
     let version = crate::model::write::Version::from(version);
-    let message = crate::model::write::Message::create(content);
+    let poster = name
+        .filter(|it| !it.trim().is_empty())
+        .map(crate::model::write::Poster::try_from)
+        .transpose()
+        .map_err(ThreadReplyError::InvalidPoster)?;
+    ContentPolicyReader::get_content_policy(&state)
+        .await
+        .map_err(ThreadReplyError::GetContentPolicy)?
+        .check(&content)
+        .map_err(ThreadReplyError::ContentPolicy)?;
+    let client_key = crate::model::write::ClientKey::from_ip(&client_ip, &config.key_secret);
+    let now = crate::utils::date_time::DateTime::now();
+    let posted = PostedMessageReader::list_posted_messages(
+        &state,
+        &crate::model::write::ContentFingerprint::of(&content),
+        config.duplicate_policy.since(now),
+    )
+    .await
+    .map_err(ThreadReplyError::ListPostedMessages)?;
+    config
+        .duplicate_policy
+        .check(&content, &posted, &client_key, Some(&thread_id), now)
+        .map_err(ThreadReplyError::DuplicateMessage)?;
+    let attachments = crate::handler::attachments::prepare_attachments(attachments)
+        .await
+        .map_err(ThreadReplyError::PrepareAttachments)?;
+    let message = crate::model::write::Message::create(
+        content,
+        poster,
+        attachments.iter().map(|it| it.attachment.clone()).collect(),
+        Some(client_key.clone()),
+    );
 
     let thread = ThreadRepository::find(&state, &thread_id)
         .await
         .map_err(ThreadReplyError::Find)?
         .ok_or_else(|| ThreadReplyError::NotFound(thread_id))?;
-    let (_, events) = thread
-        .reply(message.clone())
-        .map_err(ThreadReplyError::Reply)?;
-    ThreadRepository::store(&state, Some(version), &events)
+    let board = BoardRepository::find_board(&state, thread.board_id())
+        .await
+        .map_err(ThreadReplyError::FindBoard)?
+        .ok_or_else(|| ThreadReplyError::BoardNotFound(thread.board_id().clone()))?;
+    board
+        .check_message(&message)
+        .map_err(ThreadReplyError::CheckMessage)?;
+    let replied = match thread.reply(message.clone(), sage, config.max_thread_messages) {
+        Ok((_, events)) => Some(events),
+        Err(crate::model::write::ThreadError::Filled) => None,
+        Err(e) => return Err(ThreadReplyError::Reply(e)),
+    };
+    // only the replies that pass the validations count toward the limit
+    RateLimiter::consume_rate_limit(
+        &state,
+        crate::model::write::RateLimitAction::Reply,
+        &client_key,
+        &config.reply_rate_limit,
+    )
+    .await
+    .map_err(ThreadReplyError::RateLimit)?;
+    let Some(events) = replied else {
+        return continue_thread(
+            &state,
+            &thread,
+            message,
+            sage,
+            config.max_thread_messages,
+            &attachments,
+            &metadata,
+        )
+        .await;
+    };
+    crate::handler::attachments::store_attachments(&state, &attachments)
+        .await
+        .map_err(ThreadReplyError::StoreAttachments)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
         .await
         .map_err(ThreadReplyError::Store)?;
 
@@ -102,3 +280,84 @@ pub async fn handler<S: ThreadRepository>(
         id: thread.id().to_string(),
     })
 }
+
+/// Posts the message into the next thread of the filled thread, creating it if needed.
+async fn continue_thread<S: BlobStore + ThreadRepository>(
+    state: &S,
+    thread: &crate::model::write::Thread,
+    message: crate::model::write::Message,
+    sage: bool,
+    max_messages: u16,
+    attachments: &[crate::handler::attachments::PreparedAttachment],
+    metadata: &crate::model::shared::event_envelope::ThreadEventMetadata,
+) -> Result<ThreadReplyResponseBody, ThreadReplyError> {
+    let mut thread = thread.clone();
+    loop {
+        // follows the chain of continuations up to the last thread
+        while let Some(next_thread_id) = thread.next_thread_id().cloned() {
+            let next = ThreadRepository::find(state, &next_thread_id)
+                .await
+                .map_err(ThreadReplyError::Find)?
+                .ok_or_else(|| ThreadReplyError::NotFound(next_thread_id))?;
+            match next.reply(message.clone(), sage, max_messages) {
+                Ok((_, events)) => {
+                    crate::handler::attachments::store_attachments(state, attachments)
+                        .await
+                        .map_err(ThreadReplyError::StoreAttachments)?;
+                    ThreadRepository::store(
+                        state,
+                        Some(next.version()),
+                        &metadata.envelop(&events),
+                    )
+                    .await
+                    .map_err(ThreadReplyError::Store)?;
+                    return Ok(ThreadReplyResponseBody {
+                        id: next.id().to_string(),
+                    });
+                }
+                Err(crate::model::write::ThreadError::Filled) => thread = next,
+                Err(e) => return Err(ThreadReplyError::Reply(e)),
+            }
+        }
+
+        let ((_, continued_events), (next, next_events)) = thread
+            .continue_thread(thread.title().clone(), message.clone())
+            .map_err(ThreadReplyError::Continue)?;
+        crate::handler::attachments::store_attachments(state, attachments)
+            .await
+            .map_err(ThreadReplyError::StoreAttachments)?;
+        // the next thread is caused by the continuation of the filled thread
+        let next_metadata = crate::model::shared::event_envelope::ThreadEventMetadata {
+            causation_id: continued_events.last().map(|it| it.id().to_string()),
+            ..metadata.clone()
+        };
+        let next_envelopes = next_metadata.envelop(&next_events);
+        let continued_envelopes = metadata.envelop(&continued_events);
+        // the next thread is stored together with the link to it so that neither is left alone,
+        // and the requested version is not checked because the thread has been filled by other
+        // replies
+        match ThreadRepository::store_all(
+            state,
+            &[
+                (None, next_envelopes.as_slice()),
+                (Some(thread.version()), continued_envelopes.as_slice()),
+            ],
+        )
+        .await
+        {
+            Ok(()) => {
+                return Ok(ThreadReplyResponseBody {
+                    id: next.id().to_string(),
+                });
+            }
+            // the thread has been continued by a concurrent reply, so the reply follows it
+            Err(ThreadRepositoryError::VersionMismatch { .. }) => {
+                thread = ThreadRepository::find(state, thread.id())
+                    .await
+                    .map_err(ThreadReplyError::Find)?
+                    .ok_or_else(|| ThreadReplyError::NotFound(thread.id().clone()))?;
+            }
+            Err(e) => return Err(ThreadReplyError::Store(e)),
+        }
+    }
+}
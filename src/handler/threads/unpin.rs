@@ -0,0 +1,104 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::moderator::Moderator;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadUnpinRequestBody {
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadUnpinResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadUnpinResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadUnpinError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+    #[error("unpin")]
+    Unpin(#[source] crate::model::write::ThreadError),
+}
+
+impl axum::response::IntoResponse for ThreadUnpinError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadUnpinError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadUnpinError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadUnpinError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadUnpinError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+            ThreadUnpinError::Unpin(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    _: Moderator,
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    Form(ThreadUnpinRequestBody { version }): Form<ThreadUnpinRequestBody>,
+) -> Result<ThreadUnpinResponseBody, ThreadUnpinError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadUnpinError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadUnpinError::Find)?
+        .ok_or_else(|| ThreadUnpinError::NotFound(thread_id))?;
+    let (_, events) = thread.unpin().map_err(ThreadUnpinError::Unpin)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadUnpinError::Store)?;
+
+    Ok(ThreadUnpinResponseBody {
+        id: thread.id().to_string(),
+    })
+}
@@ -0,0 +1,126 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::client_ip::ClientIp;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadUnreactRequestBody {
+    pub reaction: String,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadUnreactResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadUnreactResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadUnreactError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("invalid message reaction")]
+    InvalidMessageReaction(#[source] crate::model::write::MessageReactionError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("unreact")]
+    Unreact(#[source] crate::model::write::ThreadError),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+}
+
+impl axum::response::IntoResponse for ThreadUnreactError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadUnreactError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadUnreactError::InvalidMessageReaction(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadUnreactError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadUnreactError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadUnreactError::Unreact(e) => match e {
+                crate::model::write::ThreadError::Closed => (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "the thread is closed and no longer accepts reactions",
+                )
+                    .into_response(),
+                _ => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            },
+            ThreadUnreactError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id, number)): Path<(String, u16)>,
+    State(state): State<S>,
+    State(config): State<Config>,
+    ClientIp(client_ip): ClientIp,
+    RequestMetadata(metadata): RequestMetadata,
+    Form(ThreadUnreactRequestBody { reaction, version }): Form<ThreadUnreactRequestBody>,
+) -> Result<ThreadUnreactResponseBody, ThreadUnreactError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let reaction = crate::model::write::MessageReaction::from_str(&reaction)
+        .map_err(ThreadUnreactError::InvalidMessageReaction)?;
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadUnreactError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadUnreactError::Find)?
+        .ok_or_else(|| ThreadUnreactError::NotFound(thread_id))?;
+    let (_, events) = thread
+        .unreact(
+            number,
+            reaction,
+            crate::model::write::ClientKey::from_ip(&client_ip, &config.key_secret),
+        )
+        .map_err(ThreadUnreactError::Unreact)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadUnreactError::Store)?;
+
+    Ok(ThreadUnreactResponseBody {
+        id: thread.id().to_string(),
+    })
+}
@@ -0,0 +1,118 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::handler::requester::Requester;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadUntagRequestBody {
+    pub tag: String,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadUntagResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadUntagResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadUntagError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("forbidden")]
+    Forbidden,
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("invalid thread tag")]
+    InvalidThreadTag(#[source] crate::model::write::ThreadTagError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+    #[error("untag")]
+    Untag(#[source] crate::model::write::ThreadError),
+}
+
+impl axum::response::IntoResponse for ThreadUntagError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadUntagError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadUntagError::Forbidden => axum::http::StatusCode::FORBIDDEN.into_response(),
+            ThreadUntagError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadUntagError::InvalidThreadTag(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadUntagError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadUntagError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+            ThreadUntagError::Untag(_) => axum::http::StatusCode::BAD_REQUEST.into_response(),
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    RequestMetadata(metadata): RequestMetadata,
+    requester: Requester,
+    Form(ThreadUntagRequestBody { tag, version }): Form<ThreadUntagRequestBody>,
+) -> Result<ThreadUntagResponseBody, ThreadUntagError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let tag = crate::model::write::ThreadTag::from_str(&tag.trim().to_lowercase())
+        .map_err(ThreadUntagError::InvalidThreadTag)?;
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadUntagError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadUntagError::Find)?
+        .ok_or_else(|| ThreadUntagError::NotFound(thread_id))?;
+    if !requester.is_owner_of(&thread) {
+        return Err(ThreadUntagError::Forbidden);
+    }
+    let (_, events) = thread.untag(tag).map_err(ThreadUntagError::Untag)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadUntagError::Store)?;
+
+    Ok(ThreadUntagResponseBody {
+        id: thread.id().to_string(),
+    })
+}
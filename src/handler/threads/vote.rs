@@ -0,0 +1,124 @@
+use std::str::FromStr as _;
+
+use axum::extract::Path;
+use axum::extract::{Form, State};
+
+use crate::config::Config;
+use crate::handler::client_ip::ClientIp;
+use crate::handler::request_metadata::RequestMetadata;
+use crate::port::ThreadRepository;
+use crate::port::ThreadRepositoryError;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct ThreadVoteRequestBody {
+    /// The 0-based index of the option
+    pub option: u8,
+    pub version: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct ThreadVoteResponseBody {
+    pub id: String,
+}
+
+impl axum::response::IntoResponse for ThreadVoteResponseBody {
+    fn into_response(self) -> axum::response::Response {
+        let location = format!("/threads/{}", self.id);
+        axum::response::Response::builder()
+            .status(axum::http::StatusCode::SEE_OTHER)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(axum::http::header::LOCATION, location)
+            .body(axum::body::Body::empty())
+            .expect("failed to build response")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadVoteError {
+    #[error("find")]
+    Find(#[source] ThreadRepositoryError),
+    #[error("invalid thread id")]
+    InvalidThreadId(#[source] crate::model::shared::id::ThreadIdError),
+    #[error("not found {0:?}")]
+    NotFound(crate::model::shared::id::ThreadId),
+    #[error("store")]
+    Store(#[source] ThreadRepositoryError),
+    #[error("vote")]
+    Vote(#[source] crate::model::write::ThreadError),
+}
+
+impl axum::response::IntoResponse for ThreadVoteError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ThreadVoteError::Find(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadVoteError::InvalidThreadId(_) => {
+                axum::http::StatusCode::BAD_REQUEST.into_response()
+            }
+            ThreadVoteError::NotFound(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+            ThreadVoteError::Store(e) => match e {
+                ThreadRepositoryError::Corrupted { .. } => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::InternalError(_) => {
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+                }
+                ThreadRepositoryError::NotFound(_) => {
+                    axum::http::StatusCode::NOT_FOUND.into_response()
+                }
+                ThreadRepositoryError::VersionMismatch { .. } => {
+                    axum::http::StatusCode::CONFLICT.into_response()
+                }
+            },
+            ThreadVoteError::Vote(e) => match e {
+                crate::model::write::ThreadError::Closed => (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "the thread is closed and no longer accepts votes",
+                )
+                    .into_response(),
+                crate::model::write::ThreadError::PollClosed => (
+                    axum::http::StatusCode::FORBIDDEN,
+                    "the poll is closed and no longer accepts votes",
+                )
+                    .into_response(),
+                _ => axum::http::StatusCode::BAD_REQUEST.into_response(),
+            },
+        }
+    }
+}
+
+pub async fn handler<S: ThreadRepository>(
+    Path((thread_id,)): Path<(String,)>,
+    State(state): State<S>,
+    State(config): State<Config>,
+    ClientIp(client_ip): ClientIp,
+    RequestMetadata(metadata): RequestMetadata,
+    Form(ThreadVoteRequestBody { option, version }): Form<ThreadVoteRequestBody>,
+) -> Result<ThreadVoteResponseBody, ThreadVoteError>
+where
+    Config: axum::extract::FromRef<S>,
+{
+    let thread_id = crate::model::shared::id::ThreadId::from_str(&thread_id)
+        .map_err(ThreadVoteError::InvalidThreadId)?;
+    let version = crate::model::write::Version::from(version);
+
+    let thread = ThreadRepository::find(&state, &thread_id)
+        .await
+        .map_err(ThreadVoteError::Find)?
+        .ok_or_else(|| ThreadVoteError::NotFound(thread_id))?;
+    let (_, events) = thread
+        .vote(
+            option,
+            crate::model::write::VoterKey::from_ip(&client_ip, &config.key_secret),
+        )
+        .map_err(ThreadVoteError::Vote)?;
+    ThreadRepository::store(&state, Some(version), &metadata.envelop(&events))
+        .await
+        .map_err(ThreadVoteError::Store)?;
+
+    Ok(ThreadVoteResponseBody {
+        id: thread.id().to_string(),
+    })
+}
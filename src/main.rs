@@ -1,4 +1,5 @@
 mod app_state;
+mod config;
 mod handler;
 mod model;
 mod port;
@@ -8,11 +9,46 @@ mod utils;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::app_state::AppState;
+use crate::config::Config;
 
 #[derive(clap::Parser)]
 struct Cli {
+    #[clap(long, env = "BBBS_BLOB_DIR", default_value = "./bbbs-blobs")]
+    blob_dir: std::path::PathBuf,
+    /// The JSON file of the content policy, reloaded when modified
+    #[clap(long, env = "BBBS_CONTENT_POLICY")]
+    content_policy: Option<std::path::PathBuf>,
+    /// `{count}/{seconds}` (e.g. `3/600`)
+    #[clap(long, env = "BBBS_CREATE_THREAD_RATE_LIMIT")]
+    create_thread_rate_limit: Option<crate::model::write::RateLimit>,
+    /// The number of threads with the same message after which it is rejected as cross-posting
+    #[clap(long)]
+    cross_post_max_threads: Option<usize>,
+    #[clap(long)]
+    cross_post_window_secs: Option<u64>,
+    /// The duration during which the same client cannot post the same message to the same thread
+    #[clap(long)]
+    duplicate_window_secs: Option<u64>,
+    /// The secret with which the client and voter keys are derived (keep it across restarts)
+    #[clap(long, env = "BBBS_KEY_SECRET")]
+    key_secret: String,
+    #[clap(long)]
+    max_message_content_length: Option<usize>,
+    /// The number of messages at which a thread is filled
+    #[clap(long)]
+    max_thread_messages: Option<u16>,
+    #[clap(long)]
+    message_edit_window_secs: Option<u64>,
+    #[clap(long, env = "BBBS_MODERATOR_TOKEN")]
+    moderator_token: Option<String>,
     #[clap(long)]
     port: Option<u16>,
+    /// `{count}/{seconds}` (e.g. `10/60`)
+    #[clap(long, env = "BBBS_REPLY_RATE_LIMIT")]
+    reply_rate_limit: Option<crate::model::write::RateLimit>,
+    /// The header set by the reverse proxy, e.g. `X-Forwarded-For` (only if behind one)
+    #[clap(long, env = "BBBS_TRUSTED_PROXY_HEADER")]
+    trusted_proxy_header: Option<String>,
 }
 
 #[tokio::main]
@@ -23,27 +59,64 @@ async fn main() {
         .init();
     let cli = <Cli as clap::Parser>::parse();
     let port = cli.port.unwrap_or(3000);
-
-    let router = handler::router().with_state(AppState::new().await).layer(
-        tower_http::trace::TraceLayer::new_for_http().make_span_with(
-            |request: &axum::http::Request<axum::body::Body>| {
-                let matched_path = request
-                    .extensions()
-                    .get::<axum::extract::MatchedPath>()
-                    .map(axum::extract::MatchedPath::as_str);
-                tracing::info_span!(
-                    "http_request",
-                    matched_path,
-                    method = ?request.method(),
-                    uri = ?request.uri(),
-                )
-            },
+    let default_config = Config::default();
+    let config = Config {
+        create_thread_rate_limit: cli
+            .create_thread_rate_limit
+            .unwrap_or(default_config.create_thread_rate_limit),
+        duplicate_policy: crate::model::write::DuplicatePolicy::new(
+            cli.cross_post_max_threads
+                .unwrap_or(default_config.duplicate_policy.cross_post_max_threads()),
+            cli.cross_post_window_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(default_config.duplicate_policy.cross_post_window()),
+            cli.duplicate_window_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(default_config.duplicate_policy.duplicate_window()),
         ),
-    );
+        key_secret: cli.key_secret,
+        message_edit_window: cli
+            .message_edit_window_secs
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(default_config.message_edit_window),
+        max_message_content_length: cli
+            .max_message_content_length
+            .unwrap_or(default_config.max_message_content_length),
+        max_thread_messages: cli
+            .max_thread_messages
+            .unwrap_or(default_config.max_thread_messages),
+        moderator_token: cli.moderator_token,
+        reply_rate_limit: cli
+            .reply_rate_limit
+            .unwrap_or(default_config.reply_rate_limit),
+        trusted_proxy_header: cli.trusted_proxy_header,
+    };
+
+    let router = handler::router()
+        .with_state(AppState::new(config, cli.blob_dir, cli.content_policy).await)
+        .layer(
+            tower_http::trace::TraceLayer::new_for_http().make_span_with(
+                |request: &axum::http::Request<axum::body::Body>| {
+                    let matched_path = request
+                        .extensions()
+                        .get::<axum::extract::MatchedPath>()
+                        .map(axum::extract::MatchedPath::as_str);
+                    tracing::info_span!(
+                        "http_request",
+                        matched_path,
+                        method = ?request.method(),
+                        uri = ?request.uri(),
+                    )
+                },
+            ),
+        );
     let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
         .await
         .unwrap();
-    axum::serve(listener, router.into_make_service())
-        .await
-        .unwrap()
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .unwrap()
 }
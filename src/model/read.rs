@@ -1,6 +1,20 @@
+mod anchor;
+mod board;
 mod message;
+mod message_attachment;
+mod message_reaction;
+mod message_revision;
+mod poll;
 mod thread;
+mod thread_tag;
 
+pub use self::anchor::parse_anchors;
+pub use self::board::Board;
 pub use self::message::Message;
+pub use self::message_attachment::MessageAttachment;
+pub use self::message_reaction::MessageReaction;
+pub use self::message_revision::MessageRevision;
+pub use self::poll::{Poll, PollOption};
 pub use self::thread::Thread;
 pub use self::thread::ThreadWithoutMessages;
+pub use self::thread_tag::ThreadTag;
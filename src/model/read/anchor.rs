@@ -0,0 +1,78 @@
+/// Parses `>>N` and `>>N-M` anchors in the content of the message numbered `number`
+///
+/// Returns the sorted and deduplicated numbers of the earlier messages. Anchors to the message
+/// itself or to later messages are ignored, and a range is cut to its first `MAX_RANGE_LEN`
+/// messages.
+pub fn parse_anchors(content: &str, number: u16) -> Vec<u16> {
+    let mut anchors = vec![];
+    let mut rest = content;
+    while let Some(index) = rest.find(">>") {
+        rest = &rest[index + 2..];
+        let Some((start, after_start)) = parse_number(rest) else {
+            continue;
+        };
+        rest = after_start;
+        let end = match rest.strip_prefix('-').and_then(parse_number) {
+            Some((end, after_end)) => {
+                rest = after_end;
+                end
+            }
+            None => start,
+        };
+        if start == 0 || start > end {
+            continue;
+        }
+        let end = end
+            .min(start + MAX_RANGE_LEN - 1)
+            .min(u32::from(number).saturating_sub(1));
+        for anchor in start..=end {
+            anchors.push(u16::try_from(anchor).expect("anchor to be less than number"));
+        }
+    }
+    anchors.sort_unstable();
+    anchors.dedup();
+    anchors
+}
+
+/// The maximum number of messages that a `>>N-M` anchor refers to
+const MAX_RANGE_LEN: u32 = 10;
+
+fn parse_number(s: &str) -> Option<(u32, &str)> {
+    let len = s.bytes().take_while(u8::is_ascii_digit).count();
+    // more than 5 digits can not be a message number
+    if len == 0 || len > 5 {
+        return None;
+    }
+    let (digits, rest) = s.split_at(len);
+    Some((digits.parse().ok()?, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_anchors() {
+        assert_eq!(parse_anchors("hello", 10), Vec::<u16>::new());
+        assert_eq!(parse_anchors(">>1", 10), vec![1]);
+        assert_eq!(parse_anchors(">>2 >>1\n>>2", 10), vec![1, 2]);
+        assert_eq!(parse_anchors(">>3-5", 10), vec![3, 4, 5]);
+        assert_eq!(parse_anchors("see >>1,>>4-5 and >>7", 10), vec![1, 4, 5, 7]);
+        // the message itself and later messages
+        assert_eq!(parse_anchors(">>10 >>11", 10), Vec::<u16>::new());
+        assert_eq!(parse_anchors(">>8-12", 10), vec![8, 9]);
+        // oversized ranges
+        assert_eq!(
+            parse_anchors(">>1-65535", 1000),
+            (1..=10).collect::<Vec<u16>>()
+        );
+        assert_eq!(
+            parse_anchors(">>995-99999", 1000),
+            vec![995, 996, 997, 998, 999]
+        );
+        // invalid
+        assert_eq!(parse_anchors(">>0 >> 1 >>x >>5-3", 10), Vec::<u16>::new());
+        assert_eq!(parse_anchors(">>123456", 10), Vec::<u16>::new());
+        assert_eq!(parse_anchors(">>>>2", 10), vec![2]);
+    }
+}
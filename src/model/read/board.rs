@@ -0,0 +1,20 @@
+#[derive(Clone)]
+pub struct Board {
+    pub description: String,
+    pub id: String,
+    pub max_content_length: u16,
+    pub max_threads: u16,
+    pub name: String,
+}
+
+impl Board {
+    /// Lowers the max content length of the board to the configured one, which applies to every board
+    pub fn with_max_content_length(self, max_content_length: usize) -> Self {
+        Self {
+            max_content_length: self
+                .max_content_length
+                .min(u16::try_from(max_content_length).unwrap_or(u16::MAX)),
+            ..self
+        }
+    }
+}
@@ -1,6 +1,27 @@
+use crate::model::read::MessageAttachment;
+use crate::model::read::MessageReaction;
+use crate::model::read::MessageRevision;
+
 #[derive(Clone)]
 pub struct Message {
+    /// The numbers of the earlier messages referenced by `>>N` anchors in the content
+    pub anchors: Vec<u16>,
+    /// The attached images (empty if the message has been deleted)
+    pub attachments: Vec<MessageAttachment>,
+    /// The content of the message (empty if the message has been deleted)
     pub content: String,
     pub created_at: String,
+    pub deleted_at: Option<String>,
+    pub deletion_reason: Option<String>,
+    pub edited_at: Option<String>,
+    /// The display name of the poster
+    pub name: Option<String>,
     pub number: u16,
+    /// The reaction counts sorted by the reaction code
+    pub reactions: Vec<MessageReaction>,
+    /// The numbers of the later messages referencing this message
+    pub replied_by: Vec<u16>,
+    pub revisions: Vec<MessageRevision>,
+    /// The trip derived from the secret of the poster
+    pub trip: Option<String>,
 }
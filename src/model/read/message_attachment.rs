@@ -0,0 +1,8 @@
+/// An image attached to a message, served from `/blobs/{hash}`
+#[derive(Clone)]
+pub struct MessageAttachment {
+    pub hash: String,
+    pub mime: String,
+    pub size: u32,
+    pub thumbnail_hash: String,
+}
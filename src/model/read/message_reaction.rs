@@ -0,0 +1,6 @@
+/// The number of reactions of a kind to a message
+#[derive(Clone)]
+pub struct MessageReaction {
+    pub code: String,
+    pub count: u32,
+}
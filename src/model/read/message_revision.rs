@@ -0,0 +1,6 @@
+/// A previous content of an edited message
+#[derive(Clone)]
+pub struct MessageRevision {
+    pub content: String,
+    pub created_at: String,
+}
@@ -0,0 +1,15 @@
+/// A poll of a thread with the live tallies
+#[derive(Clone)]
+pub struct Poll {
+    pub closes_at: Option<String>,
+    pub options: Vec<PollOption>,
+    pub question: String,
+    pub votes_count: u32,
+}
+
+/// The number of votes for an option of a poll
+#[derive(Clone)]
+pub struct PollOption {
+    pub count: u32,
+    pub label: String,
+}
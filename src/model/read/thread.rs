@@ -1,36 +1,76 @@
 use crate::model::{
-    read::Message,
-    shared::event::{ThreadCreated, ThreadEvent, ThreadReplied},
+    read::{
+        Message, MessageAttachment, MessageReaction, MessageRevision, Poll, PollOption,
+        parse_anchors,
+    },
+    shared::event::{
+        ThreadClosed, ThreadContinued, ThreadCreated, ThreadEvent, ThreadFilled,
+        ThreadMessageAttachment, ThreadMessageDeleted, ThreadMessageEdited, ThreadMessageReacted,
+        ThreadMessageUnreacted, ThreadPinned, ThreadPollVoted, ThreadReopened, ThreadReplied,
+        ThreadTagged, ThreadTitleChanged, ThreadUnpinned, ThreadUntagged,
+    },
+    shared::id::ThreadId,
+    shared::replay::{ReplayError, check_next},
 };
 
 #[derive(Clone)]
 pub struct ThreadWithoutMessages {
+    pub board_id: String,
+    /// The time of the last non-sage message
+    pub bumped_at: String,
+    pub closed: bool,
     pub created_at: String,
+    pub filled: bool,
     pub first_message: Message,
     pub id: String,
     pub last_message: Message,
+    pub next_thread_id: Option<String>,
+    pub pinned: bool,
+    pub previous_thread_id: Option<String>,
     pub replies_count: u16,
+    /// The sorted tags
+    pub tags: Vec<String>,
+    pub title: String,
     pub version: u32,
 }
 
 impl From<Thread> for ThreadWithoutMessages {
     fn from(
         Thread {
+            board_id,
+            bumped_at,
+            closed,
             created_at,
+            filled,
             first_message,
             id,
             last_message,
             messages: _,
+            next_thread_id,
+            pinned,
+            poll: _,
+            previous_thread_id,
             replies_count,
+            tags,
+            title,
             version,
         }: Thread,
     ) -> Self {
         Self {
+            board_id,
+            bumped_at,
+            closed,
             created_at,
+            filled,
             first_message,
             id,
             last_message,
+            next_thread_id,
+            pinned,
+            previous_thread_id,
             replies_count,
+            tags,
+            title,
             version,
         }
     }
@@ -38,83 +78,494 @@ impl From<Thread> for ThreadWithoutMessages {
 
 #[derive(Clone)]
 pub struct Thread {
+    pub board_id: String,
+    /// The time of the last non-sage message
+    pub bumped_at: String,
+    pub closed: bool,
     pub created_at: String,
+    pub filled: bool,
     pub first_message: Message,
     pub id: String,
     pub last_message: Message,
     pub messages: Vec<Message>,
+    pub next_thread_id: Option<String>,
+    pub pinned: bool,
+    pub poll: Option<Poll>,
+    pub previous_thread_id: Option<String>,
     pub replies_count: u16,
+    /// The sorted tags
+    pub tags: Vec<String>,
+    pub title: String,
     pub version: u32,
 }
 
 impl Thread {
-    pub fn replay(events: Vec<ThreadEvent>) -> Self {
+    pub fn replay(events: Vec<ThreadEvent>) -> Result<Self, ReplayError> {
         let mut iter = events.into_iter();
 
-        let first_event = iter.next().expect("events not to be empty");
+        let first_event = iter.next().ok_or(ReplayError::Empty)?;
         let mut thread = match first_event {
             ThreadEvent::Created(ThreadCreated {
                 at,
+                attachments,
+                board_id,
+                client_key: _,
                 content,
+                fingerprint: _,
                 id: _,
+                name,
+                poll,
+                previous_thread_id,
+                tags,
                 thread_id,
+                title,
+                trip,
                 version,
             }) => Self {
-                created_at: at.clone(),
+                board_id: board_id.unwrap_or_default().to_string(),
+                bumped_at: at.to_string(),
+                closed: false,
+                created_at: at.to_string(),
+                filled: false,
                 first_message: Message {
+                    anchors: vec![],
+                    attachments: Self::attachments_from_event(attachments.clone()),
                     content: content.clone(),
-                    created_at: at.clone(),
+                    created_at: at.to_string(),
+                    deleted_at: None,
+                    deletion_reason: None,
+                    edited_at: None,
+                    name: name.clone(),
                     number: 1,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: trip.clone(),
                 },
-                id: thread_id.clone(),
+                id: thread_id.to_string(),
                 last_message: Message {
+                    anchors: vec![],
+                    attachments: Self::attachments_from_event(attachments.clone()),
                     content: content.clone(),
-                    created_at: at.clone(),
+                    created_at: at.to_string(),
+                    deleted_at: None,
+                    deletion_reason: None,
+                    edited_at: None,
+                    name: name.clone(),
                     number: 1,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: trip.clone(),
                 },
                 messages: vec![Message {
+                    anchors: vec![],
+                    attachments: Self::attachments_from_event(attachments),
                     content,
-                    created_at: at,
+                    created_at: at.to_string(),
+                    deleted_at: None,
+                    deletion_reason: None,
+                    edited_at: None,
+                    name,
                     number: 1,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip,
                 }],
+                next_thread_id: None,
+                pinned: false,
+                poll: poll.map(|it| Poll {
+                    closes_at: it.closes_at.map(|it| it.to_string()),
+                    options: it
+                        .options
+                        .into_iter()
+                        .map(|label| PollOption { count: 0, label })
+                        .collect(),
+                    question: it.question,
+                    votes_count: 0,
+                }),
+                previous_thread_id: previous_thread_id.as_ref().map(ThreadId::to_string),
                 replies_count: 0,
+                tags: {
+                    let mut tags = tags;
+                    tags.sort();
+                    tags.dedup();
+                    tags
+                },
+                title,
                 version,
             },
-            ThreadEvent::Replied(_) => {
-                unreachable!("first event should be Created")
+            event @ (ThreadEvent::Closed(_)
+            | ThreadEvent::Continued(_)
+            | ThreadEvent::Filled(_)
+            | ThreadEvent::MessageDeleted(_)
+            | ThreadEvent::MessageEdited(_)
+            | ThreadEvent::MessageReacted(_)
+            | ThreadEvent::MessageUnreacted(_)
+            | ThreadEvent::Pinned(_)
+            | ThreadEvent::PollVoted(_)
+            | ThreadEvent::Reopened(_)
+            | ThreadEvent::Replied(_)
+            | ThreadEvent::Tagged(_)
+            | ThreadEvent::TitleChanged(_)
+            | ThreadEvent::Unpinned(_)
+            | ThreadEvent::Untagged(_)) => {
+                return Err(ReplayError::not_created_first(&event));
             }
         };
 
         for event in iter {
-            thread.apply(event);
+            thread.apply(event)?;
         }
 
-        thread
+        Ok(thread)
     }
 
-    pub fn apply(&mut self, event: ThreadEvent) {
+    /// Applies the event that follows the events applied so far
+    ///
+    /// The thread is left as is if the event cannot be applied.
+    pub fn apply(&mut self, event: ThreadEvent) -> Result<(), ReplayError> {
+        check_next(self.version, &event)?;
+        let event_id = event.id().clone();
+        let event_version = u32::from(event.version());
+        let thread_id = event.thread_id().clone();
+        let invalid = |field: &'static str| ReplayError::InvalidField {
+            event_id: event_id.clone(),
+            field,
+            thread_id: thread_id.clone(),
+            version: event_version,
+        };
         match event {
-            ThreadEvent::Created(_) => {
-                unreachable!("subsequent events not to be Created")
+            ThreadEvent::Closed(ThreadClosed {
+                at: _,
+                id: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.closed = true;
+                self.version = version;
+            }
+            ThreadEvent::Continued(ThreadContinued {
+                at: _,
+                id: _,
+                next_thread_id,
+                thread_id: _,
+                version,
+            }) => {
+                self.next_thread_id = Some(next_thread_id.to_string());
+                self.version = version;
+            }
+            event @ ThreadEvent::Created(_) => {
+                return Err(ReplayError::duplicate_created(&event));
+            }
+            ThreadEvent::Filled(ThreadFilled {
+                at: _,
+                id: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.filled = true;
+                self.version = version;
+            }
+            ThreadEvent::MessageDeleted(ThreadMessageDeleted {
+                at,
+                id: _,
+                number,
+                reason,
+                thread_id: _,
+                version,
+            }) => {
+                let message = self
+                    .messages
+                    .iter_mut()
+                    .find(|message| message.number == number)
+                    .ok_or_else(|| invalid("number"))?;
+                // a tombstone links to nothing
+                let old_anchors = std::mem::take(&mut message.anchors);
+                message.attachments = vec![];
+                message.content = String::new();
+                message.deleted_at = Some(at.to_string());
+                message.deletion_reason = Some(reason.to_string());
+                message.reactions = vec![];
+                message.revisions = vec![];
+                let message = message.clone();
+                if self.first_message.number == number {
+                    self.first_message = message.clone();
+                }
+                if self.last_message.number == number {
+                    self.last_message = message;
+                }
+                self.update_replied_by(number, &old_anchors, false);
+                self.version = version;
+            }
+            ThreadEvent::MessageEdited(ThreadMessageEdited {
+                at,
+                content,
+                id: _,
+                number,
+                thread_id: _,
+                version,
+            }) => {
+                let message = self
+                    .messages
+                    .iter_mut()
+                    .find(|message| message.number == number)
+                    .ok_or_else(|| invalid("number"))?;
+                let anchors = parse_anchors(&content, number);
+                let old_anchors = std::mem::replace(&mut message.anchors, anchors.clone());
+                message.revisions.push(MessageRevision {
+                    content: std::mem::replace(&mut message.content, content),
+                    created_at: message
+                        .edited_at
+                        .replace(at.to_string())
+                        .unwrap_or_else(|| message.created_at.clone()),
+                });
+                let message = message.clone();
+                if self.first_message.number == number {
+                    self.first_message = message.clone();
+                }
+                if self.last_message.number == number {
+                    self.last_message = message;
+                }
+                self.update_replied_by(number, &old_anchors, false);
+                self.update_replied_by(number, &anchors, true);
+                self.version = version;
+            }
+            ThreadEvent::MessageReacted(ThreadMessageReacted {
+                at: _,
+                id: _,
+                number,
+                reaction,
+                reactor: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.update_reactions(number, reaction.to_string(), true)
+                    .map_err(invalid)?;
+                self.version = version;
+            }
+            ThreadEvent::MessageUnreacted(ThreadMessageUnreacted {
+                at: _,
+                id: _,
+                number,
+                reaction,
+                reactor: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.update_reactions(number, reaction.to_string(), false)
+                    .map_err(invalid)?;
+                self.version = version;
+            }
+            ThreadEvent::Pinned(ThreadPinned {
+                at: _,
+                id: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.pinned = true;
+                self.version = version;
+            }
+            ThreadEvent::PollVoted(ThreadPollVoted {
+                at: _,
+                id: _,
+                option,
+                thread_id: _,
+                version,
+                voter: _,
+            }) => {
+                if let Some(poll) = self.poll.as_mut() {
+                    if let Some(option) = poll.options.get_mut(usize::from(option)) {
+                        option.count += 1;
+                    }
+                    poll.votes_count += 1;
+                }
+                self.version = version;
+            }
+            ThreadEvent::Reopened(ThreadReopened {
+                at: _,
+                id: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.closed = false;
+                self.version = version;
             }
             ThreadEvent::Replied(ThreadReplied {
                 at,
+                attachments,
+                client_key: _,
                 content,
+                fingerprint: _,
                 id: _,
+                name,
+                sage,
                 thread_id: _,
+                trip,
                 version,
             }) => {
-                let message_count = self.replies_count + 1;
+                // an invalid event must leave the thread unchanged
+                let message_count = self
+                    .replies_count
+                    .checked_add(1)
+                    .ok_or_else(|| invalid("replies_count"))?;
+                let number = message_count
+                    .checked_add(1)
+                    .ok_or_else(|| invalid("number"))?;
+                if !sage {
+                    self.bumped_at = at.to_string();
+                }
+                let anchors = parse_anchors(&content, number);
                 let message = Message {
+                    anchors: anchors.clone(),
+                    attachments: Self::attachments_from_event(attachments),
                     content,
-                    created_at: at,
-                    number: message_count + 1,
+                    created_at: at.to_string(),
+                    deleted_at: None,
+                    deletion_reason: None,
+                    edited_at: None,
+                    name,
+                    number,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip,
                 };
                 self.last_message = message.clone();
                 self.messages.push(message);
+                self.update_replied_by(number, &anchors, true);
                 self.replies_count = message_count;
                 self.version = version;
             }
+            ThreadEvent::Tagged(ThreadTagged {
+                at: _,
+                id: _,
+                tag,
+                thread_id: _,
+                version,
+            }) => {
+                if let Err(index) = self.tags.binary_search(&tag) {
+                    self.tags.insert(index, tag);
+                }
+                self.version = version;
+            }
+            ThreadEvent::TitleChanged(ThreadTitleChanged {
+                at: _,
+                id: _,
+                thread_id: _,
+                title,
+                version,
+            }) => {
+                self.title = title;
+                self.version = version;
+            }
+            ThreadEvent::Unpinned(ThreadUnpinned {
+                at: _,
+                id: _,
+                thread_id: _,
+                version,
+            }) => {
+                self.pinned = false;
+                self.version = version;
+            }
+            ThreadEvent::Untagged(ThreadUntagged {
+                at: _,
+                id: _,
+                tag,
+                thread_id: _,
+                version,
+            }) => {
+                self.tags.retain(|it| it != &tag);
+                self.version = version;
+            }
+        }
+        Ok(())
+    }
+
+    fn attachments_from_event(attachments: Vec<ThreadMessageAttachment>) -> Vec<MessageAttachment> {
+        attachments
+            .into_iter()
+            .map(|it| MessageAttachment {
+                hash: it.hash.to_string(),
+                mime: it.mime.to_string(),
+                size: it.size,
+                thumbnail_hash: it.thumbnail_hash.to_string(),
+            })
+            .collect()
+    }
+
+    /// Increments (or decrements) the count of `code` reactions to the message
+    ///
+    /// Returns the name of the invalid field on error.
+    fn update_reactions(
+        &mut self,
+        number: u16,
+        code: String,
+        reacted: bool,
+    ) -> Result<(), &'static str> {
+        let message = self
+            .messages
+            .iter_mut()
+            .find(|message| message.number == number)
+            .ok_or("number")?;
+        match message
+            .reactions
+            .binary_search_by(|reaction| reaction.code.cmp(&code))
+        {
+            Ok(index) => {
+                if reacted {
+                    message.reactions[index].count += 1;
+                } else {
+                    message.reactions[index].count -= 1;
+                    if message.reactions[index].count == 0 {
+                        message.reactions.remove(index);
+                    }
+                }
+            }
+            Err(index) => {
+                if !reacted {
+                    return Err("reaction");
+                }
+                message
+                    .reactions
+                    .insert(index, MessageReaction { code, count: 1 });
+            }
+        }
+        let message = message.clone();
+        if self.first_message.number == number {
+            self.first_message = message.clone();
+        }
+        if self.last_message.number == number {
+            self.last_message = message;
+        }
+        Ok(())
+    }
+
+    /// Adds (or removes) `number` to (or from) the replied-by lists of the messages in `anchors`
+    fn update_replied_by(&mut self, number: u16, anchors: &[u16], replied: bool) {
+        for anchor in anchors {
+            let Some(message) = self
+                .messages
+                .iter_mut()
+                .find(|message| message.number == *anchor)
+            else {
+                continue;
+            };
+            if replied {
+                message.replied_by.push(number);
+                message.replied_by.sort_unstable();
+                message.replied_by.dedup();
+            } else {
+                message.replied_by.retain(|it| *it != number);
+            }
+            let message = message.clone();
+            if self.first_message.number == *anchor {
+                self.first_message = message.clone();
+            }
+            if self.last_message.number == *anchor {
+                self.last_message = message;
+            }
         }
     }
 }
@@ -125,32 +576,555 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_anchors() {
+        let thread_id = "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+            .parse::<ThreadId>()
+            .expect("thread id to be valid");
+        let replied = |content: &str, version: u32| {
+            ThreadEvent::Replied(ThreadReplied {
+                at: "2023-10-01T01:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                client_key: None,
+                content: content.to_string(),
+                fingerprint: None,
+                id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5"
+                    .parse()
+                    .expect("id to be valid"),
+                name: None,
+                sage: false,
+                thread_id: thread_id.clone(),
+                trip: None,
+                version,
+            })
+        };
+        let events = vec![
+            ThreadEvent::Created(ThreadCreated {
+                at: "2023-10-01T00:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                board_id: None,
+                client_key: None,
+                content: ">>1 >>2".to_string(),
+                fingerprint: None,
+                id: "99164b55-98d0-4e7c-98cf-95f7c43da68f"
+                    .parse()
+                    .expect("id to be valid"),
+                name: None,
+                poll: None,
+                previous_thread_id: None,
+                tags: vec![],
+                thread_id: thread_id.clone(),
+                title: "Thread title".to_string(),
+                trip: None,
+                version: 1,
+            }),
+            replied(">>1", 2),
+            replied(">>1-2 >>4", 3),
+        ];
+
+        let mut thread = Thread::replay(events).expect("events to be replayed");
+        assert_eq!(thread.messages[0].anchors, Vec::<u16>::new());
+        assert_eq!(thread.messages[0].replied_by, vec![2, 3]);
+        assert_eq!(thread.first_message.replied_by, vec![2, 3]);
+        assert_eq!(thread.messages[1].anchors, vec![1]);
+        assert_eq!(thread.messages[1].replied_by, vec![3]);
+        assert_eq!(thread.messages[2].anchors, vec![1, 2]);
+        assert_eq!(thread.messages[2].replied_by, Vec::<u16>::new());
+
+        thread
+            .apply(ThreadEvent::MessageEdited(ThreadMessageEdited {
+                at: "2023-10-01T02:00:00.000Z".parse().expect("at to be valid"),
+                content: ">>2".to_string(),
+                id: "6b3a2f5e-58f4-4f5c-9d0e-0f3c5f1c8a3d"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 3,
+                thread_id: thread_id.clone(),
+                version: 4,
+            }))
+            .expect("event to be applied");
+        assert_eq!(thread.messages[0].replied_by, vec![2]);
+        assert_eq!(thread.messages[1].replied_by, vec![3]);
+        assert_eq!(thread.messages[2].anchors, vec![2]);
+        assert_eq!(thread.last_message.anchors, vec![2]);
+
+        thread
+            .apply(ThreadEvent::MessageDeleted(ThreadMessageDeleted {
+                at: "2023-10-01T03:00:00.000Z".parse().expect("at to be valid"),
+                id: "3e5f7a9c-1b2d-4e6f-8a0c-2d4f6b8a0c1e"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 3,
+                reason: "author_request".parse().expect("reason to be valid"),
+                thread_id: thread_id.clone(),
+                version: 5,
+            }))
+            .expect("event to be applied");
+        assert_eq!(thread.messages[1].replied_by, Vec::<u16>::new());
+        assert_eq!(thread.messages[2].anchors, Vec::<u16>::new());
+        assert_eq!(thread.last_message.anchors, Vec::<u16>::new());
+    }
+
+    #[test]
+    fn test_reactions() {
+        let thread_id = "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+            .parse::<ThreadId>()
+            .expect("thread id to be valid");
+        let reacted = |reaction: &str, version: u32| {
+            ThreadEvent::MessageReacted(ThreadMessageReacted {
+                at: "2023-10-01T01:00:00.000Z".parse().expect("at to be valid"),
+                id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 1,
+                reaction: reaction.parse().expect("reaction to be valid"),
+                reactor: None,
+                thread_id: thread_id.clone(),
+                version,
+            })
+        };
+        let events = vec![
+            ThreadEvent::Created(ThreadCreated {
+                at: "2023-10-01T00:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                board_id: None,
+                client_key: None,
+                content: "Root message".to_string(),
+                fingerprint: None,
+                id: "99164b55-98d0-4e7c-98cf-95f7c43da68f"
+                    .parse()
+                    .expect("id to be valid"),
+                name: None,
+                poll: None,
+                previous_thread_id: None,
+                tags: vec![],
+                thread_id: thread_id.clone(),
+                title: "Thread title".to_string(),
+                trip: None,
+                version: 1,
+            }),
+            reacted("thumbs_up", 2),
+            reacted("heart", 3),
+            reacted("thumbs_up", 4),
+            ThreadEvent::MessageUnreacted(ThreadMessageUnreacted {
+                at: "2023-10-01T02:00:00.000Z".parse().expect("at to be valid"),
+                id: "6b3a2f5e-58f4-4f5c-9d0e-0f3c5f1c8a3d"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 1,
+                reaction: "heart".parse().expect("reaction to be valid"),
+                reactor: None,
+                thread_id: thread_id.clone(),
+                version: 5,
+            }),
+        ];
+
+        let thread = Thread::replay(events).expect("events to be replayed");
+        let reactions = thread.messages[0]
+            .reactions
+            .iter()
+            .map(|it| (it.code.as_str(), it.count))
+            .collect::<Vec<_>>();
+        assert_eq!(reactions, vec![("thumbs_up", 2)]);
+        assert_eq!(thread.first_message.reactions.len(), 1);
+        // reactions do not bump the thread
+        assert_eq!(thread.last_message.created_at, "2023-10-01T00:00:00.000Z");
+        assert_eq!(thread.version, 5);
+    }
+
+    #[test]
+    fn test_poll() {
+        let thread_id = "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+            .parse::<ThreadId>()
+            .expect("thread id to be valid");
+        let voted = |option: u8, voter: &str, version: u32| {
+            ThreadEvent::PollVoted(ThreadPollVoted {
+                at: "2023-10-01T01:00:00.000Z".parse().expect("at to be valid"),
+                id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5"
+                    .parse()
+                    .expect("id to be valid"),
+                option,
+                thread_id: thread_id.clone(),
+                version,
+                voter: voter.parse().expect("voter to be valid"),
+            })
+        };
+        let events = vec![
+            ThreadEvent::Created(ThreadCreated {
+                at: "2023-10-01T00:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                board_id: None,
+                client_key: None,
+                content: "Root message".to_string(),
+                fingerprint: None,
+                id: "99164b55-98d0-4e7c-98cf-95f7c43da68f"
+                    .parse()
+                    .expect("id to be valid"),
+                name: None,
+                poll: Some(crate::model::shared::event::ThreadCreatedPoll {
+                    closes_at: Some(
+                        "2023-10-02T00:00:00.000Z"
+                            .parse()
+                            .expect("closes_at to be valid"),
+                    ),
+                    options: vec!["yes".to_string(), "no".to_string(), "maybe".to_string()],
+                    question: "Do you agree?".to_string(),
+                }),
+                previous_thread_id: None,
+                tags: vec![],
+                thread_id: thread_id.clone(),
+                title: "Thread title".to_string(),
+                trip: None,
+                version: 1,
+            }),
+            voted(0, "Zx9Lq2Wm8Rt4Yp6N", 2),
+            voted(2, "Kd3Hs7Vb1Nc5Xf0G", 3),
+            voted(0, "Pm4Tj8Ew2Qa6Uo1I", 4),
+        ];
+
+        let thread = Thread::replay(events).expect("events to be replayed");
+        let poll = thread.poll.expect("poll to be present");
+        assert_eq!(poll.closes_at.as_deref(), Some("2023-10-02T00:00:00.000Z"));
+        assert_eq!(poll.question, "Do you agree?");
+        let options = poll
+            .options
+            .iter()
+            .map(|it| (it.label.as_str(), it.count))
+            .collect::<Vec<_>>();
+        assert_eq!(options, vec![("yes", 2), ("no", 0), ("maybe", 1)]);
+        assert_eq!(poll.votes_count, 3);
+        // votes do not bump the thread
+        assert_eq!(thread.bumped_at, "2023-10-01T00:00:00.000Z");
+        assert_eq!(thread.version, 4);
+    }
+
     #[test]
     fn test_replay() {
         let events = vec![
             ThreadEvent::Created(ThreadCreated {
-                at: "2023-10-01T00:00:00Z".to_string(),
+                at: "2023-10-01T00:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                board_id: Some("dev".parse().expect("board id to be valid")),
+                client_key: None,
                 content: "Root message".to_string(),
-                id: "99164b55-98d0-4e7c-98cf-95f7c43da68f".to_string(),
-                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81".to_string(),
+                fingerprint: None,
+                id: "99164b55-98d0-4e7c-98cf-95f7c43da68f"
+                    .parse()
+                    .expect("id to be valid"),
+                name: Some("Alice".to_string()),
+                poll: None,
+                previous_thread_id: Some(
+                    "5d1e3f7a-9b2c-4d6e-8f0a-1b3c5d7e9f2a"
+                        .parse()
+                        .expect("thread id to be valid"),
+                ),
+                tags: vec!["web".to_string(), "rust".to_string()],
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                title: "Thread title".to_string(),
+                trip: None,
                 version: 1,
             }),
             ThreadEvent::Replied(ThreadReplied {
-                at: "2023-10-01T01:00:00Z".to_string(),
+                at: "2023-10-01T01:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                client_key: None,
                 content: "Reply message".to_string(),
-                id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5".to_string(),
-                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81".to_string(),
+                fingerprint: None,
+                id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5"
+                    .parse()
+                    .expect("id to be valid"),
+                name: Some("Bob".to_string()),
+                sage: true,
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                trip: Some("K8xJ2mQp0a".to_string()),
                 version: 2,
             }),
+            ThreadEvent::TitleChanged(ThreadTitleChanged {
+                at: "2023-10-01T02:00:00.000Z".parse().expect("at to be valid"),
+                id: "0d0fdbd2-7f4b-4c55-9a4a-5f4cbd4c6a52"
+                    .parse()
+                    .expect("id to be valid"),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                title: "Changed title".to_string(),
+                version: 3,
+            }),
+            ThreadEvent::MessageEdited(ThreadMessageEdited {
+                at: "2023-10-01T03:00:00.000Z".parse().expect("at to be valid"),
+                content: "Edited reply message".to_string(),
+                id: "6b3a2f5e-58f4-4f5c-9d0e-0f3c5f1c8a3d"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 2,
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 4,
+            }),
+            ThreadEvent::MessageEdited(ThreadMessageEdited {
+                at: "2023-10-01T04:00:00.000Z".parse().expect("at to be valid"),
+                content: "Edited reply message (2)".to_string(),
+                id: "9a1e8d3c-2b7f-4e6a-8c5d-4f3b2a1e0d9c"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 2,
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 5,
+            }),
+            ThreadEvent::MessageDeleted(ThreadMessageDeleted {
+                at: "2023-10-01T05:00:00.000Z".parse().expect("at to be valid"),
+                id: "3e5f7a9c-1b2d-4e6f-8a0c-2d4f6b8a0c1e"
+                    .parse()
+                    .expect("id to be valid"),
+                number: 1,
+                reason: "moderator".parse().expect("reason to be valid"),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 6,
+            }),
+            ThreadEvent::Closed(ThreadClosed {
+                at: "2023-10-01T06:00:00.000Z".parse().expect("at to be valid"),
+                id: "7c9e1a3b-5d7f-4a2c-9e4b-6d8f0a2c4e6b"
+                    .parse()
+                    .expect("id to be valid"),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 7,
+            }),
+            ThreadEvent::Filled(ThreadFilled {
+                at: "2023-10-01T07:00:00.000Z".parse().expect("at to be valid"),
+                id: "1f3a5c7e-9b0d-4f2a-8c4e-6a8c0e2a4c6e"
+                    .parse()
+                    .expect("id to be valid"),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 8,
+            }),
+            ThreadEvent::Continued(ThreadContinued {
+                at: "2023-10-01T08:00:00.000Z".parse().expect("at to be valid"),
+                id: "2a4c6e8a-0c2e-4a6c-8e0a-2c4e6a8c0e2a"
+                    .parse()
+                    .expect("id to be valid"),
+                next_thread_id: "8e0a2c4e-6a8c-4e2a-9c4e-6a8c0e2a4c6e"
+                    .parse()
+                    .expect("thread id to be valid"),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 9,
+            }),
+            ThreadEvent::Pinned(ThreadPinned {
+                at: "2023-10-01T09:00:00.000Z".parse().expect("at to be valid"),
+                id: "5b7d9f1a-3c5e-4a7b-9d1f-3a5c7e9b1d3f"
+                    .parse()
+                    .expect("id to be valid"),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 10,
+            }),
+            ThreadEvent::Tagged(ThreadTagged {
+                at: "2023-10-01T10:00:00.000Z".parse().expect("at to be valid"),
+                id: "6c8e0a2b-4d6f-4b8c-8e2a-4b6d8f0a2c4e"
+                    .parse()
+                    .expect("id to be valid"),
+                tag: "help".to_string(),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 11,
+            }),
+            ThreadEvent::Untagged(ThreadUntagged {
+                at: "2023-10-01T11:00:00.000Z".parse().expect("at to be valid"),
+                id: "7d9f1b3c-5e7a-4c9d-9f3b-5c7e9a1b3d5f"
+                    .parse()
+                    .expect("id to be valid"),
+                tag: "web".to_string(),
+                thread_id: "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+                    .parse()
+                    .expect("thread id to be valid"),
+                version: 12,
+            }),
         ];
 
-        let thread = Thread::replay(events);
-        assert_eq!(thread.created_at, "2023-10-01T00:00:00Z");
+        let thread = Thread::replay(events).expect("events to be replayed");
+        assert_eq!(thread.board_id, "dev");
+        // the sage reply does not bump the thread
+        assert_eq!(thread.bumped_at, "2023-10-01T00:00:00.000Z");
+        assert!(thread.closed);
+        assert_eq!(thread.created_at, "2023-10-01T00:00:00.000Z");
+        assert!(thread.filled);
+        assert!(thread.pinned);
         assert_eq!(thread.id, "c4ac95d6-45c7-4006-b768-2a172dee3f81");
         assert_eq!(thread.messages.len(), 2);
-        assert_eq!(thread.messages[0].content, "Root message");
-        assert_eq!(thread.messages[1].content, "Reply message");
+        assert_eq!(thread.messages[0].content, "");
+        assert_eq!(
+            thread.messages[0].deleted_at.as_deref(),
+            Some("2023-10-01T05:00:00.000Z")
+        );
+        assert_eq!(
+            thread.messages[0].deletion_reason.as_deref(),
+            Some("moderator")
+        );
+        assert_eq!(thread.messages[0].name.as_deref(), Some("Alice"));
+        assert_eq!(thread.messages[0].number, 1);
+        assert_eq!(thread.messages[0].trip, None);
+        assert_eq!(thread.messages[1].name.as_deref(), Some("Bob"));
+        assert_eq!(thread.messages[1].trip.as_deref(), Some("K8xJ2mQp0a"));
+        assert_eq!(thread.first_message.content, "");
+        assert!(thread.first_message.deleted_at.is_some());
+        assert_eq!(thread.messages[1].content, "Edited reply message (2)");
+        assert_eq!(
+            thread.messages[1].edited_at.as_deref(),
+            Some("2023-10-01T04:00:00.000Z")
+        );
+        assert_eq!(thread.messages[1].revisions.len(), 2);
+        assert_eq!(thread.messages[1].revisions[0].content, "Reply message");
+        assert_eq!(
+            thread.messages[1].revisions[0].created_at,
+            "2023-10-01T01:00:00.000Z"
+        );
+        assert_eq!(
+            thread.messages[1].revisions[1].content,
+            "Edited reply message"
+        );
+        assert_eq!(
+            thread.messages[1].revisions[1].created_at,
+            "2023-10-01T03:00:00.000Z"
+        );
+        assert_eq!(thread.last_message.content, "Edited reply message (2)");
+        assert_eq!(
+            thread.next_thread_id.as_deref(),
+            Some("8e0a2c4e-6a8c-4e2a-9c4e-6a8c0e2a4c6e")
+        );
+        assert_eq!(
+            thread.previous_thread_id.as_deref(),
+            Some("5d1e3f7a-9b2c-4d6e-8f0a-1b3c5d7e9f2a")
+        );
         assert_eq!(thread.replies_count, 1);
-        assert_eq!(thread.version, 2);
+        assert_eq!(thread.tags, vec!["help", "rust"]);
+        assert_eq!(thread.title, "Changed title");
+        assert_eq!(thread.version, 12);
+    }
+
+    #[test]
+    fn test_replay_corrupted() {
+        let thread_id = "c4ac95d6-45c7-4006-b768-2a172dee3f81"
+            .parse::<ThreadId>()
+            .expect("thread id to be valid");
+        let created = |version: u32| {
+            ThreadEvent::Created(ThreadCreated {
+                at: "2023-10-01T00:00:00.000Z".parse().expect("at to be valid"),
+                attachments: vec![],
+                board_id: None,
+                client_key: None,
+                content: "Root message".to_string(),
+                fingerprint: None,
+                id: "99164b55-98d0-4e7c-98cf-95f7c43da68f"
+                    .parse()
+                    .expect("id to be valid"),
+                name: None,
+                poll: None,
+                previous_thread_id: None,
+                tags: vec![],
+                thread_id: thread_id.clone(),
+                title: "Thread title".to_string(),
+                trip: None,
+                version,
+            })
+        };
+        let unreacted = |number: u16, version: u32| {
+            ThreadEvent::MessageUnreacted(ThreadMessageUnreacted {
+                at: "2023-10-01T01:00:00.000Z".parse().expect("at to be valid"),
+                id: "6b3a2f5e-58f4-4f5c-9d0e-0f3c5f1c8a3d"
+                    .parse()
+                    .expect("id to be valid"),
+                number,
+                reaction: "heart".parse().expect("reaction to be valid"),
+                reactor: None,
+                thread_id: thread_id.clone(),
+                version,
+            })
+        };
+
+        assert!(matches!(Thread::replay(vec![]), Err(ReplayError::Empty)));
+        assert!(matches!(
+            Thread::replay(vec![unreacted(1, 1)]),
+            Err(ReplayError::NotCreatedFirst {
+                kind: "message_unreacted",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Thread::replay(vec![created(1), created(2)]),
+            Err(ReplayError::DuplicateCreated { version: 2, .. })
+        ));
+        assert!(matches!(
+            Thread::replay(vec![created(1), unreacted(1, 3)]),
+            Err(ReplayError::VersionOutOfOrder {
+                actual: 3,
+                expected: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Thread::replay(vec![created(1), unreacted(2, 2)]),
+            Err(ReplayError::InvalidField {
+                field: "number",
+                version: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Thread::replay(vec![created(1), unreacted(1, 2)]),
+            Err(ReplayError::InvalidField {
+                field: "reaction",
+                ..
+            })
+        ));
+
+        // the thread is left as is
+        let mut thread = Thread::replay(vec![created(1)]).expect("events to be replayed");
+        assert!(thread.apply(unreacted(1, 2)).is_err());
+        assert_eq!(thread.version, 1);
+        assert!(thread.apply(unreacted(1, 1)).is_err());
+
+        // also when the reply overflows the message number
+        thread.replies_count = u16::MAX - 1;
+        let replied = ThreadEvent::Replied(ThreadReplied {
+            at: "2023-10-01T02:00:00.000Z".parse().expect("at to be valid"),
+            attachments: vec![],
+            client_key: None,
+            content: "Reply message".to_string(),
+            fingerprint: None,
+            id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5"
+                .parse()
+                .expect("id to be valid"),
+            name: None,
+            sage: false,
+            thread_id: thread_id.clone(),
+            trip: None,
+            version: 2,
+        });
+        assert!(matches!(
+            thread.apply(replied),
+            Err(ReplayError::InvalidField {
+                field: "number",
+                ..
+            })
+        ));
+        assert_eq!(thread.bumped_at, "2023-10-01T00:00:00.000Z");
+        assert_eq!(thread.replies_count, u16::MAX - 1);
+        assert_eq!(thread.version, 1);
     }
 }
@@ -0,0 +1,6 @@
+/// The number of threads with a tag
+#[derive(Clone)]
+pub struct ThreadTag {
+    pub count: u32,
+    pub name: String,
+}
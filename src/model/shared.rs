@@ -1,2 +1,6 @@
 pub mod event;
+pub mod event_envelope;
+pub mod event_schema;
 pub mod id;
+pub mod replay;
+pub mod thread_snapshot;
@@ -1,35 +1,165 @@
-use std::str::FromStr as _;
-
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum ThreadEvent {
+    Closed(ThreadClosed),
+    Continued(ThreadContinued),
     Created(ThreadCreated),
+    Filled(ThreadFilled),
+    MessageDeleted(ThreadMessageDeleted),
+    MessageEdited(ThreadMessageEdited),
+    MessageReacted(ThreadMessageReacted),
+    MessageUnreacted(ThreadMessageUnreacted),
+    Pinned(ThreadPinned),
+    PollVoted(ThreadPollVoted),
+    Reopened(ThreadReopened),
     Replied(ThreadReplied),
+    Tagged(ThreadTagged),
+    TitleChanged(ThreadTitleChanged),
+    Unpinned(ThreadUnpinned),
+    Untagged(ThreadUntagged),
 }
 
 impl ThreadEvent {
-    pub fn thread_id(&self) -> crate::model::shared::id::ThreadId {
-        crate::model::shared::id::ThreadId::from_str(match self {
+    pub fn id(&self) -> &crate::model::shared::id::EventId {
+        match self {
+            ThreadEvent::Closed(event) => &event.id,
+            ThreadEvent::Continued(event) => &event.id,
+            ThreadEvent::Created(event) => &event.id,
+            ThreadEvent::Filled(event) => &event.id,
+            ThreadEvent::MessageDeleted(event) => &event.id,
+            ThreadEvent::MessageEdited(event) => &event.id,
+            ThreadEvent::MessageReacted(event) => &event.id,
+            ThreadEvent::MessageUnreacted(event) => &event.id,
+            ThreadEvent::Pinned(event) => &event.id,
+            ThreadEvent::PollVoted(event) => &event.id,
+            ThreadEvent::Reopened(event) => &event.id,
+            ThreadEvent::Replied(event) => &event.id,
+            ThreadEvent::Tagged(event) => &event.id,
+            ThreadEvent::TitleChanged(event) => &event.id,
+            ThreadEvent::Unpinned(event) => &event.id,
+            ThreadEvent::Untagged(event) => &event.id,
+        }
+    }
+
+    /// The `kind` tag of the serialized event
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ThreadEvent::Closed(_) => "closed",
+            ThreadEvent::Continued(_) => "continued",
+            ThreadEvent::Created(_) => "created",
+            ThreadEvent::Filled(_) => "filled",
+            ThreadEvent::MessageDeleted(_) => "message_deleted",
+            ThreadEvent::MessageEdited(_) => "message_edited",
+            ThreadEvent::MessageReacted(_) => "message_reacted",
+            ThreadEvent::MessageUnreacted(_) => "message_unreacted",
+            ThreadEvent::Pinned(_) => "pinned",
+            ThreadEvent::PollVoted(_) => "poll_voted",
+            ThreadEvent::Reopened(_) => "reopened",
+            ThreadEvent::Replied(_) => "replied",
+            ThreadEvent::Tagged(_) => "tagged",
+            ThreadEvent::TitleChanged(_) => "title_changed",
+            ThreadEvent::Unpinned(_) => "unpinned",
+            ThreadEvent::Untagged(_) => "untagged",
+        }
+    }
+
+    pub fn thread_id(&self) -> &crate::model::shared::id::ThreadId {
+        match self {
+            ThreadEvent::Closed(event) => &event.thread_id,
+            ThreadEvent::Continued(event) => &event.thread_id,
             ThreadEvent::Created(event) => &event.thread_id,
+            ThreadEvent::Filled(event) => &event.thread_id,
+            ThreadEvent::MessageDeleted(event) => &event.thread_id,
+            ThreadEvent::MessageEdited(event) => &event.thread_id,
+            ThreadEvent::MessageReacted(event) => &event.thread_id,
+            ThreadEvent::MessageUnreacted(event) => &event.thread_id,
+            ThreadEvent::Pinned(event) => &event.thread_id,
+            ThreadEvent::PollVoted(event) => &event.thread_id,
+            ThreadEvent::Reopened(event) => &event.thread_id,
             ThreadEvent::Replied(event) => &event.thread_id,
-        })
-        .expect("thread_id in event to be valid")
+            ThreadEvent::Tagged(event) => &event.thread_id,
+            ThreadEvent::TitleChanged(event) => &event.thread_id,
+            ThreadEvent::Unpinned(event) => &event.thread_id,
+            ThreadEvent::Untagged(event) => &event.thread_id,
+        }
     }
 
     pub fn version(&self) -> crate::model::write::Version {
         crate::model::write::Version::from(match self {
+            ThreadEvent::Closed(event) => event.version,
+            ThreadEvent::Continued(event) => event.version,
             ThreadEvent::Created(event) => event.version,
+            ThreadEvent::Filled(event) => event.version,
+            ThreadEvent::MessageDeleted(event) => event.version,
+            ThreadEvent::MessageEdited(event) => event.version,
+            ThreadEvent::MessageReacted(event) => event.version,
+            ThreadEvent::MessageUnreacted(event) => event.version,
+            ThreadEvent::Pinned(event) => event.version,
+            ThreadEvent::PollVoted(event) => event.version,
+            ThreadEvent::Reopened(event) => event.version,
             ThreadEvent::Replied(event) => event.version,
+            ThreadEvent::Tagged(event) => event.version,
+            ThreadEvent::TitleChanged(event) => event.version,
+            ThreadEvent::Unpinned(event) => event.version,
+            ThreadEvent::Untagged(event) => event.version,
         })
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadClosed {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadClosed> for ThreadEvent {
+    fn from(event: ThreadClosed) -> Self {
+        ThreadEvent::Closed(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadContinued {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub next_thread_id: crate::model::shared::id::ThreadId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadContinued> for ThreadEvent {
+    fn from(event: ThreadContinued) -> Self {
+        ThreadEvent::Continued(event)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct ThreadCreated {
-    pub at: String,
+    pub at: crate::utils::date_time::DateTime,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<ThreadMessageAttachment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_id: Option<crate::model::shared::id::BoardId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<crate::model::write::ClientKey>,
     pub content: String,
-    pub id: String,
-    pub thread_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<crate::model::write::ContentFingerprint>,
+    pub id: crate::model::shared::id::EventId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll: Option<ThreadCreatedPoll>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_thread_id: Option<crate::model::shared::id::ThreadId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trip: Option<String>,
     pub version: u32,
 }
 
@@ -39,12 +169,169 @@ impl From<ThreadCreated> for ThreadEvent {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadCreatedPoll {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closes_at: Option<crate::utils::date_time::DateTime>,
+    pub options: Vec<String>,
+    pub question: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadFilled {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadFilled> for ThreadEvent {
+    fn from(event: ThreadFilled) -> Self {
+        ThreadEvent::Filled(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadMessageAttachment {
+    pub hash: crate::model::shared::id::BlobHash,
+    pub mime: crate::model::write::MessageAttachmentMime,
+    pub size: u32,
+    pub thumbnail_hash: crate::model::shared::id::BlobHash,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadMessageDeleted {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub number: u16,
+    pub reason: crate::model::write::MessageDeletionReason,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadMessageDeleted> for ThreadEvent {
+    fn from(event: ThreadMessageDeleted) -> Self {
+        ThreadEvent::MessageDeleted(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadMessageEdited {
+    pub at: crate::utils::date_time::DateTime,
+    pub content: String,
+    pub id: crate::model::shared::id::EventId,
+    pub number: u16,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadMessageEdited> for ThreadEvent {
+    fn from(event: ThreadMessageEdited) -> Self {
+        ThreadEvent::MessageEdited(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadMessageReacted {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub number: u16,
+    pub reaction: crate::model::write::MessageReaction,
+    /// The client key of the reactor, unknown for the reactions before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactor: Option<crate::model::write::ClientKey>,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadMessageReacted> for ThreadEvent {
+    fn from(event: ThreadMessageReacted) -> Self {
+        ThreadEvent::MessageReacted(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadMessageUnreacted {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub number: u16,
+    pub reaction: crate::model::write::MessageReaction,
+    /// The client key of the reactor, unknown for the reactions before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactor: Option<crate::model::write::ClientKey>,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadMessageUnreacted> for ThreadEvent {
+    fn from(event: ThreadMessageUnreacted) -> Self {
+        ThreadEvent::MessageUnreacted(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadPinned {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadPinned> for ThreadEvent {
+    fn from(event: ThreadPinned) -> Self {
+        ThreadEvent::Pinned(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadPollVoted {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    /// The 0-based index of the option
+    pub option: u8,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+    pub voter: crate::model::write::VoterKey,
+}
+
+impl From<ThreadPollVoted> for ThreadEvent {
+    fn from(event: ThreadPollVoted) -> Self {
+        ThreadEvent::PollVoted(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadReopened {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadReopened> for ThreadEvent {
+    fn from(event: ThreadReopened) -> Self {
+        ThreadEvent::Reopened(event)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct ThreadReplied {
-    pub at: String,
+    pub at: crate::utils::date_time::DateTime,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<ThreadMessageAttachment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<crate::model::write::ClientKey>,
     pub content: String,
-    pub id: String,
-    pub thread_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<crate::model::write::ContentFingerprint>,
+    pub id: crate::model::shared::id::EventId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sage: bool,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trip: Option<String>,
     pub version: u32,
 }
 
@@ -54,33 +341,554 @@ impl From<ThreadReplied> for ThreadEvent {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadTagged {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub tag: String,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadTagged> for ThreadEvent {
+    fn from(event: ThreadTagged) -> Self {
+        ThreadEvent::Tagged(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadTitleChanged {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub title: String,
+    pub version: u32,
+}
+
+impl From<ThreadTitleChanged> for ThreadEvent {
+    fn from(event: ThreadTitleChanged) -> Self {
+        ThreadEvent::TitleChanged(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadUnpinned {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadUnpinned> for ThreadEvent {
+    fn from(event: ThreadUnpinned) -> Self {
+        ThreadEvent::Unpinned(event)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadUntagged {
+    pub at: crate::utils::date_time::DateTime,
+    pub id: crate::model::shared::id::EventId,
+    pub tag: String,
+    pub thread_id: crate::model::shared::id::ThreadId,
+    pub version: u32,
+}
+
+impl From<ThreadUntagged> for ThreadEvent {
+    fn from(event: ThreadUntagged) -> Self {
+        ThreadEvent::Untagged(event)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr as _;
+
     use super::*;
 
+    #[test]
+    fn test_message_event_closed() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 2;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "closed",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Closed(ThreadClosed {
+                at,
+                id,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_continued() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let next_thread_id =
+            crate::model::shared::id::ThreadId::from_str("4a4d1b0e-2f8c-4f55-9e0e-1c8a6f0d8b7e")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 1001;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "continued",
+    "next_thread_id": "{next_thread_id}",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Continued(ThreadContinued {
+                at,
+                id,
+                next_thread_id,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_message_event_created() -> anyhow::Result<()> {
-        let at = "2023-10-01T12:00:00.000Z".to_owned();
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let content = "Hello, world!".to_owned();
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let title = "Hello".to_owned();
+        let version = 1;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "content": "{content}",
+    "id": "{id}",
+    "kind": "created",
+    "thread_id": "{thread_id}",
+    "title": "{title}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Created(ThreadCreated {
+                at,
+                attachments: vec![],
+                board_id: None,
+                client_key: None,
+                content,
+                fingerprint: None,
+                id,
+                name: None,
+                poll: None,
+                previous_thread_id: None,
+                tags: vec![],
+                thread_id,
+                title,
+                trip: None,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_created_with_optional_fields() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let board_id = crate::model::shared::id::BoardId::from_str("dev")?;
+        let client_key = crate::model::write::ClientKey::from_str("pG0n5h1Yy3KLbqnA")?;
+        let fingerprint = crate::model::write::ContentFingerprint::from_str(
+            "LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ",
+        )?;
+        let hash = crate::model::shared::id::BlobHash::from_str(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        )?;
+        let thumbnail_hash = crate::model::shared::id::BlobHash::from_str(
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        )?;
+        let closes_at = crate::utils::date_time::DateTime::from_str("2023-10-02T12:00:00.000Z")?;
         let content = "Hello, world!".to_owned();
-        let id = "0779b098-f41d-404a-b055-36463a7c009b".to_owned();
-        let thread_id = "b8392399-53a3-4f8e-8288-875448037455".to_owned();
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let name = "Alice".to_owned();
+        let previous_thread_id =
+            crate::model::shared::id::ThreadId::from_str("4a4d1b0e-2f8c-4f55-9e0e-1c8a6f0d8b7e")?;
+        let question = "Agree?".to_owned();
+        let tag = "rust".to_owned();
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let title = "Hello".to_owned();
+        let trip = "K8xJ2mQp0a".to_owned();
         let version = 1;
         assert_eq!(
             serde_json::from_str::<ThreadEvent>(&format!(
                 r#"
 {{
     "at": "{at}",
+    "attachments": [
+        {{
+            "hash": "{hash}",
+            "mime": "image/png",
+            "size": 123,
+            "thumbnail_hash": "{thumbnail_hash}"
+        }}
+    ],
+    "board_id": "{board_id}",
+    "client_key": "{client_key}",
     "content": "{content}",
+    "fingerprint": "{fingerprint}",
     "id": "{id}",
     "kind": "created",
+    "name": "{name}",
+    "poll": {{
+        "closes_at": "{closes_at}",
+        "options": ["yes", "no"],
+        "question": "{question}"
+    }},
+    "previous_thread_id": "{previous_thread_id}",
+    "tags": ["{tag}"],
     "thread_id": "{thread_id}",
+    "title": "{title}",
+    "trip": "{trip}",
     "version": {version}
 }}"#
             ))?,
             ThreadEvent::Created(ThreadCreated {
+                at,
+                attachments: vec![ThreadMessageAttachment {
+                    hash,
+                    mime: crate::model::write::MessageAttachmentMime::from_str("image/png")?,
+                    size: 123,
+                    thumbnail_hash,
+                }],
+                board_id: Some(board_id),
+                client_key: Some(client_key),
+                content,
+                fingerprint: Some(fingerprint),
+                id,
+                name: Some(name),
+                poll: Some(ThreadCreatedPoll {
+                    closes_at: Some(closes_at),
+                    options: vec!["yes".to_owned(), "no".to_owned()],
+                    question,
+                }),
+                previous_thread_id: Some(previous_thread_id),
+                tags: vec![tag],
+                thread_id,
+                title,
+                trip: Some(trip),
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_filled() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 1000;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "filled",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Filled(ThreadFilled {
+                at,
+                id,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_malformed() {
+        let json = |at: &str, id: &str, thread_id: &str| {
+            format!(
+                r#"{{"at":"{at}","id":"{id}","kind":"closed","thread_id":"{thread_id}","version":2}}"#
+            )
+        };
+        let at = "2023-10-01T12:00:00.000Z";
+        let id = "0779b098-f41d-404a-b055-36463a7c009b";
+        let thread_id = "b8392399-53a3-4f8e-8288-875448037455";
+        assert!(serde_json::from_str::<ThreadEvent>(&json(at, id, thread_id)).is_ok());
+        assert!(serde_json::from_str::<ThreadEvent>(&json("yesterday", id, thread_id)).is_err());
+        assert!(serde_json::from_str::<ThreadEvent>(&json(at, "1", thread_id)).is_err());
+        assert!(serde_json::from_str::<ThreadEvent>(&json(at, id, "b8392399")).is_err());
+    }
+
+    #[test]
+    fn test_message_event_malformed_message_reacted() {
+        let json = |reaction: &str, reactor: &str| {
+            format!(
+                r#"{{"at":"2023-10-01T12:00:00.000Z","id":"0779b098-f41d-404a-b055-36463a7c009b","kind":"message_reacted","number":2,"reaction":"{reaction}","reactor":"{reactor}","thread_id":"b8392399-53a3-4f8e-8288-875448037455","version":3}}"#
+            )
+        };
+        let reaction = "thumbs_up";
+        let reactor = "pG0n5h1Yy3KLbqnA";
+        assert!(serde_json::from_str::<ThreadEvent>(&json(reaction, reactor)).is_ok());
+        assert!(serde_json::from_str::<ThreadEvent>(&json("thumbs_sideways", reactor)).is_err());
+        assert!(serde_json::from_str::<ThreadEvent>(&json(reaction, "pG0n5h1Y")).is_err());
+    }
+
+    #[test]
+    fn test_message_event_message_deleted() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let number = 2;
+        let reason = crate::model::write::MessageDeletionReason::from_str("moderator")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "message_deleted",
+    "number": {number},
+    "reason": "{reason}",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::MessageDeleted(ThreadMessageDeleted {
+                at,
+                id,
+                number,
+                reason,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_message_edited() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let content = "Edited message".to_owned();
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let number = 2;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "content": "{content}",
+    "id": "{id}",
+    "kind": "message_edited",
+    "number": {number},
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::MessageEdited(ThreadMessageEdited {
                 at,
                 content,
                 id,
+                number,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_message_reacted() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let number = 2;
+        let reaction = crate::model::write::MessageReaction::from_str("thumbs_up")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "message_reacted",
+    "number": {number},
+    "reaction": "{reaction}",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::MessageReacted(ThreadMessageReacted {
+                at,
+                id,
+                number,
+                reaction,
+                reactor: None,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_message_unreacted() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let number = 2;
+        let reaction = crate::model::write::MessageReaction::from_str("thumbs_up")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "message_unreacted",
+    "number": {number},
+    "reaction": "{reaction}",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::MessageUnreacted(ThreadMessageUnreacted {
+                at,
+                id,
+                number,
+                reaction,
+                reactor: None,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_pinned() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "pinned",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Pinned(ThreadPinned {
+                at,
+                id,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_poll_voted() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        let voter = crate::model::write::VoterKey::from_str("K8xJ2mQp0aK8xJ2m")?;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "poll_voted",
+    "option": 1,
+    "thread_id": "{thread_id}",
+    "version": {version},
+    "voter": "{voter}"
+}}"#
+            ))?,
+            ThreadEvent::PollVoted(ThreadPollVoted {
+                at,
+                id,
+                option: 1,
+                thread_id,
+                version,
+                voter,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_reopened() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "reopened",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Reopened(ThreadReopened {
+                at,
+                id,
                 thread_id,
                 version,
             })
@@ -90,27 +898,200 @@ mod tests {
 
     #[test]
     fn test_message_event_replied() -> anyhow::Result<()> {
-        let at = "2023-10-01T12:00:00.000Z".to_owned();
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let client_key = crate::model::write::ClientKey::from_str("pG0n5h1Yy3KLbqnA")?;
         let content = "Reply to message".to_owned();
-        let id = "0779b098-f41d-404a-b055-36463a7c009b".to_owned();
-        let thread_id = "b8392399-53a3-4f8e-8288-875448037455".to_owned();
+        let fingerprint = crate::model::write::ContentFingerprint::from_str(
+            "LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ",
+        )?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let name = "Alice".to_owned();
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let trip = "K8xJ2mQp0a".to_owned();
         let version = 2;
         assert_eq!(
             serde_json::from_str::<ThreadEvent>(&format!(
                 r#"
 {{
     "at": "{at}",
+    "client_key": "{client_key}",
     "content": "{content}",
+    "fingerprint": "{fingerprint}",
     "id": "{id}",
     "kind": "replied",
+    "name": "{name}",
     "thread_id": "{thread_id}",
+    "trip": "{trip}",
     "version": {version}
 }}"#
             ))?,
             ThreadEvent::Replied(ThreadReplied {
                 at,
+                attachments: vec![],
+                client_key: Some(client_key),
                 content,
+                fingerprint: Some(fingerprint),
+                id,
+                name: Some(name),
+                sage: false,
+                thread_id,
+                trip: Some(trip),
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_replied_with_sage() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let replied = |sage: bool| {
+            ThreadEvent::Replied(ThreadReplied {
+                at,
+                attachments: vec![],
+                client_key: None,
+                content: "Reply to message".to_owned(),
+                fingerprint: None,
+                id: id.clone(),
+                name: None,
+                sage,
+                thread_id: thread_id.clone(),
+                trip: None,
+                version: 2,
+            })
+        };
+        let json = serde_json::to_string(&replied(true))?;
+        assert!(json.contains(r#""sage":true"#));
+        assert_eq!(serde_json::from_str::<ThreadEvent>(&json)?, replied(true));
+        let json = serde_json::to_string(&replied(false))?;
+        assert!(!json.contains(r#""sage""#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_tagged() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let tag = "rust".to_owned();
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "tagged",
+    "tag": "{tag}",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Tagged(ThreadTagged {
+                at,
+                id,
+                tag,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_title_changed() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let title = "New title".to_owned();
+        let version = 2;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "title_changed",
+    "thread_id": "{thread_id}",
+    "title": "{title}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::TitleChanged(ThreadTitleChanged {
+                at,
+                id,
+                thread_id,
+                title,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_unpinned() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "unpinned",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Unpinned(ThreadUnpinned {
+                at,
+                id,
+                thread_id,
+                version,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_message_event_untagged() -> anyhow::Result<()> {
+        let at = crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?;
+        let id =
+            crate::model::shared::id::EventId::from_str("0779b098-f41d-404a-b055-36463a7c009b")?;
+        let tag = "rust".to_owned();
+        let thread_id =
+            crate::model::shared::id::ThreadId::from_str("b8392399-53a3-4f8e-8288-875448037455")?;
+        let version = 3;
+        assert_eq!(
+            serde_json::from_str::<ThreadEvent>(&format!(
+                r#"
+{{
+    "at": "{at}",
+    "id": "{id}",
+    "kind": "untagged",
+    "tag": "{tag}",
+    "thread_id": "{thread_id}",
+    "version": {version}
+}}"#
+            ))?,
+            ThreadEvent::Untagged(ThreadUntagged {
+                at,
                 id,
+                tag,
                 thread_id,
                 version,
             })
@@ -0,0 +1,127 @@
+use crate::model::shared::event::ThreadEvent;
+
+/// The one who caused the event
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ThreadEventActor {
+    /// An anonymous poster, identified by the client key
+    Anonymous { id: String },
+    /// A request authenticated with the moderator token
+    Moderator,
+}
+
+/// The context of the request that caused the events, kept for auditing and debugging
+///
+/// The metadata is not part of the event itself, so it never affects the replay.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadEventMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<ThreadEventActor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub causation_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ip_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+impl ThreadEventMetadata {
+    /// Wraps the events emitted by one command
+    ///
+    /// The first event is caused by the request itself (`causation_id`), and each following event
+    /// by the event before it.
+    pub fn envelop(&self, events: &[ThreadEvent]) -> Vec<ThreadEventEnvelope> {
+        let mut causation_id = self.causation_id.clone();
+        events
+            .iter()
+            .map(|event| ThreadEventEnvelope {
+                event: event.clone(),
+                metadata: Self {
+                    causation_id: causation_id.replace(event.id().to_string()),
+                    ..self.clone()
+                },
+            })
+            .collect()
+    }
+}
+
+/// An event with the metadata of the request that caused it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThreadEventEnvelope {
+    pub event: ThreadEvent,
+    pub metadata: ThreadEventMetadata,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelop() -> anyhow::Result<()> {
+        let (thread, created) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        let (_, closed) = thread.close()?;
+        let events = [created, closed].concat();
+        let metadata = ThreadEventMetadata {
+            actor: Some(ThreadEventActor::Moderator),
+            causation_id: Some("request".to_owned()),
+            correlation_id: Some("correlation".to_owned()),
+            request_id: Some("request".to_owned()),
+            ..Default::default()
+        };
+
+        let envelopes = metadata.envelop(&events);
+        assert_eq!(envelopes.len(), 2);
+        assert_eq!(envelopes[0].event, events[0]);
+        assert_eq!(
+            envelopes[0].metadata.causation_id.as_deref(),
+            Some("request")
+        );
+        assert_eq!(
+            envelopes[1].metadata.causation_id,
+            Some(events[0].id().to_string())
+        );
+        assert_eq!(
+            envelopes[1].metadata,
+            ThreadEventMetadata {
+                causation_id: Some(events[0].id().to_string()),
+                ..metadata
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde() -> anyhow::Result<()> {
+        let metadata = ThreadEventMetadata {
+            actor: Some(ThreadEventActor::Anonymous {
+                id: "fxZ3Zr9Xy2cQ0a1b".to_owned(),
+            }),
+            request_id: Some("42".to_owned()),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&metadata)?;
+        assert_eq!(
+            json,
+            r#"{"actor":{"kind":"anonymous","id":"fxZ3Zr9Xy2cQ0a1b"},"request_id":"42"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ThreadEventMetadata>(&json)?,
+            metadata
+        );
+        assert_eq!(
+            serde_json::from_str::<ThreadEventMetadata>("{}")?,
+            ThreadEventMetadata::default()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::model::shared::event::ThreadEvent;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventSchemaError {
+    #[error("deserialize")]
+    Deserialize(#[source] serde_json::Error),
+    #[error("kind mismatch (kind: {kind}, data kind: {data_kind:?})")]
+    KindMismatch {
+        data_kind: Option<String>,
+        kind: String,
+    },
+    #[error("serialize")]
+    Serialize(#[source] serde_json::Error),
+    #[error("unknown kind: {0}")]
+    UnknownKind(String),
+    #[error("unsupported schema version (kind: {kind}, schema version: {schema_version})")]
+    UnsupportedSchemaVersion { kind: String, schema_version: u32 },
+}
+
+/// Returns the current schema version of the event kind
+///
+/// The version must be incremented, with an upcaster in `upcast`, whenever the payload of the kind
+/// changes in a way that older payloads cannot be deserialized into the current struct as is.
+///
+/// - `created` and `replied`
+///   - v1: the initial version
+///   - v2: `fingerprint` (and `client_key`) added
+/// - others
+///   - v1: the initial version
+pub fn current_schema_version(kind: &str) -> Option<u32> {
+    match kind {
+        "created" | "replied" => Some(2),
+        "closed" | "continued" | "filled" | "message_deleted" | "message_edited"
+        | "message_reacted" | "message_unreacted" | "pinned" | "poll_voted" | "reopened"
+        | "tagged" | "title_changed" | "unpinned" | "untagged" => Some(1),
+        _ => None,
+    }
+}
+
+/// A serialized event as it is stored, with the schema version of its payload
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredThreadEvent {
+    pub data: String,
+    pub kind: String,
+    pub schema_version: u32,
+}
+
+impl StoredThreadEvent {
+    /// Serializes the event with the current schema version of its kind
+    pub fn serialize(event: &ThreadEvent) -> Result<Self, EventSchemaError> {
+        let kind = event.kind();
+        let schema_version = current_schema_version(kind)
+            .ok_or_else(|| EventSchemaError::UnknownKind(kind.to_owned()))?;
+        Ok(Self {
+            data: serde_json::to_string(event).map_err(EventSchemaError::Serialize)?,
+            kind: kind.to_owned(),
+            schema_version,
+        })
+    }
+
+    /// Deserializes the event, upcasting the payload of an older schema version to the current one
+    pub fn deserialize(&self) -> Result<ThreadEvent, EventSchemaError> {
+        let current = current_schema_version(&self.kind)
+            .ok_or_else(|| EventSchemaError::UnknownKind(self.kind.clone()))?;
+        if self.schema_version == 0 || self.schema_version > current {
+            return Err(EventSchemaError::UnsupportedSchemaVersion {
+                kind: self.kind.clone(),
+                schema_version: self.schema_version,
+            });
+        }
+        let mut data =
+            serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&self.data)
+                .map_err(EventSchemaError::Deserialize)?;
+        let data_kind = data.get("kind").and_then(|it| it.as_str());
+        if data_kind != Some(self.kind.as_str()) {
+            return Err(EventSchemaError::KindMismatch {
+                data_kind: data_kind.map(str::to_owned),
+                kind: self.kind.clone(),
+            });
+        }
+        for schema_version in self.schema_version..current {
+            upcast(&self.kind, schema_version, &mut data)?;
+        }
+        serde_json::from_value::<ThreadEvent>(serde_json::Value::Object(data))
+            .map_err(EventSchemaError::Deserialize)
+    }
+}
+
+/// Converts the payload of the kind from `schema_version` to `schema_version + 1`
+fn upcast(
+    kind: &str,
+    schema_version: u32,
+    data: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<(), EventSchemaError> {
+    match (kind, schema_version) {
+        ("created", 1) | ("replied", 1) => {
+            // the fingerprint is derived from the content, but the client is unknown
+            let fingerprint = data
+                .get("content")
+                .and_then(|it| it.as_str())
+                .and_then(|it| crate::model::write::MessageContent::try_from(it.to_owned()).ok())
+                .map(|it| crate::model::write::ContentFingerprint::of(&it).to_string());
+            if let Some(fingerprint) = fingerprint {
+                data.entry("fingerprint")
+                    .or_insert(serde_json::Value::String(fingerprint));
+            }
+            Ok(())
+        }
+        _ => Err(EventSchemaError::UnsupportedSchemaVersion {
+            kind: kind.to_owned(),
+            schema_version,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use crate::model::shared::event::ThreadCreated;
+    use crate::model::shared::event::ThreadCreatedPoll;
+    use crate::model::shared::event::ThreadMessageAttachment;
+    use crate::model::shared::event::ThreadReplied;
+
+    use super::*;
+
+    /// The payloads of every kind in schema version 1
+    ///
+    /// These fixtures must never be changed, so that the events stored in v1 can always be read.
+    /// The events stored before `data` existed are converted into v1 by the SQLite migrations and
+    /// tested against the baseline tables in `SqliteStore`.
+    const V1_FIXTURES: [(&str, &str); 16] = [
+        ("closed", include_str!("event_schema/v1/closed.json")),
+        ("continued", include_str!("event_schema/v1/continued.json")),
+        ("created", include_str!("event_schema/v1/created.json")),
+        ("filled", include_str!("event_schema/v1/filled.json")),
+        (
+            "message_deleted",
+            include_str!("event_schema/v1/message_deleted.json"),
+        ),
+        (
+            "message_edited",
+            include_str!("event_schema/v1/message_edited.json"),
+        ),
+        (
+            "message_reacted",
+            include_str!("event_schema/v1/message_reacted.json"),
+        ),
+        (
+            "message_unreacted",
+            include_str!("event_schema/v1/message_unreacted.json"),
+        ),
+        ("pinned", include_str!("event_schema/v1/pinned.json")),
+        (
+            "poll_voted",
+            include_str!("event_schema/v1/poll_voted.json"),
+        ),
+        ("reopened", include_str!("event_schema/v1/reopened.json")),
+        ("replied", include_str!("event_schema/v1/replied.json")),
+        ("tagged", include_str!("event_schema/v1/tagged.json")),
+        (
+            "title_changed",
+            include_str!("event_schema/v1/title_changed.json"),
+        ),
+        ("unpinned", include_str!("event_schema/v1/unpinned.json")),
+        ("untagged", include_str!("event_schema/v1/untagged.json")),
+    ];
+
+    fn v1(kind: &str) -> StoredThreadEvent {
+        let (_, data) = V1_FIXTURES
+            .iter()
+            .find(|(it, _)| *it == kind)
+            .expect("fixture to exist");
+        StoredThreadEvent {
+            data: data.to_string(),
+            kind: kind.to_owned(),
+            schema_version: 1,
+        }
+    }
+
+    #[test]
+    fn test_deserialize_v1() -> anyhow::Result<()> {
+        for (kind, _) in V1_FIXTURES {
+            let event = v1(kind).deserialize()?;
+            assert_eq!(event.kind(), kind);
+            // upcasted events are stored again in the current schema version
+            let stored = StoredThreadEvent::serialize(&event)?;
+            assert_eq!(Some(stored.schema_version), current_schema_version(kind));
+            assert_eq!(stored.deserialize()?, event);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_v1_created() -> anyhow::Result<()> {
+        let content = "Hello, world!".to_owned();
+        let fingerprint = crate::model::write::ContentFingerprint::of(
+            &crate::model::write::MessageContent::try_from(content.clone())?,
+        );
+        assert_eq!(
+            v1("created").deserialize()?,
+            ThreadEvent::Created(ThreadCreated {
+                at: crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?,
+                attachments: vec![ThreadMessageAttachment {
+                    hash: crate::model::shared::id::BlobHash::from_str(
+                        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                    )?,
+                    mime: crate::model::write::MessageAttachmentMime::from_str("image/png")?,
+                    size: 123,
+                    thumbnail_hash: crate::model::shared::id::BlobHash::from_str(
+                        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+                    )?,
+                }],
+                board_id: Some(crate::model::shared::id::BoardId::from_str("dev")?),
+                client_key: None,
+                content,
+                fingerprint: Some(fingerprint),
+                id: crate::model::shared::id::EventId::from_str(
+                    "0779b098-f41d-404a-b055-36463a7c009b"
+                )?,
+                name: Some("Alice".to_owned()),
+                poll: Some(ThreadCreatedPoll {
+                    closes_at: Some(crate::utils::date_time::DateTime::from_str(
+                        "2023-10-02T12:00:00.000Z",
+                    )?),
+                    options: vec!["yes".to_owned(), "no".to_owned()],
+                    question: "Agree?".to_owned(),
+                }),
+                previous_thread_id: Some(crate::model::shared::id::ThreadId::from_str(
+                    "4a4d1b0e-2f8c-4f55-9e0e-1c8a6f0d8b7e",
+                )?),
+                tags: vec!["rust".to_owned()],
+                thread_id: crate::model::shared::id::ThreadId::from_str(
+                    "b8392399-53a3-4f8e-8288-875448037455",
+                )?,
+                title: "Hello".to_owned(),
+                trip: Some("K8xJ2mQp0a".to_owned()),
+                version: 1,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_v1_replied() -> anyhow::Result<()> {
+        let content = "Reply to message".to_owned();
+        let fingerprint = crate::model::write::ContentFingerprint::of(
+            &crate::model::write::MessageContent::try_from(content.clone())?,
+        );
+        assert_eq!(
+            v1("replied").deserialize()?,
+            ThreadEvent::Replied(ThreadReplied {
+                at: crate::utils::date_time::DateTime::from_str("2023-10-01T12:00:00.000Z")?,
+                attachments: vec![],
+                client_key: None,
+                content,
+                fingerprint: Some(fingerprint),
+                id: crate::model::shared::id::EventId::from_str(
+                    "0779b098-f41d-404a-b055-36463a7c009b"
+                )?,
+                name: Some("Alice".to_owned()),
+                sage: true,
+                thread_id: crate::model::shared::id::ThreadId::from_str(
+                    "b8392399-53a3-4f8e-8288-875448037455",
+                )?,
+                trip: Some("K8xJ2mQp0a".to_owned()),
+                version: 2,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_unsupported() {
+        let stored = |kind: &str, schema_version: u32| StoredThreadEvent {
+            schema_version,
+            ..v1(kind)
+        };
+        assert!(matches!(
+            stored("created", 3).deserialize(),
+            Err(EventSchemaError::UnsupportedSchemaVersion { .. })
+        ));
+        assert!(matches!(
+            stored("closed", 0).deserialize(),
+            Err(EventSchemaError::UnsupportedSchemaVersion { .. })
+        ));
+        assert!(matches!(
+            StoredThreadEvent {
+                kind: "deleted".to_owned(),
+                ..v1("closed")
+            }
+            .deserialize(),
+            Err(EventSchemaError::UnknownKind(_))
+        ));
+        assert!(matches!(
+            StoredThreadEvent {
+                kind: "reopened".to_owned(),
+                ..v1("closed")
+            }
+            .deserialize(),
+            Err(EventSchemaError::KindMismatch { .. })
+        ));
+    }
+}
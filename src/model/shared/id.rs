@@ -1,5 +1,9 @@
+mod blob_hash;
+mod board_id;
 mod event_id;
 mod thread_id;
 
+pub use self::blob_hash::{BlobHash, BlobHashError};
+pub use self::board_id::{BoardId, BoardIdError};
 pub use self::event_id::{EventId, EventIdError};
 pub use self::thread_id::{ThreadId, ThreadIdError};
@@ -0,0 +1,80 @@
+#[derive(Debug, thiserror::Error)]
+#[error("blob hash error")]
+pub struct BlobHashError(#[source] Box<dyn std::error::Error + Send + Sync>);
+
+/// The lowercase hex SHA-256 of the content of a blob
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct BlobHash(String);
+
+impl BlobHash {
+    const LEN: usize = 64;
+
+    pub fn of(bytes: &[u8]) -> Self {
+        use sha2::Digest as _;
+        let digest = sha2::Sha256::digest(bytes);
+        Self(digest.iter().map(|it| format!("{it:02x}")).collect())
+    }
+}
+
+impl std::str::FromStr for BlobHash {
+    type Err = BlobHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != Self::LEN {
+            return Err(BlobHashError("invalid length".into()));
+        }
+        if !s
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        {
+            return Err(BlobHashError("invalid character".into()));
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl std::fmt::Display for BlobHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BlobHash {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for BlobHash {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_of() {
+        assert_eq!(
+            BlobHash::of(b"").to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(BlobHash::of(b"a"), BlobHash::of(b"b"));
+    }
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        let hash = BlobHash::of(b"hello");
+        assert_eq!(BlobHash::from_str(&hash.to_string())?, hash);
+        assert!(BlobHash::from_str("").is_err());
+        assert!(BlobHash::from_str(&"A".repeat(64)).is_err());
+        assert!(BlobHash::from_str(&"../".repeat(21)).is_err());
+        assert!(BlobHash::from_str(&"a".repeat(65)).is_err());
+        Ok(())
+    }
+}
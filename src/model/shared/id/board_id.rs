@@ -0,0 +1,82 @@
+#[derive(Debug, thiserror::Error)]
+#[error("board id error")]
+pub struct BoardIdError(#[source] Box<dyn std::error::Error + Send + Sync>);
+
+/// A URL-safe slug of a board (e.g. `dev`, `ops`)
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct BoardId(String);
+
+impl BoardId {
+    const MAX_LEN: usize = 32;
+}
+
+impl Default for BoardId {
+    /// The board that threads created before boards existed belong to
+    fn default() -> Self {
+        Self("default".to_owned())
+    }
+}
+
+impl std::str::FromStr for BoardId {
+    type Err = BoardIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.len() > Self::MAX_LEN {
+            return Err(BoardIdError("invalid length".into()));
+        }
+        if !s
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            return Err(BoardIdError("invalid character".into()));
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl std::fmt::Display for BoardId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BoardId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for BoardId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_impl_default() {
+        assert_eq!(BoardId::default().to_string(), "default");
+    }
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        assert_eq!(BoardId::from_str("dev")?.to_string(), "dev");
+        assert_eq!(BoardId::from_str("dev-ops_2")?.to_string(), "dev-ops_2");
+        assert!(BoardId::from_str(&"x".repeat(32)).is_ok());
+        assert!(BoardId::from_str(&"x".repeat(33)).is_err());
+        assert!(BoardId::from_str("").is_err());
+        assert!(BoardId::from_str("Dev").is_err());
+        assert!(BoardId::from_str("dev/ops").is_err());
+        assert_eq!(
+            BoardId::from_str("../").unwrap_err().to_string(),
+            "board id error"
+        );
+        Ok(())
+    }
+}
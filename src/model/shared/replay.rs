@@ -0,0 +1,158 @@
+use crate::model::shared::event::ThreadEvent;
+use crate::model::shared::id::EventId;
+use crate::model::shared::id::ThreadId;
+
+/// A violation found while replaying the stored events of a thread
+///
+/// The stored events are not trusted to be consistent (they may be corrupted or edited by hand), so
+/// the replay reports the offending event instead of panicking.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("duplicate created event (thread: {thread_id}, event: {event_id}, version: {version})")]
+    DuplicateCreated {
+        event_id: EventId,
+        thread_id: ThreadId,
+        version: u32,
+    },
+    #[error("no events to replay")]
+    Empty,
+    #[error(
+        "invalid {field} in event (thread: {thread_id}, event: {event_id}, version: {version})"
+    )]
+    InvalidField {
+        event_id: EventId,
+        field: &'static str,
+        thread_id: ThreadId,
+        version: u32,
+    },
+    #[error("first event is not created (thread: {thread_id}, event: {event_id}, kind: {kind})")]
+    NotCreatedFirst {
+        event_id: EventId,
+        kind: &'static str,
+        thread_id: ThreadId,
+    },
+    #[error(
+        "version out of order (thread: {thread_id}, event: {event_id}, expected: {expected}, actual: {actual})"
+    )]
+    VersionOutOfOrder {
+        actual: u32,
+        event_id: EventId,
+        expected: u32,
+        thread_id: ThreadId,
+    },
+}
+
+impl ReplayError {
+    pub fn duplicate_created(event: &ThreadEvent) -> Self {
+        Self::DuplicateCreated {
+            event_id: event.id().clone(),
+            thread_id: event.thread_id().clone(),
+            version: u32::from(event.version()),
+        }
+    }
+
+    pub fn invalid_field(event: &ThreadEvent, field: &'static str) -> Self {
+        Self::InvalidField {
+            event_id: event.id().clone(),
+            field,
+            thread_id: event.thread_id().clone(),
+            version: u32::from(event.version()),
+        }
+    }
+
+    pub fn not_created_first(event: &ThreadEvent) -> Self {
+        Self::NotCreatedFirst {
+            event_id: event.id().clone(),
+            kind: event.kind(),
+            thread_id: event.thread_id().clone(),
+        }
+    }
+}
+
+/// Checks that the event can be applied to a thread of `version`
+///
+/// An event must not be `Created`, and its version must be the next of `version`.
+pub fn check_next(version: u32, event: &ThreadEvent) -> Result<(), ReplayError> {
+    if let ThreadEvent::Created(_) = event {
+        return Err(ReplayError::duplicate_created(event));
+    }
+    let actual = u32::from(event.version());
+    match version.checked_add(1) {
+        Some(expected) if expected == actual => Ok(()),
+        expected => Err(ReplayError::VersionOutOfOrder {
+            actual,
+            event_id: event.id().clone(),
+            expected: expected.unwrap_or(version),
+            thread_id: event.thread_id().clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::shared::event::ThreadClosed;
+    use crate::model::shared::event::ThreadCreated;
+
+    use super::*;
+
+    #[test]
+    fn test_check_next() -> anyhow::Result<()> {
+        let thread_id = "c4ac95d6-45c7-4006-b768-2a172dee3f81".parse::<ThreadId>()?;
+        let closed = |version: u32| -> anyhow::Result<ThreadEvent> {
+            Ok(ThreadEvent::Closed(ThreadClosed {
+                at: "2023-10-01T00:00:00.000Z".parse()?,
+                id: "4f24e399-d53a-4779-af3e-3fdfdd00f8c5".parse()?,
+                thread_id: thread_id.clone(),
+                version,
+            }))
+        };
+        let created = ThreadEvent::Created(ThreadCreated {
+            at: "2023-10-01T00:00:00.000Z".parse()?,
+            attachments: vec![],
+            board_id: None,
+            client_key: None,
+            content: "content".to_owned(),
+            fingerprint: None,
+            id: "99164b55-98d0-4e7c-98cf-95f7c43da68f".parse()?,
+            name: None,
+            poll: None,
+            previous_thread_id: None,
+            tags: vec![],
+            thread_id: thread_id.clone(),
+            title: "title".to_owned(),
+            trip: None,
+            version: 2,
+        });
+
+        assert!(check_next(1, &closed(2)?).is_ok());
+        assert!(matches!(
+            check_next(1, &created),
+            Err(ReplayError::DuplicateCreated { version: 2, .. })
+        ));
+        assert!(matches!(
+            check_next(1, &closed(3)?),
+            Err(ReplayError::VersionOutOfOrder {
+                actual: 3,
+                expected: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            check_next(2, &closed(2)?),
+            Err(ReplayError::VersionOutOfOrder {
+                actual: 2,
+                expected: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            check_next(u32::MAX, &closed(0)?),
+            Err(ReplayError::VersionOutOfOrder { .. })
+        ));
+        assert!(matches!(
+            ReplayError::not_created_first(&closed(1)?),
+            ReplayError::NotCreatedFirst { kind: "closed", .. }
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::model::shared::event::ThreadCreatedPoll;
+use crate::model::shared::event::ThreadMessageAttachment;
+use crate::model::shared::id::BoardId;
+use crate::model::shared::id::ThreadId;
+use crate::model::write::ClientKey;
+use crate::model::write::MessageReaction;
+use crate::model::write::VoterKey;
+use crate::utils::date_time::DateTime;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadSnapshotError {
+    #[error("deserialize")]
+    Deserialize(#[source] serde_json::Error),
+    #[error("invalid {0} in snapshot")]
+    InvalidField(&'static str),
+    #[error("serialize")]
+    Serialize(#[source] serde_json::Error),
+    #[error("unsupported format version: {0}")]
+    UnsupportedFormatVersion(u32),
+}
+
+/// The state of a `write::Thread` at a version
+///
+/// Only the events after the version need to be replayed to load the thread. A snapshot is a cache
+/// of the events, so it can always be discarded and rebuilt from them.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadSnapshot {
+    pub board_id: BoardId,
+    pub closed: bool,
+    pub deleted_message_numbers: Vec<u16>,
+    pub filled: bool,
+    pub id: ThreadId,
+    pub messages: Vec<ThreadSnapshotMessage>,
+    pub next_thread_id: Option<ThreadId>,
+    pub pinned: bool,
+    pub poll: Option<ThreadCreatedPoll>,
+    pub poll_voters: Vec<VoterKey>,
+    pub previous_thread_id: Option<ThreadId>,
+    pub reactions: Vec<ThreadSnapshotReaction>,
+    pub tags: Vec<String>,
+    pub title: String,
+    pub version: u32,
+}
+
+impl ThreadSnapshot {
+    /// The format of the snapshot
+    ///
+    /// It must be incremented whenever `write::Thread` or this struct changes. The snapshots in
+    /// other formats are ignored and rebuilt from the events.
+    pub const FORMAT_VERSION: u32 = 2;
+
+    /// The interval of the versions at which a snapshot is taken
+    pub const INTERVAL: usize = 100;
+
+    /// Returns whether a snapshot is taken when the thread is stored from `from` to `to`
+    ///
+    /// A snapshot is taken each time the version passes a multiple of `INTERVAL`, so that about
+    /// `INTERVAL` events at most are replayed on top of the latest snapshot.
+    pub fn is_due(
+        from: Option<crate::model::write::Version>,
+        to: crate::model::write::Version,
+    ) -> bool {
+        let interval = u32::try_from(Self::INTERVAL).expect("INTERVAL to fit in u32");
+        from.map_or(0, u32::from) / interval < u32::from(to) / interval
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadSnapshotMessage {
+    pub attachments: Vec<ThreadMessageAttachment>,
+    pub client_key: Option<ClientKey>,
+    pub content: String,
+    pub created_at: DateTime,
+    pub name: Option<String>,
+    pub trip: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ThreadSnapshotReaction {
+    pub count: u32,
+    pub number: u16,
+    pub reaction: MessageReaction,
+    /// The client keys of the reactors, which may be fewer than the count
+    pub reactors: Vec<ClientKey>,
+}
+
+/// A serialized snapshot as it is stored, with its format version
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredThreadSnapshot {
+    pub data: String,
+    pub format_version: u32,
+    pub version: u32,
+}
+
+impl StoredThreadSnapshot {
+    pub fn serialize(snapshot: &ThreadSnapshot) -> Result<Self, ThreadSnapshotError> {
+        Ok(Self {
+            data: serde_json::to_string(snapshot).map_err(ThreadSnapshotError::Serialize)?,
+            format_version: ThreadSnapshot::FORMAT_VERSION,
+            version: snapshot.version,
+        })
+    }
+
+    /// Deserializes the snapshot, which must be in the current format
+    pub fn deserialize(&self) -> Result<ThreadSnapshot, ThreadSnapshotError> {
+        if self.format_version != ThreadSnapshot::FORMAT_VERSION {
+            return Err(ThreadSnapshotError::UnsupportedFormatVersion(
+                self.format_version,
+            ));
+        }
+        let snapshot = serde_json::from_str::<ThreadSnapshot>(&self.data)
+            .map_err(ThreadSnapshotError::Deserialize)?;
+        if snapshot.version != self.version {
+            return Err(ThreadSnapshotError::InvalidField("version"));
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        let version = crate::model::write::Version::from;
+        assert!(!ThreadSnapshot::is_due(None, version(1)));
+        assert!(!ThreadSnapshot::is_due(Some(version(1)), version(99)));
+        assert!(ThreadSnapshot::is_due(Some(version(99)), version(100)));
+        assert!(ThreadSnapshot::is_due(Some(version(1)), version(101)));
+        assert!(!ThreadSnapshot::is_due(Some(version(100)), version(101)));
+        assert!(ThreadSnapshot::is_due(Some(version(150)), version(250)));
+        assert!(ThreadSnapshot::is_due(None, version(100)));
+    }
+
+    #[test]
+    fn test_stored_thread_snapshot() -> anyhow::Result<()> {
+        let snapshot = ThreadSnapshot {
+            board_id: "dev".parse()?,
+            closed: false,
+            deleted_message_numbers: vec![],
+            filled: false,
+            id: "c4ac95d6-45c7-4006-b768-2a172dee3f81".parse()?,
+            messages: vec![ThreadSnapshotMessage {
+                attachments: vec![],
+                client_key: None,
+                content: "content".to_owned(),
+                created_at: "2023-10-01T00:00:00.000Z".parse()?,
+                name: None,
+                trip: None,
+            }],
+            next_thread_id: None,
+            pinned: false,
+            poll: None,
+            poll_voters: vec![],
+            previous_thread_id: None,
+            reactions: vec![],
+            tags: vec![],
+            title: "title".to_owned(),
+            version: 3,
+        };
+        let stored = StoredThreadSnapshot::serialize(&snapshot)?;
+        assert_eq!(stored.format_version, ThreadSnapshot::FORMAT_VERSION);
+        assert_eq!(stored.version, 3);
+        assert_eq!(stored.deserialize()?, snapshot);
+
+        assert!(matches!(
+            StoredThreadSnapshot {
+                format_version: ThreadSnapshot::FORMAT_VERSION + 1,
+                ..stored.clone()
+            }
+            .deserialize(),
+            Err(ThreadSnapshotError::UnsupportedFormatVersion(_))
+        ));
+        assert!(matches!(
+            StoredThreadSnapshot {
+                version: 2,
+                ..stored.clone()
+            }
+            .deserialize(),
+            Err(ThreadSnapshotError::InvalidField("version"))
+        ));
+        assert!(matches!(
+            StoredThreadSnapshot {
+                data: "{}".to_owned(),
+                ..stored
+            }
+            .deserialize(),
+            Err(ThreadSnapshotError::Deserialize(_))
+        ));
+        Ok(())
+    }
+}
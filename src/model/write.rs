@@ -1,9 +1,39 @@
+mod board;
+mod client_key;
+mod content_fingerprint;
+mod content_policy;
+mod duplicate_policy;
 mod message;
+mod message_attachment;
+mod message_attachment_mime;
 mod message_content;
+mod message_deletion_reason;
+mod message_reaction;
+mod poll;
+mod poster;
+mod rate_limit;
 mod thread;
+mod thread_tag;
+mod thread_title;
 mod version;
+mod voter_key;
 
+pub use self::board::{Board, BoardError};
+pub use self::client_key::ClientKey;
+pub use self::content_fingerprint::ContentFingerprint;
+pub use self::content_policy::{ContentPolicy, ContentPolicyError, ContentPolicyViolation};
+pub use self::duplicate_policy::{DuplicateMessage, DuplicatePolicy, PostedMessage};
 pub use self::message::Message;
+pub use self::message_attachment::{MessageAttachment, MessageAttachmentError};
+pub use self::message_attachment_mime::MessageAttachmentMime;
 pub use self::message_content::{MessageContent, MessageContentError};
+pub use self::message_deletion_reason::{MessageDeletionReason, MessageDeletionReasonError};
+pub use self::message_reaction::{MessageReaction, MessageReactionError};
+pub use self::poll::{Poll, PollError};
+pub use self::poster::{Poster, PosterError};
+pub use self::rate_limit::{RateLimit, RateLimitAction, RateLimitExceeded, RateLimitWindow};
 pub use self::thread::{Thread, ThreadError};
-pub use self::version::Version;
+pub use self::thread_tag::{ThreadTag, ThreadTagError};
+pub use self::thread_title::{ThreadTitle, ThreadTitleError};
+pub use self::version::{Version, VersionError};
+pub use self::voter_key::VoterKey;
@@ -0,0 +1,190 @@
+use crate::model::shared::id::BoardId;
+use crate::model::write::Message;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BoardError {
+    #[error("description too long: {0}")]
+    DescriptionTooLong(usize),
+    #[error("invalid max content length: {0}")]
+    InvalidMaxContentLength(u16),
+    #[error("invalid max threads: {0}")]
+    InvalidMaxThreads(u16),
+    #[error("message too long: {0}")]
+    MessageTooLong(usize),
+    #[error("name contains newline")]
+    NameContainsNewline,
+    #[error("name empty")]
+    NameEmpty,
+    #[error("name too long: {0}")]
+    NameTooLong(usize),
+}
+
+/// A board containing threads, with its own limits
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Board {
+    description: String,
+    id: BoardId,
+    max_content_length: u16,
+    max_threads: u16,
+    name: String,
+}
+
+impl Board {
+    const MAX_DESCRIPTION_LEN: usize = 255;
+    const MAX_NAME_LEN: usize = 64;
+    const MAX_THREADS: u16 = 1000;
+
+    pub fn new(
+        id: BoardId,
+        name: String,
+        description: String,
+        max_content_length: u16,
+        max_threads: u16,
+    ) -> Result<Self, BoardError> {
+        let len = name.trim().chars().count();
+        if len == 0 {
+            return Err(BoardError::NameEmpty);
+        }
+        if len > Self::MAX_NAME_LEN {
+            return Err(BoardError::NameTooLong(len));
+        }
+        if name.contains(['\r', '\n']) {
+            return Err(BoardError::NameContainsNewline);
+        }
+        let len = description.chars().count();
+        if len > Self::MAX_DESCRIPTION_LEN {
+            return Err(BoardError::DescriptionTooLong(len));
+        }
+        if max_content_length == 0 {
+            return Err(BoardError::InvalidMaxContentLength(max_content_length));
+        }
+        if !(1..=Self::MAX_THREADS).contains(&max_threads) {
+            return Err(BoardError::InvalidMaxThreads(max_threads));
+        }
+        Ok(Self {
+            description,
+            id,
+            max_content_length,
+            max_threads,
+            name,
+        })
+    }
+
+    /// The board that threads without a board reference belong to
+    ///
+    /// Its messages are limited only by the configured max message content length.
+    pub fn default_board() -> Self {
+        Self {
+            description: String::default(),
+            id: BoardId::default(),
+            max_content_length: u16::MAX,
+            max_threads: 100,
+            name: "default".to_owned(),
+        }
+    }
+
+    /// Checks that the message fits in the limits of this board
+    pub fn check_message(&self, message: &Message) -> Result<(), BoardError> {
+        let len = message.content.length();
+        if len > usize::from(self.max_content_length) {
+            return Err(BoardError::MessageTooLong(len));
+        }
+        Ok(())
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn id(&self) -> &BoardId {
+        &self.id
+    }
+
+    pub fn max_content_length(&self) -> u16 {
+        self.max_content_length
+    }
+
+    pub fn max_threads(&self) -> u16 {
+        self.max_threads
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_new() -> anyhow::Result<()> {
+        let id = BoardId::from_str("dev")?;
+        let board = Board::new(
+            id.clone(),
+            "Development".to_owned(),
+            "talk about development".to_owned(),
+            100,
+            50,
+        )?;
+        assert_eq!(board.id(), &id);
+        assert_eq!(board.name(), "Development");
+        assert_eq!(board.description(), "talk about development");
+        assert_eq!(board.max_content_length(), 100);
+        assert_eq!(board.max_threads(), 50);
+
+        let new = |name: &str, max_content_length: u16, max_threads: u16| {
+            Board::new(
+                id.clone(),
+                name.to_owned(),
+                String::default(),
+                max_content_length,
+                max_threads,
+            )
+        };
+        assert!(new(" ", 100, 50).is_err());
+        assert!(new(&"x".repeat(65), 100, 50).is_err());
+        assert!(new("dev\nops", 100, 50).is_err());
+        assert!(new("dev", 0, 50).is_err());
+        assert!(new("dev", 1000, 50).is_ok());
+        assert!(new("dev", 100, 0).is_err());
+        assert!(new("dev", 100, 1001).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_message() -> anyhow::Result<()> {
+        let board = Board::new(
+            BoardId::from_str("dev")?,
+            "Development".to_owned(),
+            String::default(),
+            5,
+            50,
+        )?;
+        let message = |content: &str| -> anyhow::Result<Message> {
+            Ok(Message::create(
+                crate::model::write::MessageContent::try_from(content.to_owned())?,
+                None,
+                vec![],
+                None,
+            ))
+        };
+        assert!(board.check_message(&message("12345")?).is_ok());
+        assert!(board.check_message(&message("123456")?).is_err());
+        // counted in graphemes like `MessageContent::new`
+        assert!(board.check_message(&message("👍🏽👍🏽👍🏽👍🏽👍🏽")?).is_ok());
+        assert!(
+            board
+                .check_message(&message("e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}")?)
+                .is_ok()
+        );
+        assert!(
+            Board::default_board()
+                .check_message(&message(&"x".repeat(1000))?)
+                .is_ok()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+#[derive(Debug, thiserror::Error)]
+#[error("invalid client key")]
+pub struct ClientKeyError;
+
+/// An opaque key that identifies the client that posted a message
+///
+/// The key is derived from the IP address of the client with an HMAC keyed by the server secret, so
+/// that it cannot be reversed by hashing every address. The address itself is not kept.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ClientKey(String);
+
+impl ClientKey {
+    const LEN: usize = 16;
+
+    /// Derives the key from the IP address with the server secret
+    ///
+    /// The secret must stay the same, or the same client gets another key.
+    pub fn from_ip(ip: &std::net::IpAddr, secret: &str) -> Self {
+        use base64::Engine as _;
+        use hmac::Mac as _;
+        let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC to accept a key of any length");
+        mac.update(format!("client#{ip}").as_bytes());
+        let mut key =
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        key.truncate(Self::LEN);
+        Self(key)
+    }
+}
+
+impl std::fmt::Display for ClientKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for ClientKey {
+    type Err = ClientKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != Self::LEN
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ClientKeyError);
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ClientKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for ClientKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_from_ip() -> anyhow::Result<()> {
+        let ip = std::net::IpAddr::from([192, 0, 2, 1]);
+        let key = ClientKey::from_ip(&ip, "secret");
+        assert_eq!(key, ClientKey::from_ip(&ip, "secret"));
+        assert_ne!(
+            key,
+            ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 2]), "secret")
+        );
+        // the key cannot be derived without the secret
+        assert_ne!(key, ClientKey::from_ip(&ip, "other secret"));
+        assert!(!key.to_string().contains("192"));
+        assert_eq!(ClientKey::from_str(&key.to_string())?, key);
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_from_str() {
+        assert!(ClientKey::from_str("abcdefghijklmnop").is_ok());
+        assert!(ClientKey::from_str("short").is_err());
+        assert!(ClientKey::from_str("abcdefghijklmno!").is_err());
+    }
+}
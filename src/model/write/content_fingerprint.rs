@@ -0,0 +1,102 @@
+use crate::model::write::MessageContent;
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid content fingerprint")]
+pub struct ContentFingerprintError;
+
+/// A hash of the normalized message content to find the same message posted again
+///
+/// The content is normalized so that differences in case and whitespace are ignored.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ContentFingerprint(String);
+
+impl ContentFingerprint {
+    const LEN: usize = 43;
+
+    pub fn of(content: &MessageContent) -> Self {
+        use base64::Engine as _;
+        use sha2::Digest as _;
+        let digest = sha2::Sha256::digest(Self::normalize(content).as_bytes());
+        Self(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest))
+    }
+
+    /// Lowercases the content and collapses every run of whitespace into a single space
+    pub fn normalize(content: &MessageContent) -> String {
+        String::from(content.clone())
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+impl std::fmt::Display for ContentFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for ContentFingerprint {
+    type Err = ContentFingerprintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != Self::LEN
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ContentFingerprintError);
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ContentFingerprint {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for ContentFingerprint {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_of() -> anyhow::Result<()> {
+        let content = |s: &str| MessageContent::try_from(s.to_owned());
+        let fingerprint = ContentFingerprint::of(&content("Hello,  world!\n")?);
+        assert_eq!(
+            fingerprint,
+            ContentFingerprint::of(&content("hello, world!")?)
+        );
+        assert_eq!(
+            fingerprint,
+            ContentFingerprint::of(&content(" HELLO,\r\nWORLD! ")?)
+        );
+        assert_ne!(
+            fingerprint,
+            ContentFingerprint::of(&content("hello world")?)
+        );
+        assert_eq!(
+            ContentFingerprint::from_str(&fingerprint.to_string())?,
+            fingerprint
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_from_str() {
+        assert!(ContentFingerprint::from_str(&"a".repeat(43)).is_ok());
+        assert!(ContentFingerprint::from_str("short").is_err());
+        assert!(ContentFingerprint::from_str(&"!".repeat(43)).is_err());
+    }
+}
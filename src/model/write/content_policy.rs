@@ -0,0 +1,217 @@
+use crate::model::write::MessageContent;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContentPolicyError {
+    #[error("invalid banned pattern")]
+    InvalidBannedPattern(#[source] regex::Error),
+    #[error("unknown forbidden characters: {0}")]
+    UnknownForbiddenCharacters(String),
+}
+
+/// The reason why a message content is rejected by the content policy
+///
+/// The message is shown to the poster as is.
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ContentPolicyViolation {
+    #[error("the message contains a banned expression")]
+    BannedPattern,
+    #[error("the message contains a banned word: {0}")]
+    BannedWord(String),
+    #[error("the message contains a forbidden character: U+{:04X}", u32::from(*.0))]
+    ForbiddenCharacter(char),
+    #[error("the message has too many lines: {0} (max: {1})")]
+    TooManyLines(usize, usize),
+    #[error("the message has too many URLs: {0} (max: {1})")]
+    TooManyUrls(usize, usize),
+}
+
+/// A group of characters that can be forbidden in message contents
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ForbiddenCharacters {
+    /// Characters that change the direction of the text (e.g. U+202E RIGHT-TO-LEFT OVERRIDE)
+    BidiControl,
+    /// Invisible characters (e.g. U+200B ZERO WIDTH SPACE)
+    ZeroWidth,
+}
+
+impl ForbiddenCharacters {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            ForbiddenCharacters::BidiControl => {
+                matches!(
+                    c,
+                    '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+                )
+            }
+            ForbiddenCharacters::ZeroWidth => {
+                matches!(
+                    c,
+                    '\u{180E}' | '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}'
+                )
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ForbiddenCharacters {
+    type Err = ContentPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bidi_control" => Ok(ForbiddenCharacters::BidiControl),
+            "zero_width" => Ok(ForbiddenCharacters::ZeroWidth),
+            _ => Err(ContentPolicyError::UnknownForbiddenCharacters(s.to_owned())),
+        }
+    }
+}
+
+/// The rules that every message content must follow, in addition to the limits of the board
+#[derive(Clone, Debug, Default)]
+pub struct ContentPolicy {
+    banned_patterns: Vec<regex::Regex>,
+    /// Lowercased to be matched case-insensitively
+    banned_words: Vec<String>,
+    forbidden_characters: Vec<ForbiddenCharacters>,
+    max_lines: Option<usize>,
+    max_urls: Option<usize>,
+}
+
+impl ContentPolicy {
+    pub fn new(
+        banned_patterns: &[String],
+        banned_words: &[String],
+        forbidden_characters: &[String],
+        max_lines: Option<usize>,
+        max_urls: Option<usize>,
+    ) -> Result<Self, ContentPolicyError> {
+        Ok(Self {
+            banned_patterns: banned_patterns
+                .iter()
+                .map(|it| regex::Regex::new(it))
+                .collect::<Result<Vec<regex::Regex>, regex::Error>>()
+                .map_err(ContentPolicyError::InvalidBannedPattern)?,
+            banned_words: banned_words
+                .iter()
+                .map(|it| it.trim().to_lowercase())
+                .filter(|it| !it.is_empty())
+                .collect(),
+            forbidden_characters: forbidden_characters
+                .iter()
+                .map(|it| <ForbiddenCharacters as std::str::FromStr>::from_str(it))
+                .collect::<Result<Vec<ForbiddenCharacters>, ContentPolicyError>>()?,
+            max_lines,
+            max_urls,
+        })
+    }
+
+    pub fn check(&self, content: &MessageContent) -> Result<(), ContentPolicyViolation> {
+        let content = String::from(content.clone());
+        if let Some(c) = content
+            .chars()
+            .find(|c| self.forbidden_characters.iter().any(|it| it.contains(*c)))
+        {
+            return Err(ContentPolicyViolation::ForbiddenCharacter(c));
+        }
+        let lowercased = content.to_lowercase();
+        if let Some(word) = self
+            .banned_words
+            .iter()
+            .find(|it| lowercased.contains(it.as_str()))
+        {
+            return Err(ContentPolicyViolation::BannedWord(word.clone()));
+        }
+        if self.banned_patterns.iter().any(|it| it.is_match(&content)) {
+            return Err(ContentPolicyViolation::BannedPattern);
+        }
+        if let Some(max_lines) = self.max_lines {
+            let lines = content.lines().count();
+            if lines > max_lines {
+                return Err(ContentPolicyViolation::TooManyLines(lines, max_lines));
+            }
+        }
+        if let Some(max_urls) = self.max_urls {
+            let urls = content.matches("http://").count() + content.matches("https://").count();
+            if urls > max_urls {
+                return Err(ContentPolicyViolation::TooManyUrls(urls, max_urls));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() -> anyhow::Result<()> {
+        let content = |s: &str| MessageContent::try_from(s.to_owned());
+        let policy = ContentPolicy::default();
+        assert!(
+            policy
+                .check(&content("https://example.com\u{200B}")?)
+                .is_ok()
+        );
+
+        let policy = ContentPolicy::new(
+            &["(?i)buy\\s+now".to_owned()],
+            &["Spam".to_owned(), " ".to_owned()],
+            &["bidi_control".to_owned(), "zero_width".to_owned()],
+            Some(2),
+            Some(1),
+        )?;
+        assert!(
+            policy
+                .check(&content("Hello\nhttps://example.com")?)
+                .is_ok()
+        );
+        assert_eq!(
+            policy.check(&content("no SPAM please")?),
+            Err(ContentPolicyViolation::BannedWord("spam".to_owned()))
+        );
+        assert_eq!(
+            policy.check(&content("Buy  now")?),
+            Err(ContentPolicyViolation::BannedPattern)
+        );
+        assert_eq!(
+            policy.check(&content("a\u{202E}b")?),
+            Err(ContentPolicyViolation::ForbiddenCharacter('\u{202E}'))
+        );
+        assert_eq!(
+            policy.check(&content("a\u{200B}b")?),
+            Err(ContentPolicyViolation::ForbiddenCharacter('\u{200B}'))
+        );
+        assert_eq!(
+            policy.check(&content("1\n2\n3")?),
+            Err(ContentPolicyViolation::TooManyLines(3, 2))
+        );
+        assert_eq!(
+            policy.check(&content("http://a https://b")?),
+            Err(ContentPolicyViolation::TooManyUrls(2, 1))
+        );
+        assert_eq!(
+            ContentPolicyViolation::ForbiddenCharacter('\u{200B}').to_string(),
+            "the message contains a forbidden character: U+200B"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_new() {
+        let new = |patterns: &[&str], forbidden_characters: &[&str]| {
+            ContentPolicy::new(
+                &patterns.iter().map(|it| it.to_string()).collect::<Vec<_>>(),
+                &[],
+                &forbidden_characters
+                    .iter()
+                    .map(|it| it.to_string())
+                    .collect::<Vec<_>>(),
+                None,
+                None,
+            )
+        };
+        assert!(new(&["a+"], &["zero_width"]).is_ok());
+        assert!(new(&["("], &[]).is_err());
+        assert!(new(&[], &["emoji"]).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+
+use crate::model::shared::id::ThreadId;
+use crate::model::write::ClientKey;
+use crate::model::write::ContentFingerprint;
+use crate::model::write::MessageContent;
+use crate::utils::date_time::DateTime;
+
+/// The reason why a message is rejected as a duplicate
+///
+/// The message is shown to the poster as is.
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DuplicateMessage {
+    #[error("the same message has been posted in too many threads: {0}")]
+    CrossPosted(usize),
+    #[error("the same message has just been posted")]
+    Duplicate,
+}
+
+/// A message posted before with the same content fingerprint
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PostedMessage {
+    pub at: DateTime,
+    pub client_key: Option<ClientKey>,
+    pub thread_id: ThreadId,
+}
+
+/// The rules to reject the same content posted again
+///
+/// - the same client posting the same content to the same thread within `duplicate_window`
+///   (e.g. a double-submitted form)
+/// - the same content posted to `cross_post_max_threads` or more threads within
+///   `cross_post_window` by any client (cross-posting spam)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DuplicatePolicy {
+    cross_post_max_threads: usize,
+    cross_post_window: std::time::Duration,
+    duplicate_window: std::time::Duration,
+}
+
+impl Default for DuplicatePolicy {
+    fn default() -> Self {
+        Self {
+            cross_post_max_threads: 3,
+            cross_post_window: std::time::Duration::from_secs(60 * 60),
+            duplicate_window: std::time::Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+impl DuplicatePolicy {
+    /// Short contents (e.g. "thanks") are often posted in many threads without being spam
+    const CROSS_POST_MIN_CHARS: usize = 16;
+
+    pub fn new(
+        cross_post_max_threads: usize,
+        cross_post_window: std::time::Duration,
+        duplicate_window: std::time::Duration,
+    ) -> Self {
+        Self {
+            cross_post_max_threads,
+            cross_post_window,
+            duplicate_window,
+        }
+    }
+
+    pub fn cross_post_max_threads(&self) -> usize {
+        self.cross_post_max_threads
+    }
+
+    pub fn cross_post_window(&self) -> std::time::Duration {
+        self.cross_post_window
+    }
+
+    pub fn duplicate_window(&self) -> std::time::Duration {
+        self.duplicate_window
+    }
+
+    /// Returns the time after which the posted messages are needed by `check`
+    pub fn since(&self, now: DateTime) -> DateTime {
+        let window = self.cross_post_window.max(self.duplicate_window);
+        DateTime::from_unix_timestamp_millis(
+            now.to_unix_timestamp_millis()
+                .saturating_sub(i64::try_from(window.as_millis()).unwrap_or(i64::MAX)),
+        )
+    }
+
+    /// Checks the content posted by the client to the thread, or to a new thread if `thread_id`
+    /// is `None`, against the messages posted before with the same fingerprint
+    pub fn check(
+        &self,
+        content: &MessageContent,
+        posted: &[PostedMessage],
+        client_key: &ClientKey,
+        thread_id: Option<&ThreadId>,
+        now: DateTime,
+    ) -> Result<(), DuplicateMessage> {
+        let within = |window: std::time::Duration, at: &DateTime| {
+            i128::from(now.to_unix_timestamp_millis()) - i128::from(at.to_unix_timestamp_millis())
+                < i128::try_from(window.as_millis()).unwrap_or(i128::MAX)
+        };
+        if posted.iter().any(|it| {
+            it.client_key.as_ref() == Some(client_key)
+                && thread_id.is_none_or(|id| &it.thread_id == id)
+                && within(self.duplicate_window, &it.at)
+        }) {
+            return Err(DuplicateMessage::Duplicate);
+        }
+        if ContentFingerprint::normalize(content).chars().count() < Self::CROSS_POST_MIN_CHARS {
+            return Ok(());
+        }
+        let threads = posted
+            .iter()
+            .filter(|it| thread_id != Some(&it.thread_id) && within(self.cross_post_window, &it.at))
+            .map(|it| &it.thread_id)
+            .collect::<BTreeSet<&ThreadId>>()
+            .len();
+        if threads >= self.cross_post_max_threads {
+            return Err(DuplicateMessage::CrossPosted(threads));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() -> anyhow::Result<()> {
+        let content = |s: &str| MessageContent::try_from(s.to_owned());
+        let at = |secs: i64| DateTime::from_unix_timestamp_millis(secs * 1000);
+        let policy = DuplicatePolicy::new(
+            2,
+            std::time::Duration::from_secs(3600),
+            std::time::Duration::from_secs(600),
+        );
+        let alice = ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 1]), "secret");
+        let bob = ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 2]), "secret");
+        let thread1 = ThreadId::generate();
+        let thread2 = ThreadId::generate();
+        let thread3 = ThreadId::generate();
+        let posted = |client_key: &ClientKey, thread_id: &ThreadId, secs: i64| PostedMessage {
+            at: at(secs),
+            client_key: Some(client_key.clone()),
+            thread_id: thread_id.clone(),
+        };
+        let long = content("Buy cheap watches at example.com")?;
+        let short = content("thanks")?;
+
+        assert_eq!(
+            policy.check(&long, &[], &alice, Some(&thread1), at(0)),
+            Ok(())
+        );
+
+        // duplicates
+        let history = [posted(&alice, &thread1, 0)];
+        assert_eq!(
+            policy.check(&short, &history, &alice, Some(&thread1), at(599)),
+            Err(DuplicateMessage::Duplicate)
+        );
+        assert_eq!(
+            policy.check(&short, &history, &alice, None, at(599)),
+            Err(DuplicateMessage::Duplicate)
+        );
+        assert_eq!(
+            policy.check(&short, &history, &alice, Some(&thread1), at(600)),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&short, &history, &alice, Some(&thread2), at(1)),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&short, &history, &bob, Some(&thread1), at(1)),
+            Ok(())
+        );
+
+        // cross-posts
+        let history = [posted(&alice, &thread1, 0), posted(&bob, &thread2, 10)];
+        assert_eq!(
+            policy.check(&long, &history, &bob, Some(&thread3), at(700)),
+            Err(DuplicateMessage::CrossPosted(2))
+        );
+        assert_eq!(
+            policy.check(&long, &history, &bob, None, at(700)),
+            Err(DuplicateMessage::CrossPosted(2))
+        );
+        assert_eq!(
+            policy.check(&long, &history, &bob, Some(&thread1), at(700)),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&long, &history, &bob, Some(&thread3), at(3600)),
+            Ok(())
+        );
+        assert_eq!(
+            policy.check(&short, &history, &bob, Some(&thread3), at(700)),
+            Ok(())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_since() {
+        let policy = DuplicatePolicy::default();
+        assert_eq!(
+            policy.since(DateTime::from_unix_timestamp_millis(7_200_000)),
+            DateTime::from_unix_timestamp_millis(3_600_000)
+        );
+    }
+}
@@ -1,25 +1,43 @@
+use crate::model::write::ClientKey;
+use crate::model::write::MessageAttachment;
 use crate::model::write::MessageContent;
+use crate::model::write::Poster;
 use crate::utils::date_time::DateTime;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Message {
+    pub attachments: Vec<MessageAttachment>,
+    /// The client that posted the message, if known
+    pub client_key: Option<ClientKey>,
     pub content: MessageContent,
     pub created_at: DateTime,
+    pub poster: Option<Poster>,
 }
 
 impl Message {
-    pub fn create(content: MessageContent) -> Self {
+    pub fn create(
+        content: MessageContent,
+        poster: Option<Poster>,
+        attachments: Vec<MessageAttachment>,
+        client_key: Option<ClientKey>,
+    ) -> Self {
         Self {
+            attachments,
+            client_key,
             content,
             created_at: DateTime::now(),
+            poster,
         }
     }
 
     #[cfg(test)]
     pub fn new_for_testing() -> Self {
         Self {
+            attachments: vec![],
+            client_key: None,
             content: MessageContent::new_for_testing(),
             created_at: DateTime::now(),
+            poster: Some(Poster::new_for_testing()),
         }
     }
 }
@@ -31,8 +49,19 @@ mod tests {
     #[test]
     fn test_create() {
         let content = MessageContent::new_for_testing();
-        let message = Message::create(content.clone());
+        let poster = Poster::new_for_testing();
+        let attachment = MessageAttachment::new_for_testing();
+        let client_key = ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 1]), "secret");
+        let message = Message::create(
+            content.clone(),
+            Some(poster.clone()),
+            vec![attachment.clone()],
+            Some(client_key.clone()),
+        );
+        assert_eq!(message.attachments, vec![attachment]);
+        assert_eq!(message.client_key, Some(client_key));
         assert_eq!(message.content, content);
+        assert_eq!(message.poster, Some(poster));
     }
 
     #[test]
@@ -0,0 +1,100 @@
+use crate::model::shared::id::BlobHash;
+use crate::model::write::MessageAttachmentMime;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MessageAttachmentError {
+    #[error("empty")]
+    Empty,
+    #[error("too large: {0}")]
+    TooLarge(usize),
+    #[error("unsupported mime")]
+    UnsupportedMime,
+}
+
+/// An image attached to a message
+///
+/// The image and its thumbnail are stored in the blob store by the hashes of their contents.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageAttachment {
+    pub hash: BlobHash,
+    pub mime: MessageAttachmentMime,
+    pub size: u32,
+    pub thumbnail_hash: BlobHash,
+}
+
+impl MessageAttachment {
+    pub const MAX_SIZE: usize = 1024 * 1024;
+
+    /// Checks the size and the type of the image before anything is decoded or stored
+    pub fn check(bytes: &[u8]) -> Result<MessageAttachmentMime, MessageAttachmentError> {
+        if bytes.is_empty() {
+            return Err(MessageAttachmentError::Empty);
+        }
+        if bytes.len() > Self::MAX_SIZE {
+            return Err(MessageAttachmentError::TooLarge(bytes.len()));
+        }
+        MessageAttachmentMime::sniff(bytes).ok_or(MessageAttachmentError::UnsupportedMime)
+    }
+
+    pub fn new(bytes: &[u8], thumbnail: &[u8]) -> Result<Self, MessageAttachmentError> {
+        let mime = Self::check(bytes)?;
+        Ok(Self {
+            hash: BlobHash::of(bytes),
+            mime,
+            size: u32::try_from(bytes.len())
+                .map_err(|_| MessageAttachmentError::TooLarge(bytes.len()))?,
+            thumbnail_hash: BlobHash::of(thumbnail),
+        })
+    }
+
+    #[cfg(test)]
+    pub fn new_for_testing() -> Self {
+        let bytes = format!("GIF89a{}", uuid::Uuid::new_v4());
+        Self {
+            hash: BlobHash::of(bytes.as_bytes()),
+            mime: MessageAttachmentMime::Gif,
+            size: bytes.len() as u32,
+            thumbnail_hash: BlobHash::of(b"thumbnail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(matches!(
+            MessageAttachment::check(b"GIF89a"),
+            Ok(MessageAttachmentMime::Gif)
+        ));
+        assert!(matches!(
+            MessageAttachment::check(b""),
+            Err(MessageAttachmentError::Empty)
+        ));
+        assert!(matches!(
+            MessageAttachment::check(b"<svg></svg>"),
+            Err(MessageAttachmentError::UnsupportedMime)
+        ));
+        let mut too_large = b"GIF89a".to_vec();
+        too_large.resize(MessageAttachment::MAX_SIZE + 1, 0);
+        assert!(matches!(
+            MessageAttachment::check(&too_large),
+            Err(MessageAttachmentError::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_new() -> anyhow::Result<()> {
+        let attachment = MessageAttachment::new(b"GIF89a", b"\x89PNG\r\n\x1A\n")?;
+        assert_eq!(attachment.hash, BlobHash::of(b"GIF89a"));
+        assert_eq!(attachment.mime, MessageAttachmentMime::Gif);
+        assert_eq!(attachment.size, 6);
+        assert_eq!(
+            attachment.thumbnail_hash,
+            BlobHash::of(b"\x89PNG\r\n\x1A\n")
+        );
+        Ok(())
+    }
+}
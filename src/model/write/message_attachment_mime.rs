@@ -0,0 +1,119 @@
+#[derive(Debug, thiserror::Error)]
+#[error("unsupported message attachment mime: {0}")]
+pub struct MessageAttachmentMimeError(String);
+
+/// The image types that can be attached to a message
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MessageAttachmentMime {
+    Gif,
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl MessageAttachmentMime {
+    /// Detects the type from the magic bytes, ignoring the type declared by the client
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(MessageAttachmentMime::Gif)
+        } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(MessageAttachmentMime::Jpeg)
+        } else if bytes.starts_with(b"\x89PNG\r\n\x1A\n") {
+            Some(MessageAttachmentMime::Png)
+        } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(MessageAttachmentMime::Webp)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for MessageAttachmentMime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MessageAttachmentMime::Gif => "image/gif",
+                MessageAttachmentMime::Jpeg => "image/jpeg",
+                MessageAttachmentMime::Png => "image/png",
+                MessageAttachmentMime::Webp => "image/webp",
+            }
+        )
+    }
+}
+
+impl std::str::FromStr for MessageAttachmentMime {
+    type Err = MessageAttachmentMimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "image/gif" => Ok(MessageAttachmentMime::Gif),
+            "image/jpeg" => Ok(MessageAttachmentMime::Jpeg),
+            "image/png" => Ok(MessageAttachmentMime::Png),
+            "image/webp" => Ok(MessageAttachmentMime::Webp),
+            _ => Err(MessageAttachmentMimeError(s.to_owned())),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MessageAttachmentMime {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for MessageAttachmentMime {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(
+            MessageAttachmentMime::sniff(b"GIF89a\x01\x00"),
+            Some(MessageAttachmentMime::Gif)
+        );
+        assert_eq!(
+            MessageAttachmentMime::sniff(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(MessageAttachmentMime::Jpeg)
+        );
+        assert_eq!(
+            MessageAttachmentMime::sniff(b"\x89PNG\r\n\x1A\n\x00"),
+            Some(MessageAttachmentMime::Png)
+        );
+        assert_eq!(
+            MessageAttachmentMime::sniff(b"RIFF\x00\x00\x00\x00WEBPVP8 "),
+            Some(MessageAttachmentMime::Webp)
+        );
+        assert_eq!(MessageAttachmentMime::sniff(b"<svg></svg>"), None);
+        assert_eq!(MessageAttachmentMime::sniff(b""), None);
+    }
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        for mime in [
+            MessageAttachmentMime::Gif,
+            MessageAttachmentMime::Jpeg,
+            MessageAttachmentMime::Png,
+            MessageAttachmentMime::Webp,
+        ] {
+            assert_eq!(MessageAttachmentMime::from_str(&mime.to_string())?, mime);
+        }
+        assert_eq!(
+            MessageAttachmentMime::from_str("image/svg+xml")
+                .unwrap_err()
+                .to_string(),
+            "unsupported message attachment mime: image/svg+xml"
+        );
+        Ok(())
+    }
+}
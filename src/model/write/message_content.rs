@@ -1,3 +1,6 @@
+use unicode_normalization::UnicodeNormalization as _;
+use unicode_segmentation::UnicodeSegmentation as _;
+
 #[derive(Debug, thiserror::Error)]
 pub enum MessageContentError {
     #[error("empty")]
@@ -10,6 +13,60 @@ pub enum MessageContentError {
 pub struct MessageContent(String);
 
 impl MessageContent {
+    /// The number of consecutive blank lines that are kept by the normalization
+    const MAX_BLANK_LINES: usize = 2;
+
+    /// Creates a content posted by a client, which must not be longer than `max_length` graphemes
+    ///
+    /// The content is normalized before it is checked. The stored contents are converted by
+    /// `TryFrom<String>` as is instead, so that changing the normalization or lowering the limit
+    /// does not change or reject the existing messages.
+    pub fn new(value: String, max_length: usize) -> Result<Self, MessageContentError> {
+        let content = Self::try_from(Self::normalize(&value))?;
+        let len = content.length();
+        if len > max_length {
+            return Err(MessageContentError::TooLong(len));
+        }
+        Ok(content)
+    }
+
+    /// Returns the number of graphemes, in which the limits of the contents are counted
+    pub fn length(&self) -> usize {
+        self.0.graphemes(true).count()
+    }
+
+    /// Converts the line endings to LF, removes the control characters except for tabs and line
+    /// feeds, composes to NFC, trims the trailing whitespace of each line and collapses the
+    /// excessive blank lines
+    ///
+    /// The invisible format characters (e.g. bidi overrides) are left to `ContentPolicy`.
+    fn normalize(value: &str) -> String {
+        let value = value
+            .replace("\r\n", "\n")
+            .replace('\r', "\n")
+            .chars()
+            .filter(|c| matches!(c, '\t' | '\n') || !c.is_control())
+            .nfc()
+            .collect::<String>();
+        let mut lines = vec![];
+        let mut blank_lines = 0;
+        for line in value.split('\n').map(str::trim_end) {
+            if line.is_empty() {
+                blank_lines += 1;
+                if lines.is_empty() || blank_lines > Self::MAX_BLANK_LINES {
+                    continue;
+                }
+            } else {
+                blank_lines = 0;
+            }
+            lines.push(line);
+        }
+        while lines.last().is_some_and(|it| it.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+
     #[cfg(test)]
     pub fn new_for_testing() -> Self {
         use rand::Rng;
@@ -34,13 +91,9 @@ impl TryFrom<String> for MessageContent {
     type Error = MessageContentError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let len = value.trim().chars().count();
-        if len == 0 {
+        if value.trim().is_empty() {
             Err(MessageContentError::Empty)
-        } else if len > 255 {
-            Err(MessageContentError::TooLong(len))
         } else {
-            assert!((1..=255).contains(&len));
             Ok(Self(value))
         }
     }
@@ -50,6 +103,51 @@ impl TryFrom<String> for MessageContent {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_new() -> anyhow::Result<()> {
+        let s = "x".repeat(255);
+        assert_eq!(String::from(MessageContent::new(s.clone(), 255)?), s);
+        let s = "x".repeat(256);
+        assert!(matches!(
+            MessageContent::new(s.clone(), 255),
+            Err(MessageContentError::TooLong(256))
+        ));
+        assert_eq!(String::from(MessageContent::new(s.clone(), 256)?), s);
+        assert!(matches!(
+            MessageContent::new(" ".to_owned(), 255),
+            Err(MessageContentError::Empty)
+        ));
+        assert!(matches!(
+            MessageContent::new("\r\n\u{0007}\u{007F}".to_owned(), 255),
+            Err(MessageContentError::Empty)
+        ));
+
+        // the length is counted in graphemes
+        let s = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}".repeat(3);
+        assert_eq!(String::from(MessageContent::new(s.clone(), 3)?), s);
+        let s = "\u{304B}\u{3099}".repeat(3);
+        assert_eq!(
+            String::from(MessageContent::new(s, 3)?),
+            "\u{304C}".repeat(3)
+        );
+        assert!(MessageContent::new("あいうえ".to_owned(), 3).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize() {
+        let f = MessageContent::normalize;
+        assert_eq!(f("a\r\nb\rc\n"), "a\nb\nc");
+        assert_eq!(f("a  \nb\t\u{3000}"), "a\nb");
+        assert_eq!(f("\n\n  a\n\n\n\n\nb\n\n"), "  a\n\n\nb");
+        assert_eq!(f("a\u{0000}b\u{001B}c\u{0085}d\te"), "abcd\te");
+        assert_eq!(f("e\u{0301}"), "\u{00E9}");
+        assert_eq!(
+            f("\u{1F468}\u{200D}\u{1F469}"),
+            "\u{1F468}\u{200D}\u{1F469}"
+        );
+    }
+
     #[test]
     fn test_string_conversion() -> anyhow::Result<()> {
         let s = "Hello, World!".to_owned();
@@ -62,10 +160,9 @@ mod tests {
         let s = " 　".to_owned();
         assert!(MessageContent::try_from(s).is_err());
 
-        let s = "x".repeat(255);
-        assert_eq!(String::from(MessageContent::try_from(s.clone())?), s);
+        // the length is checked by `new`
         let s = "x".repeat(256);
-        assert!(MessageContent::try_from(s).is_err());
+        assert_eq!(String::from(MessageContent::try_from(s.clone())?), s);
 
         Ok(())
     }
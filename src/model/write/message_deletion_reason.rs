@@ -0,0 +1,83 @@
+#[derive(Debug, thiserror::Error)]
+#[error("unknown message deletion reason: {0}")]
+pub struct MessageDeletionReasonError(String);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageDeletionReason {
+    AuthorRequest,
+    Moderator,
+}
+
+impl std::fmt::Display for MessageDeletionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MessageDeletionReason::AuthorRequest => "author_request",
+                MessageDeletionReason::Moderator => "moderator",
+            }
+        )
+    }
+}
+
+impl std::str::FromStr for MessageDeletionReason {
+    type Err = MessageDeletionReasonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "author_request" => Ok(MessageDeletionReason::AuthorRequest),
+            "moderator" => Ok(MessageDeletionReason::Moderator),
+            _ => Err(MessageDeletionReasonError(s.to_owned())),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MessageDeletionReason {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for MessageDeletionReason {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_impl_display() {
+        assert_eq!(
+            MessageDeletionReason::AuthorRequest.to_string(),
+            "author_request"
+        );
+        assert_eq!(MessageDeletionReason::Moderator.to_string(), "moderator");
+    }
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        for reason in [
+            MessageDeletionReason::AuthorRequest,
+            MessageDeletionReason::Moderator,
+        ] {
+            assert_eq!(
+                MessageDeletionReason::from_str(&reason.to_string())?,
+                reason
+            );
+        }
+        assert_eq!(
+            MessageDeletionReason::from_str("spam")
+                .unwrap_err()
+                .to_string(),
+            "unknown message deletion reason: spam"
+        );
+        Ok(())
+    }
+}
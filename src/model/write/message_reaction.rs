@@ -0,0 +1,91 @@
+#[derive(Debug, thiserror::Error)]
+#[error("unknown message reaction: {0}")]
+pub struct MessageReactionError(String);
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MessageReaction {
+    Heart,
+    Laugh,
+    Sad,
+    Surprised,
+    ThumbsDown,
+    ThumbsUp,
+}
+
+impl std::fmt::Display for MessageReaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MessageReaction::Heart => "heart",
+                MessageReaction::Laugh => "laugh",
+                MessageReaction::Sad => "sad",
+                MessageReaction::Surprised => "surprised",
+                MessageReaction::ThumbsDown => "thumbs_down",
+                MessageReaction::ThumbsUp => "thumbs_up",
+            }
+        )
+    }
+}
+
+impl std::str::FromStr for MessageReaction {
+    type Err = MessageReactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heart" => Ok(MessageReaction::Heart),
+            "laugh" => Ok(MessageReaction::Laugh),
+            "sad" => Ok(MessageReaction::Sad),
+            "surprised" => Ok(MessageReaction::Surprised),
+            "thumbs_down" => Ok(MessageReaction::ThumbsDown),
+            "thumbs_up" => Ok(MessageReaction::ThumbsUp),
+            _ => Err(MessageReactionError(s.to_owned())),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MessageReaction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        <Self as std::str::FromStr>::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for MessageReaction {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_impl_display() {
+        assert_eq!(MessageReaction::Heart.to_string(), "heart");
+        assert_eq!(MessageReaction::ThumbsUp.to_string(), "thumbs_up");
+    }
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        for reaction in [
+            MessageReaction::Heart,
+            MessageReaction::Laugh,
+            MessageReaction::Sad,
+            MessageReaction::Surprised,
+            MessageReaction::ThumbsDown,
+            MessageReaction::ThumbsUp,
+        ] {
+            assert_eq!(MessageReaction::from_str(&reaction.to_string())?, reaction);
+        }
+        assert_eq!(
+            MessageReaction::from_str("poop").unwrap_err().to_string(),
+            "unknown message reaction: poop"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::utils::date_time::DateTime;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PollError {
+    #[error("duplicate option: {0}")]
+    DuplicateOption(String),
+    #[error("option contains newline")]
+    OptionContainsNewline,
+    #[error("option empty")]
+    OptionEmpty,
+    #[error("option too long: {0}")]
+    OptionTooLong(usize),
+    #[error("question contains newline")]
+    QuestionContainsNewline,
+    #[error("question empty")]
+    QuestionEmpty,
+    #[error("question too long: {0}")]
+    QuestionTooLong(usize),
+    #[error("too few options: {0}")]
+    TooFewOptions(usize),
+    #[error("too many options: {0}")]
+    TooManyOptions(usize),
+}
+
+/// A question with options to vote for, attached to a thread when it is created
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Poll {
+    closes_at: Option<DateTime>,
+    options: Vec<String>,
+    question: String,
+}
+
+impl Poll {
+    const MAX_OPTION_LEN: usize = 64;
+    const MAX_OPTIONS: usize = 10;
+    const MAX_QUESTION_LEN: usize = 255;
+    const MIN_OPTIONS: usize = 2;
+
+    pub fn new(
+        question: String,
+        options: Vec<String>,
+        closes_at: Option<DateTime>,
+    ) -> Result<Self, PollError> {
+        let len = question.trim().chars().count();
+        if len == 0 {
+            return Err(PollError::QuestionEmpty);
+        }
+        if len > Self::MAX_QUESTION_LEN {
+            return Err(PollError::QuestionTooLong(len));
+        }
+        if question.contains(['\r', '\n']) {
+            return Err(PollError::QuestionContainsNewline);
+        }
+        if options.len() < Self::MIN_OPTIONS {
+            return Err(PollError::TooFewOptions(options.len()));
+        }
+        if options.len() > Self::MAX_OPTIONS {
+            return Err(PollError::TooManyOptions(options.len()));
+        }
+        for (index, option) in options.iter().enumerate() {
+            let len = option.trim().chars().count();
+            if len == 0 {
+                return Err(PollError::OptionEmpty);
+            }
+            if len > Self::MAX_OPTION_LEN {
+                return Err(PollError::OptionTooLong(len));
+            }
+            if option.contains(['\r', '\n']) {
+                return Err(PollError::OptionContainsNewline);
+            }
+            if options[..index].contains(option) {
+                return Err(PollError::DuplicateOption(option.clone()));
+            }
+        }
+        Ok(Self {
+            closes_at,
+            options,
+            question,
+        })
+    }
+
+    pub fn closes_at(&self) -> Option<DateTime> {
+        self.closes_at
+    }
+
+    /// Returns `true` if votes are no longer accepted at `at`
+    pub fn is_closed_at(&self, at: DateTime) -> bool {
+        self.closes_at.is_some_and(|closes_at| at >= closes_at)
+    }
+
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    pub fn question(&self) -> &str {
+        &self.question
+    }
+
+    #[cfg(test)]
+    pub fn new_for_testing() -> Self {
+        Self {
+            closes_at: None,
+            options: vec!["yes".to_owned(), "no".to_owned()],
+            question: "Do you agree?".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() -> anyhow::Result<()> {
+        let new = |question: &str, options: &[&str]| {
+            Poll::new(
+                question.to_owned(),
+                options.iter().map(|it| (*it).to_owned()).collect(),
+                None,
+            )
+        };
+        let poll = new("Lunch?", &["ramen", "sushi"])?;
+        assert_eq!(poll.question(), "Lunch?");
+        assert_eq!(poll.options(), ["ramen", "sushi"]);
+        assert_eq!(poll.closes_at(), None);
+
+        assert!(new(" ", &["ramen", "sushi"]).is_err());
+        assert!(new(&"x".repeat(256), &["ramen", "sushi"]).is_err());
+        assert!(new("Lunch?\nDinner?", &["ramen", "sushi"]).is_err());
+        assert!(new("Lunch?", &["ramen"]).is_err());
+        assert!(
+            new(
+                "Lunch?",
+                &["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"]
+            )
+            .is_ok()
+        );
+        assert!(
+            new(
+                "Lunch?",
+                &["1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11"]
+            )
+            .is_err()
+        );
+        assert!(new("Lunch?", &["ramen", " "]).is_err());
+        assert!(new("Lunch?", &["ramen", &"x".repeat(65)]).is_err());
+        assert!(new("Lunch?", &["ramen", "ramen"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_closed_at() -> anyhow::Result<()> {
+        let at = DateTime::from_unix_timestamp_millis(1_000);
+        let poll = Poll::new(
+            "Lunch?".to_owned(),
+            vec!["ramen".to_owned(), "sushi".to_owned()],
+            Some(at),
+        )?;
+        assert!(!poll.is_closed_at(DateTime::from_unix_timestamp_millis(999)));
+        assert!(poll.is_closed_at(at));
+        assert!(!Poll::new_for_testing().is_closed_at(DateTime::now()));
+        Ok(())
+    }
+}
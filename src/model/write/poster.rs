@@ -0,0 +1,152 @@
+#[derive(Debug, thiserror::Error)]
+pub enum PosterError {
+    #[error("contains newline")]
+    ContainsNewline,
+    #[error("empty")]
+    Empty,
+    #[error("invalid trip")]
+    InvalidTrip,
+    #[error("too long: {0}")]
+    TooLong(usize),
+}
+
+/// A display name and a trip of a message poster
+///
+/// The trip is derived from the secret in the `name#secret` input. The secret itself is not kept.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Poster {
+    name: String,
+    trip: Option<String>,
+}
+
+impl Poster {
+    const MAX_NAME_LEN: usize = 32;
+    const TRIP_LEN: usize = 10;
+
+    pub fn new(name: String, trip: Option<String>) -> Result<Self, PosterError> {
+        let len = name.chars().count();
+        if len > Self::MAX_NAME_LEN {
+            return Err(PosterError::TooLong(len));
+        }
+        if name.contains(['\r', '\n']) {
+            return Err(PosterError::ContainsNewline);
+        }
+        if name.trim().is_empty() && trip.is_none() {
+            return Err(PosterError::Empty);
+        }
+        if trip.as_ref().is_some_and(|trip| {
+            trip.len() != Self::TRIP_LEN
+                || !trip
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }) {
+            return Err(PosterError::InvalidTrip);
+        }
+        Ok(Self {
+            // prevents impersonating a trip by writing the trip mark in the name
+            name: name.trim().replace('◆', "◇"),
+            trip,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn trip(&self) -> Option<&str> {
+        self.trip.as_deref()
+    }
+
+    fn trip_from_secret(secret: &str) -> String {
+        use base64::Engine as _;
+        use sha2::Digest as _;
+        let digest = sha2::Sha256::digest(secret.as_bytes());
+        let mut trip = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest);
+        trip.truncate(Self::TRIP_LEN);
+        trip
+    }
+
+    #[cfg(test)]
+    pub fn new_for_testing() -> Self {
+        use rand::Rng;
+        let mut rng = rand::rng();
+        let len = rng.random_range(1..=Self::MAX_NAME_LEN);
+        let s = rng
+            .sample_iter(rand::distr::Alphanumeric)
+            .map(char::from)
+            .take(len)
+            .collect::<String>();
+        Self {
+            name: s.clone(),
+            trip: Some(Self::trip_from_secret(&s)),
+        }
+    }
+}
+
+impl TryFrom<String> for Poster {
+    type Error = PosterError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.split_once('#') {
+            None => Self::new(value, None),
+            Some((name, secret)) => {
+                if secret.is_empty() {
+                    return Self::new(name.to_owned(), None);
+                }
+                Self::new(name.to_owned(), Some(Self::trip_from_secret(secret)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_conversion() -> anyhow::Result<()> {
+        let poster = Poster::try_from("Alice".to_owned())?;
+        assert_eq!(poster.name(), "Alice");
+        assert_eq!(poster.trip(), None);
+
+        let poster = Poster::try_from("Alice#secret".to_owned())?;
+        assert_eq!(poster.name(), "Alice");
+        let trip = poster.trip().expect("trip to be present").to_owned();
+        assert_eq!(trip.len(), 10);
+        assert!(!trip.contains("secret"));
+        // stable
+        assert_eq!(
+            Poster::try_from("Bob#secret".to_owned())?.trip(),
+            Some(trip.as_str())
+        );
+        assert_ne!(
+            Poster::try_from("Alice#secret2".to_owned())?.trip(),
+            Some(trip.as_str())
+        );
+        // restorable
+        assert_eq!(Poster::new("Alice".to_owned(), Some(trip.clone()))?, poster);
+
+        let poster = Poster::try_from("#secret".to_owned())?;
+        assert_eq!(poster.name(), "");
+        assert!(poster.trip().is_some());
+
+        let poster = Poster::try_from("◆fake".to_owned())?;
+        assert_eq!(poster.name(), "◇fake");
+
+        assert!(Poster::try_from(String::default()).is_err());
+        assert!(Poster::try_from(" #".to_owned()).is_err());
+        assert!(Poster::try_from("x".repeat(32)).is_ok());
+        assert!(Poster::try_from("x".repeat(33)).is_err());
+        assert!(Poster::try_from("Alice\nBob".to_owned()).is_err());
+        assert!(Poster::new("Alice".to_owned(), Some("short".to_owned())).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_for_testing() {
+        let poster1 = Poster::new_for_testing();
+        let poster2 = Poster::new_for_testing();
+        assert_ne!(poster1, poster2);
+    }
+}
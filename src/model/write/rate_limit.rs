@@ -0,0 +1,158 @@
+use crate::utils::date_time::DateTime;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitError {
+    #[error("invalid format: {0} (expected: {{count}}/{{seconds}})")]
+    InvalidFormat(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("rate limit exceeded (retry after {retry_after:?})")]
+pub struct RateLimitExceeded {
+    pub retry_after: std::time::Duration,
+}
+
+/// An action whose frequency is limited per client
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RateLimitAction {
+    CreateThread,
+    Reply,
+}
+
+impl std::fmt::Display for RateLimitAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RateLimitAction::CreateThread => "create_thread",
+                RateLimitAction::Reply => "reply",
+            }
+        )
+    }
+}
+
+/// The requests counted in the current window of a client
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitWindow {
+    pub count: u32,
+    pub started_at: DateTime,
+}
+
+/// At most `max_count` requests per `window` (e.g. `3/600` is 3 requests per 10 minutes)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+    max_count: u32,
+    window: std::time::Duration,
+}
+
+impl RateLimit {
+    pub fn new(max_count: u32, window: std::time::Duration) -> Self {
+        Self { max_count, window }
+    }
+
+    /// Counts a request at `now` in the window, starting a new window if the previous one is over
+    pub fn consume(
+        &self,
+        window: Option<RateLimitWindow>,
+        now: DateTime,
+    ) -> Result<RateLimitWindow, RateLimitExceeded> {
+        let now_millis = now.to_unix_timestamp_millis();
+        let window_millis = i64::try_from(self.window.as_millis()).unwrap_or(i64::MAX);
+        let window = window
+            .filter(|it| {
+                now_millis.saturating_sub(it.started_at.to_unix_timestamp_millis()) < window_millis
+            })
+            .unwrap_or(RateLimitWindow {
+                count: 0,
+                started_at: now,
+            });
+        if window.count >= self.max_count {
+            let ends_at = window
+                .started_at
+                .to_unix_timestamp_millis()
+                .saturating_add(window_millis);
+            // rounded up because `Retry-After` is in seconds
+            let retry_after_millis = u64::try_from(ends_at.saturating_sub(now_millis)).unwrap_or(0);
+            return Err(RateLimitExceeded {
+                retry_after: std::time::Duration::from_secs(
+                    retry_after_millis.div_ceil(1000).max(1),
+                ),
+            });
+        }
+        Ok(RateLimitWindow {
+            count: window.count + 1,
+            ..window
+        })
+    }
+}
+
+impl std::str::FromStr for RateLimit {
+    type Err = RateLimitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (count, secs) = s
+            .split_once('/')
+            .ok_or_else(|| RateLimitError::InvalidFormat(s.to_owned()))?;
+        let max_count =
+            u32::from_str(count.trim()).map_err(|_| RateLimitError::InvalidFormat(s.to_owned()))?;
+        let secs =
+            u64::from_str(secs.trim()).map_err(|_| RateLimitError::InvalidFormat(s.to_owned()))?;
+        if secs == 0 {
+            return Err(RateLimitError::InvalidFormat(s.to_owned()));
+        }
+        Ok(Self::new(max_count, std::time::Duration::from_secs(secs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_consume() -> anyhow::Result<()> {
+        let limit = RateLimit::from_str("2/60")?;
+        let at = |secs: i64| DateTime::from_unix_timestamp_millis(secs * 1000);
+
+        let window = limit.consume(None, at(0))?;
+        assert_eq!(window.count, 1);
+        let window = limit.consume(Some(window), at(10))?;
+        assert_eq!(window.count, 2);
+        assert_eq!(window.started_at, at(0));
+        let exceeded = limit
+            .consume(Some(window.clone()), at(30))
+            .expect_err("to be exceeded");
+        assert_eq!(exceeded.retry_after, std::time::Duration::from_secs(30));
+
+        // a new window
+        let window = limit.consume(Some(window), at(60))?;
+        assert_eq!(
+            window,
+            RateLimitWindow {
+                count: 1,
+                started_at: at(60)
+            }
+        );
+
+        assert!(
+            RateLimit::new(0, std::time::Duration::from_secs(1))
+                .consume(None, at(0))
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        assert_eq!(
+            RateLimit::from_str("3/600")?,
+            RateLimit::new(3, std::time::Duration::from_secs(600))
+        );
+        assert!(RateLimit::from_str("3").is_err());
+        assert!(RateLimit::from_str("3/0").is_err());
+        assert!(RateLimit::from_str("x/60").is_err());
+        Ok(())
+    }
+}
@@ -1,122 +1,1056 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::str::FromStr;
 
+use crate::model::shared::event::ThreadClosed;
+use crate::model::shared::event::ThreadContinued;
 use crate::model::shared::event::ThreadCreated;
+use crate::model::shared::event::ThreadCreatedPoll;
 use crate::model::shared::event::ThreadEvent;
+use crate::model::shared::event::ThreadFilled;
+use crate::model::shared::event::ThreadMessageAttachment;
+use crate::model::shared::event::ThreadMessageDeleted;
+use crate::model::shared::event::ThreadMessageEdited;
+use crate::model::shared::event::ThreadMessageReacted;
+use crate::model::shared::event::ThreadMessageUnreacted;
+use crate::model::shared::event::ThreadPinned;
+use crate::model::shared::event::ThreadPollVoted;
+use crate::model::shared::event::ThreadReopened;
 use crate::model::shared::event::ThreadReplied;
+use crate::model::shared::event::ThreadTagged;
+use crate::model::shared::event::ThreadTitleChanged;
+use crate::model::shared::event::ThreadUnpinned;
+use crate::model::shared::event::ThreadUntagged;
+use crate::model::shared::id::BoardId;
 use crate::model::shared::id::EventId;
 use crate::model::shared::id::ThreadId;
+use crate::model::shared::replay::ReplayError;
+use crate::model::shared::replay::check_next;
+use crate::model::shared::thread_snapshot::ThreadSnapshot;
+use crate::model::shared::thread_snapshot::ThreadSnapshotError;
+use crate::model::shared::thread_snapshot::ThreadSnapshotMessage;
+use crate::model::shared::thread_snapshot::ThreadSnapshotReaction;
+use crate::model::write::ClientKey;
+use crate::model::write::ContentFingerprint;
 use crate::model::write::Message;
+use crate::model::write::MessageAttachment;
 use crate::model::write::MessageContent;
+use crate::model::write::MessageDeletionReason;
+use crate::model::write::MessageReaction;
+use crate::model::write::Poll;
+use crate::model::write::Poster;
+use crate::model::write::ThreadTag;
+use crate::model::write::ThreadTitle;
 use crate::model::write::Version;
+use crate::model::write::VersionError;
+use crate::model::write::VoterKey;
 use crate::utils::date_time::DateTime;
 
 #[derive(Debug, thiserror::Error)]
-#[error("thread error")]
-pub struct ThreadError(#[source] Box<dyn std::error::Error + Send + Sync>);
+pub enum ThreadError {
+    #[error("message has already been reacted by the reactor (number: {0}, reaction: {1})")]
+    AlreadyReacted(u16, MessageReaction),
+    #[error("thread is closed")]
+    Closed,
+    #[error("thread has already been continued (next: {0})")]
+    Continued(ThreadId),
+    #[error("thread is filled")]
+    Filled,
+    #[error("message has been deleted (number: {0})")]
+    MessageDeleted(u16),
+    #[error("message edit window has expired (number: {0})")]
+    MessageEditWindowExpired(u16),
+    #[error("message not found (number: {0})")]
+    MessageNotFound(u16),
+    #[error("thread is not closed")]
+    NotClosed,
+    #[error("thread is not filled")]
+    NotFilled,
+    #[error("thread is not pinned")]
+    NotPinned,
+    #[error("thread is not tagged (tag: {0})")]
+    NotTagged(ThreadTag),
+    #[error("thread is pinned")]
+    Pinned,
+    #[error("poll has already been voted by the voter")]
+    PollAlreadyVoted,
+    #[error("poll is closed")]
+    PollClosed,
+    #[error("poll not found")]
+    PollNotFound,
+    #[error("poll option not found (option: {0})")]
+    PollOptionNotFound(u8),
+    #[error("reaction not found (number: {0}, reaction: {1})")]
+    ReactionNotFound(u16, MessageReaction),
+    #[error("thread is already tagged (tag: {0})")]
+    Tagged(ThreadTag),
+    #[error("too many attachments: {0}")]
+    TooManyAttachments(usize),
+    #[error("too many tags: {0}")]
+    TooManyTags(usize),
+    #[error("version overflow")]
+    VersionOverflow(#[from] VersionError),
+}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Thread {
+    board_id: BoardId,
+    closed: bool,
+    deleted_message_numbers: BTreeSet<u16>,
+    filled: bool,
     id: ThreadId,
-    message_count: usize,
-    root_message: Message,
+    messages: Vec<Message>,
+    next_thread_id: Option<ThreadId>,
+    pinned: bool,
+    poll: Option<Poll>,
+    poll_voters: BTreeSet<VoterKey>,
+    previous_thread_id: Option<ThreadId>,
+    reactions: BTreeMap<(u16, MessageReaction), u32>,
+    /// The clients that have reacted, to let them remove their own reactions
+    reactors: BTreeSet<(u16, MessageReaction, ClientKey)>,
+    tags: BTreeSet<ThreadTag>,
+    title: ThreadTitle,
     version: Version,
 }
 
 impl Thread {
-    pub fn create(message: Message) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+    pub const MAX_ATTACHMENTS: usize = 4;
+    const MAX_TAGS: usize = 5;
+
+    pub fn board_id(&self) -> &BoardId {
+        &self.board_id
+    }
+
+    pub fn change_title(
+        &self,
+        title: ThreadTitle,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadTitleChanged {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            thread_id: self.id.clone(),
+            title: String::from(title.clone()),
+            version: u32::from(version),
+        });
+        Ok((
+            Self {
+                title,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    pub fn close(&self) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.closed {
+            return Err(ThreadError::Closed);
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadClosed {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        Ok((
+            Self {
+                closed: true,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    /// Creates the next thread of this filled thread.
+    ///
+    /// Returns this thread with the link to the next thread and the next thread.
+    #[allow(clippy::type_complexity)]
+    pub fn continue_thread(
+        &self,
+        title: ThreadTitle,
+        message: Message,
+    ) -> Result<((Self, Vec<ThreadEvent>), (Self, Vec<ThreadEvent>)), ThreadError> {
+        if let Some(next_thread_id) = &self.next_thread_id {
+            return Err(ThreadError::Continued(next_thread_id.clone()));
+        }
+        if !self.filled {
+            return Err(ThreadError::NotFilled);
+        }
+        let (next, next_events) = Self::create_with_previous(
+            self.board_id.clone(),
+            title,
+            message,
+            self.tags.clone(),
+            None,
+            Some(self.id.clone()),
+        )?;
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadContinued {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            next_thread_id: next.id.clone(),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        Ok((
+            (
+                Self {
+                    next_thread_id: Some(next.id.clone()),
+                    version,
+                    ..self.clone()
+                },
+                vec![event],
+            ),
+            (next, next_events),
+        ))
+    }
+
+    pub fn create(
+        board_id: BoardId,
+        title: ThreadTitle,
+        message: Message,
+        tags: BTreeSet<ThreadTag>,
+        poll: Option<Poll>,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        Self::create_with_previous(board_id, title, message, tags, poll, None)
+    }
+
+    fn create_with_previous(
+        board_id: BoardId,
+        title: ThreadTitle,
+        message: Message,
+        tags: BTreeSet<ThreadTag>,
+        poll: Option<Poll>,
+        previous_thread_id: Option<ThreadId>,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if message.attachments.len() > Self::MAX_ATTACHMENTS {
+            return Err(ThreadError::TooManyAttachments(message.attachments.len()));
+        }
+        if tags.len() > Self::MAX_TAGS {
+            return Err(ThreadError::TooManyTags(tags.len()));
+        }
         let id = ThreadId::generate();
         let version = Version::initial();
 
         let event = ThreadEvent::from(ThreadCreated {
-            at: DateTime::now().to_string(),
+            at: message.created_at,
+            attachments: attachments_to_event(&message.attachments),
+            board_id: Some(board_id.clone()),
+            client_key: message.client_key.clone(),
             content: String::from(message.content.clone()),
-            id: EventId::generate().to_string(),
-            thread_id: id.to_string(),
+            fingerprint: Some(ContentFingerprint::of(&message.content)),
+            id: EventId::generate(),
+            name: message.poster.as_ref().map(|it| it.name().to_owned()),
+            poll: poll.as_ref().map(|it| ThreadCreatedPoll {
+                closes_at: it.closes_at(),
+                options: it.options().to_vec(),
+                question: it.question().to_owned(),
+            }),
+            previous_thread_id: previous_thread_id.clone(),
+            tags: tags.iter().map(ThreadTag::to_string).collect(),
+            thread_id: id.clone(),
+            title: String::from(title.clone()),
+            trip: message
+                .poster
+                .as_ref()
+                .and_then(|it| it.trip().map(str::to_owned)),
             version: u32::from(version),
         });
         Ok((
             Self {
+                board_id,
+                closed: false,
+                deleted_message_numbers: BTreeSet::new(),
+                filled: false,
                 id,
-                message_count: 1,
-                root_message: message,
+                messages: vec![message],
+                next_thread_id: None,
+                pinned: false,
+                poll,
+                poll_voters: BTreeSet::new(),
+                previous_thread_id,
+                reactions: BTreeMap::new(),
+                reactors: BTreeSet::new(),
+                tags,
+                title,
                 version,
             },
             vec![event],
         ))
     }
 
-    pub fn replay(events: &[ThreadEvent]) -> Self {
-        let mut iter = events.into_iter();
+    pub fn replay(events: &[ThreadEvent]) -> Result<Self, ReplayError> {
+        let mut iter = events.iter();
 
-        let first_event = iter.next().expect("events not to be empty");
-        let mut thread = match first_event {
+        let first_event = iter.next().ok_or(ReplayError::Empty)?;
+        let invalid = |field: &'static str| ReplayError::invalid_field(first_event, field);
+        let thread = match first_event {
             ThreadEvent::Created(ThreadCreated {
                 at,
+                attachments,
+                board_id,
+                client_key,
                 content,
+                fingerprint: _,
                 id: _,
+                name,
+                poll,
+                previous_thread_id,
+                tags,
                 thread_id,
+                title,
+                trip,
                 version,
             }) => Self {
-                id: ThreadId::from_str(&thread_id).expect("thread id in event to be valid"),
-                message_count: 1,
-                root_message: Message {
+                board_id: board_id.clone().unwrap_or_default(),
+                closed: false,
+                deleted_message_numbers: BTreeSet::new(),
+                filled: false,
+                id: thread_id.clone(),
+                messages: vec![Message {
+                    attachments: attachments_from_event(attachments),
+                    client_key: client_key.clone(),
                     content: MessageContent::try_from(content.to_owned())
-                        .expect("message content in event to be valid"),
-                    created_at: DateTime::from_str(&at)
-                        .expect("message created_at in event to be valid"),
-                },
+                        .map_err(|_| invalid("content"))?,
+                    created_at: *at,
+                    poster: poster_from_event(name, trip).map_err(invalid)?,
+                }],
+                next_thread_id: None,
+                pinned: false,
+                poll: poll
+                    .as_ref()
+                    .map(|it| {
+                        Poll::new(it.question.clone(), it.options.clone(), it.closes_at)
+                            .map_err(|_| invalid("poll"))
+                    })
+                    .transpose()?,
+                poll_voters: BTreeSet::new(),
+                previous_thread_id: previous_thread_id.clone(),
+                reactions: BTreeMap::new(),
+                reactors: BTreeSet::new(),
+                tags: tags
+                    .iter()
+                    .map(|it| ThreadTag::from_str(it))
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid("tags"))?,
+                title: ThreadTitle::try_from(title.to_owned()).map_err(|_| invalid("title"))?,
                 version: Version::from(*version),
             },
-            ThreadEvent::Replied(_) => {
-                unreachable!("first event should be Created")
+            ThreadEvent::Closed(_)
+            | ThreadEvent::Continued(_)
+            | ThreadEvent::Filled(_)
+            | ThreadEvent::MessageDeleted(_)
+            | ThreadEvent::MessageEdited(_)
+            | ThreadEvent::MessageReacted(_)
+            | ThreadEvent::MessageUnreacted(_)
+            | ThreadEvent::Pinned(_)
+            | ThreadEvent::PollVoted(_)
+            | ThreadEvent::Reopened(_)
+            | ThreadEvent::Replied(_)
+            | ThreadEvent::Tagged(_)
+            | ThreadEvent::TitleChanged(_)
+            | ThreadEvent::Unpinned(_)
+            | ThreadEvent::Untagged(_) => {
+                return Err(ReplayError::not_created_first(first_event));
             }
         };
 
-        for event in iter {
+        thread.replay_from(iter.as_slice())
+    }
+
+    /// Replays the events that follow the state of this thread, e.g. a thread restored from a
+    /// snapshot
+    pub fn replay_from(mut self, events: &[ThreadEvent]) -> Result<Self, ReplayError> {
+        for event in events {
+            check_next(u32::from(self.version), event)?;
+            let invalid = |field: &'static str| ReplayError::invalid_field(event, field);
             match event {
+                ThreadEvent::Closed(ThreadClosed {
+                    at: _,
+                    id: _,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.closed = true;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::Continued(ThreadContinued {
+                    at: _,
+                    id: _,
+                    next_thread_id,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.next_thread_id = Some(next_thread_id.clone());
+                    self.version = Version::from(*version);
+                }
                 ThreadEvent::Created(_) => {
-                    unreachable!("subsequent events not to be Created")
+                    return Err(ReplayError::duplicate_created(event));
+                }
+                ThreadEvent::Filled(ThreadFilled {
+                    at: _,
+                    id: _,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.filled = true;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::MessageDeleted(ThreadMessageDeleted {
+                    at: _,
+                    id: _,
+                    number,
+                    reason: _,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.deleted_message_numbers.insert(*number);
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::MessageEdited(ThreadMessageEdited {
+                    at: _,
+                    content,
+                    id: _,
+                    number,
+                    thread_id: _,
+                    version,
+                }) => {
+                    let message = usize::from(*number)
+                        .checked_sub(1)
+                        .and_then(|index| self.messages.get_mut(index))
+                        .ok_or_else(|| invalid("number"))?;
+                    message.content = MessageContent::try_from(content.to_owned())
+                        .map_err(|_| invalid("content"))?;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::MessageReacted(ThreadMessageReacted {
+                    at: _,
+                    id: _,
+                    number,
+                    reaction,
+                    reactor,
+                    thread_id: _,
+                    version,
+                }) => {
+                    if let Some(reactor) = reactor {
+                        self.reactors.insert((*number, *reaction, reactor.clone()));
+                    }
+                    *self.reactions.entry((*number, *reaction)).or_default() += 1;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::MessageUnreacted(ThreadMessageUnreacted {
+                    at: _,
+                    id: _,
+                    number,
+                    reaction,
+                    reactor,
+                    thread_id: _,
+                    version,
+                }) => {
+                    if let Some(reactor) = reactor {
+                        self.reactors.remove(&(*number, *reaction, reactor.clone()));
+                    }
+                    let count = self
+                        .reactions
+                        .get_mut(&(*number, *reaction))
+                        .ok_or_else(|| invalid("reaction"))?;
+                    *count -= 1;
+                    if *count == 0 {
+                        self.reactions.remove(&(*number, *reaction));
+                    }
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::Pinned(ThreadPinned {
+                    at: _,
+                    id: _,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.pinned = true;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::PollVoted(ThreadPollVoted {
+                    at: _,
+                    id: _,
+                    option: _,
+                    thread_id: _,
+                    version,
+                    voter,
+                }) => {
+                    self.poll_voters.insert(voter.clone());
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::Reopened(ThreadReopened {
+                    at: _,
+                    id: _,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.closed = false;
+                    self.version = Version::from(*version);
                 }
                 ThreadEvent::Replied(ThreadReplied {
+                    at,
+                    attachments,
+                    client_key,
+                    content,
+                    fingerprint: _,
+                    id: _,
+                    name,
+                    sage: _,
+                    thread_id: _,
+                    trip,
+                    version,
+                }) => {
+                    self.messages.push(Message {
+                        attachments: attachments_from_event(attachments),
+                        client_key: client_key.clone(),
+                        content: MessageContent::try_from(content.to_owned())
+                            .map_err(|_| invalid("content"))?,
+                        created_at: *at,
+                        poster: poster_from_event(name, trip).map_err(invalid)?,
+                    });
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::Tagged(ThreadTagged {
+                    at: _,
+                    id: _,
+                    tag,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.tags
+                        .insert(ThreadTag::from_str(tag).map_err(|_| invalid("tag"))?);
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::TitleChanged(ThreadTitleChanged {
+                    at: _,
+                    id: _,
+                    thread_id: _,
+                    title,
+                    version,
+                }) => {
+                    self.title =
+                        ThreadTitle::try_from(title.to_owned()).map_err(|_| invalid("title"))?;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::Unpinned(ThreadUnpinned {
+                    at: _,
+                    id: _,
+                    thread_id: _,
+                    version,
+                }) => {
+                    self.pinned = false;
+                    self.version = Version::from(*version);
+                }
+                ThreadEvent::Untagged(ThreadUntagged {
                     at: _,
-                    content: _,
                     id: _,
+                    tag,
                     thread_id: _,
                     version,
                 }) => {
-                    thread.message_count += 1;
-                    thread.version = Version::from(*version);
+                    self.tags
+                        .remove(&ThreadTag::from_str(tag).map_err(|_| invalid("tag"))?);
+                    self.version = Version::from(*version);
                 }
             }
         }
 
-        thread
+        Ok(self)
+    }
+
+    /// Restores the thread from the snapshot, without the events after it
+    pub fn from_snapshot(
+        ThreadSnapshot {
+            board_id,
+            closed,
+            deleted_message_numbers,
+            filled,
+            id,
+            messages,
+            next_thread_id,
+            pinned,
+            poll,
+            poll_voters,
+            previous_thread_id,
+            reactions,
+            tags,
+            title,
+            version,
+        }: ThreadSnapshot,
+    ) -> Result<Self, ThreadSnapshotError> {
+        Ok(Self {
+            board_id,
+            closed,
+            deleted_message_numbers: deleted_message_numbers.into_iter().collect(),
+            filled,
+            id,
+            messages: messages
+                .into_iter()
+                .map(|it| {
+                    Ok(Message {
+                        attachments: attachments_from_event(&it.attachments),
+                        client_key: it.client_key,
+                        content: MessageContent::try_from(it.content)
+                            .map_err(|_| ThreadSnapshotError::InvalidField("content"))?,
+                        created_at: it.created_at,
+                        poster: poster_from_event(&it.name, &it.trip)
+                            .map_err(ThreadSnapshotError::InvalidField)?,
+                    })
+                })
+                .collect::<Result<_, ThreadSnapshotError>>()?,
+            next_thread_id,
+            pinned,
+            poll: poll
+                .map(|it| {
+                    Poll::new(it.question, it.options, it.closes_at)
+                        .map_err(|_| ThreadSnapshotError::InvalidField("poll"))
+                })
+                .transpose()?,
+            poll_voters: poll_voters.into_iter().collect(),
+            previous_thread_id,
+            reactions: reactions
+                .iter()
+                .map(|it| ((it.number, it.reaction), it.count))
+                .collect(),
+            reactors: reactions
+                .iter()
+                .flat_map(|it| {
+                    it.reactors
+                        .iter()
+                        .map(|reactor| (it.number, it.reaction, reactor.clone()))
+                })
+                .collect(),
+            tags: tags
+                .iter()
+                .map(|it| ThreadTag::from_str(it))
+                .collect::<Result<_, _>>()
+                .map_err(|_| ThreadSnapshotError::InvalidField("tags"))?,
+            title: ThreadTitle::try_from(title)
+                .map_err(|_| ThreadSnapshotError::InvalidField("title"))?,
+            version: Version::from(version),
+        })
+    }
+
+    /// Returns the snapshot of the current state of the thread
+    pub fn snapshot(&self) -> ThreadSnapshot {
+        ThreadSnapshot {
+            board_id: self.board_id.clone(),
+            closed: self.closed,
+            deleted_message_numbers: self.deleted_message_numbers.iter().copied().collect(),
+            filled: self.filled,
+            id: self.id.clone(),
+            messages: self
+                .messages
+                .iter()
+                .map(|it| ThreadSnapshotMessage {
+                    attachments: attachments_to_event(&it.attachments),
+                    client_key: it.client_key.clone(),
+                    content: String::from(it.content.clone()),
+                    created_at: it.created_at,
+                    name: it.poster.as_ref().map(|it| it.name().to_owned()),
+                    trip: it
+                        .poster
+                        .as_ref()
+                        .and_then(|it| it.trip().map(str::to_owned)),
+                })
+                .collect(),
+            next_thread_id: self.next_thread_id.clone(),
+            pinned: self.pinned,
+            poll: self.poll.as_ref().map(|it| ThreadCreatedPoll {
+                closes_at: it.closes_at(),
+                options: it.options().to_vec(),
+                question: it.question().to_owned(),
+            }),
+            poll_voters: self.poll_voters.iter().cloned().collect(),
+            previous_thread_id: self.previous_thread_id.clone(),
+            reactions: self
+                .reactions
+                .iter()
+                .map(|((number, reaction), count)| ThreadSnapshotReaction {
+                    count: *count,
+                    number: *number,
+                    reaction: *reaction,
+                    reactors: self
+                        .reactors
+                        .iter()
+                        .filter(|(n, r, _)| n == number && r == reaction)
+                        .map(|(_, _, reactor)| reactor.clone())
+                        .collect(),
+                })
+                .collect(),
+            tags: self.tags.iter().map(ThreadTag::to_string).collect(),
+            title: String::from(self.title.clone()),
+            version: u32::from(self.version),
+        }
+    }
+
+    pub fn delete_message(
+        &self,
+        number: u16,
+        reason: MessageDeletionReason,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        self.message_index(number)?;
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadMessageDeleted {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            number,
+            reason,
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        let mut deleted_message_numbers = self.deleted_message_numbers.clone();
+        deleted_message_numbers.insert(number);
+        Ok((
+            Self {
+                deleted_message_numbers,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    pub fn edit_message(
+        &self,
+        number: u16,
+        content: MessageContent,
+        edit_window: std::time::Duration,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.closed {
+            return Err(ThreadError::Closed);
+        }
+        let index = self.message_index(number)?;
+        let at = DateTime::now();
+        let elapsed = at.to_unix_timestamp_millis()
+            - self.messages[index].created_at.to_unix_timestamp_millis();
+        if u128::try_from(elapsed).unwrap_or_default() > edit_window.as_millis() {
+            return Err(ThreadError::MessageEditWindowExpired(number));
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadMessageEdited {
+            at,
+            content: String::from(content.clone()),
+            id: EventId::generate(),
+            number,
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        let mut messages = self.messages.clone();
+        messages[index].content = content;
+        Ok((
+            Self {
+                messages,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
     }
 
     pub fn id(&self) -> &ThreadId {
         &self.id
     }
 
-    pub fn reply(&self, message: Message) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
-        if self.message_count == 1000 {
-            return Err(ThreadError(
-                "Thread has reached the maximum number of messages".into(),
-            ));
+    /// Returns whether the message was posted by the client
+    ///
+    /// The messages posted before the client keys were recorded belong to no client.
+    pub fn is_posted_by(&self, number: u16, client_key: &ClientKey) -> bool {
+        usize::from(number)
+            .checked_sub(1)
+            .and_then(|index| self.messages.get(index))
+            .and_then(|message| message.client_key.as_ref())
+            == Some(client_key)
+    }
+
+    pub fn message(&self, number: u16) -> Option<&Message> {
+        usize::from(number)
+            .checked_sub(1)
+            .and_then(|index| self.messages.get(index))
+    }
+
+    pub fn next_thread_id(&self) -> Option<&ThreadId> {
+        self.next_thread_id.as_ref()
+    }
+
+    /// Pins the thread to the top of the thread list.
+    pub fn pin(&self) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.pinned {
+            return Err(ThreadError::Pinned);
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadPinned {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        Ok((
+            Self {
+                pinned: true,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    pub fn poll(&self) -> Option<&Poll> {
+        self.poll.as_ref()
+    }
+
+    /// Reacts to the message, at most once per reaction and reactor
+    pub fn react(
+        &self,
+        number: u16,
+        reaction: MessageReaction,
+        reactor: ClientKey,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.closed {
+            return Err(ThreadError::Closed);
+        }
+        self.message_index(number)?;
+        let mut reactors = self.reactors.clone();
+        if !reactors.insert((number, reaction, reactor.clone())) {
+            return Err(ThreadError::AlreadyReacted(number, reaction));
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadMessageReacted {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            number,
+            reaction,
+            reactor: Some(reactor.clone()),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        let mut reactions = self.reactions.clone();
+        *reactions.entry((number, reaction)).or_default() += 1;
+        Ok((
+            Self {
+                reactions,
+                reactors,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    pub fn reopen(&self) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if !self.closed {
+            return Err(ThreadError::NotClosed);
         }
-        let version = self.version.next();
-        let event = ThreadEvent::from(ThreadReplied {
-            at: DateTime::now().to_string(),
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadReopened {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        Ok((
+            Self {
+                closed: false,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    /// Replies to the thread. A sage reply does not bump the thread in the thread list.
+    ///
+    /// The thread is filled by the reply that makes it `max_messages` messages long.
+    pub fn reply(
+        &self,
+        message: Message,
+        sage: bool,
+        max_messages: u16,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.closed {
+            return Err(ThreadError::Closed);
+        }
+        if self.filled {
+            return Err(ThreadError::Filled);
+        }
+        if message.attachments.len() > Self::MAX_ATTACHMENTS {
+            return Err(ThreadError::TooManyAttachments(message.attachments.len()));
+        }
+        let version = self.version.next()?;
+        let mut events = vec![ThreadEvent::from(ThreadReplied {
+            at: message.created_at,
+            attachments: attachments_to_event(&message.attachments),
+            client_key: message.client_key.clone(),
             content: String::from(message.content.clone()),
-            id: EventId::generate().to_string(),
-            thread_id: self.id.to_string(),
+            fingerprint: Some(ContentFingerprint::of(&message.content)),
+            id: EventId::generate(),
+            name: message.poster.as_ref().map(|it| it.name().to_owned()),
+            sage,
+            thread_id: self.id.clone(),
+            trip: message
+                .poster
+                .as_ref()
+                .and_then(|it| it.trip().map(str::to_owned)),
+            version: u32::from(version),
+        })];
+        let mut messages = self.messages.clone();
+        messages.push(message);
+        let filled = messages.len() >= usize::from(max_messages);
+        let version = if filled {
+            let version = version.next()?;
+            events.push(ThreadEvent::from(ThreadFilled {
+                at: DateTime::now(),
+                id: EventId::generate(),
+                thread_id: self.id.clone(),
+                version: u32::from(version),
+            }));
+            version
+        } else {
+            version
+        };
+        Ok((
+            Self {
+                filled,
+                messages,
+                version,
+                ..self.clone()
+            },
+            events,
+        ))
+    }
+
+    fn message_index(&self, number: u16) -> Result<usize, ThreadError> {
+        let index = usize::from(number)
+            .checked_sub(1)
+            .filter(|index| *index < self.messages.len())
+            .ok_or(ThreadError::MessageNotFound(number))?;
+        if self.deleted_message_numbers.contains(&number) {
+            return Err(ThreadError::MessageDeleted(number));
+        }
+        Ok(index)
+    }
+
+    pub fn tag(&self, tag: ThreadTag) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.tags.contains(&tag) {
+            return Err(ThreadError::Tagged(tag));
+        }
+        if self.tags.len() >= Self::MAX_TAGS {
+            return Err(ThreadError::TooManyTags(self.tags.len() + 1));
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadTagged {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            tag: tag.to_string(),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        let mut tags = self.tags.clone();
+        tags.insert(tag);
+        Ok((
+            Self {
+                tags,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    pub fn title(&self) -> &ThreadTitle {
+        &self.title
+    }
+
+    /// Removes the reaction of the reactor from the message
+    pub fn unreact(
+        &self,
+        number: u16,
+        reaction: MessageReaction,
+        reactor: ClientKey,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.closed {
+            return Err(ThreadError::Closed);
+        }
+        self.message_index(number)?;
+        let mut reactors = self.reactors.clone();
+        if !reactors.remove(&(number, reaction, reactor.clone())) {
+            return Err(ThreadError::ReactionNotFound(number, reaction));
+        }
+        let mut reactions = self.reactions.clone();
+        let count = reactions
+            .get_mut(&(number, reaction))
+            .ok_or(ThreadError::ReactionNotFound(number, reaction))?;
+        *count -= 1;
+        if *count == 0 {
+            reactions.remove(&(number, reaction));
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadMessageUnreacted {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            number,
+            reaction,
+            reactor: Some(reactor.clone()),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        Ok((
+            Self {
+                reactions,
+                reactors,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    /// Unpins the thread.
+    pub fn unpin(&self) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if !self.pinned {
+            return Err(ThreadError::NotPinned);
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadUnpinned {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+        });
+        Ok((
+            Self {
+                pinned: false,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+
+    pub fn untag(&self, tag: ThreadTag) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if !self.tags.contains(&tag) {
+            return Err(ThreadError::NotTagged(tag));
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadUntagged {
+            at: DateTime::now(),
+            id: EventId::generate(),
+            tag: tag.to_string(),
+            thread_id: self.id.clone(),
             version: u32::from(version),
         });
+        let mut tags = self.tags.clone();
+        tags.remove(&tag);
         Ok((
             Self {
-                id: self.id.clone(),
-                message_count: self.message_count + 1,
-                root_message: self.root_message.clone(),
+                tags,
                 version,
+                ..self.clone()
             },
             vec![event],
         ))
@@ -125,57 +1059,806 @@ impl Thread {
     pub fn version(&self) -> Version {
         self.version
     }
+
+    pub fn vote(
+        &self,
+        option: u8,
+        voter: VoterKey,
+    ) -> Result<(Self, Vec<ThreadEvent>), ThreadError> {
+        if self.closed {
+            return Err(ThreadError::Closed);
+        }
+        let poll = self.poll.as_ref().ok_or(ThreadError::PollNotFound)?;
+        if usize::from(option) >= poll.options().len() {
+            return Err(ThreadError::PollOptionNotFound(option));
+        }
+        let at = DateTime::now();
+        if poll.is_closed_at(at) {
+            return Err(ThreadError::PollClosed);
+        }
+        if self.poll_voters.contains(&voter) {
+            return Err(ThreadError::PollAlreadyVoted);
+        }
+        let version = self.version.next()?;
+        let event = ThreadEvent::from(ThreadPollVoted {
+            at,
+            id: EventId::generate(),
+            option,
+            thread_id: self.id.clone(),
+            version: u32::from(version),
+            voter: voter.clone(),
+        });
+        let mut poll_voters = self.poll_voters.clone();
+        poll_voters.insert(voter);
+        Ok((
+            Self {
+                poll_voters,
+                version,
+                ..self.clone()
+            },
+            vec![event],
+        ))
+    }
+}
+
+fn attachments_from_event(attachments: &[ThreadMessageAttachment]) -> Vec<MessageAttachment> {
+    attachments
+        .iter()
+        .map(|it| MessageAttachment {
+            hash: it.hash.clone(),
+            mime: it.mime,
+            size: it.size,
+            thumbnail_hash: it.thumbnail_hash.clone(),
+        })
+        .collect()
+}
+
+fn attachments_to_event(attachments: &[MessageAttachment]) -> Vec<ThreadMessageAttachment> {
+    attachments
+        .iter()
+        .map(|it| ThreadMessageAttachment {
+            hash: it.hash.clone(),
+            mime: it.mime,
+            size: it.size,
+            thumbnail_hash: it.thumbnail_hash.clone(),
+        })
+        .collect()
+}
+
+/// Returns the name of the invalid field on error
+fn poster_from_event(
+    name: &Option<String>,
+    trip: &Option<String>,
+) -> Result<Option<Poster>, &'static str> {
+    match (name, trip) {
+        (None, None) => Ok(None),
+        (name, trip) => Poster::new(name.clone().unwrap_or_default(), trip.clone())
+            .map(Some)
+            .map_err(|_| "name"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_change_title() -> anyhow::Result<()> {
+        let message = Message::new_for_testing();
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            message.clone(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let title = ThreadTitle::new_for_testing();
+        let (changed, events) = created.change_title(title.clone())?;
+        assert_eq!(changed.id(), created.id());
+        assert_eq!(changed.title(), &title);
+        assert_eq!(changed.version(), created.version().next()?);
+        assert_eq!(events.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_close_and_reopen() -> anyhow::Result<()> {
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        assert!(matches!(created.reopen(), Err(ThreadError::NotClosed)));
+
+        let (closed, events) = created.close()?;
+        assert_eq!(closed.version(), created.version().next()?);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(closed.close(), Err(ThreadError::Closed)));
+        assert!(matches!(
+            closed.reply(Message::new_for_testing(), false, 1000),
+            Err(ThreadError::Closed)
+        ));
+        assert!(matches!(
+            closed.edit_message(
+                1,
+                MessageContent::new_for_testing(),
+                std::time::Duration::from_secs(60)
+            ),
+            Err(ThreadError::Closed)
+        ));
+
+        let (reopened, events) = closed.reopen()?;
+        assert_eq!(reopened.version(), closed.version().next()?);
+        assert_eq!(events.len(), 1);
+        reopened.reply(Message::new_for_testing(), false, 1000)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_continue_thread() -> anyhow::Result<()> {
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        assert!(matches!(
+            created.continue_thread(ThreadTitle::new_for_testing(), Message::new_for_testing()),
+            Err(ThreadError::NotFilled)
+        ));
+
+        let mut events = created_events;
+        let mut filled = created;
+        for _ in 0..999 {
+            let (t, e) = filled.reply(Message::new_for_testing(), false, 1000)?;
+            filled = t;
+            events.extend(e);
+        }
+        let ((continued, continued_events), (next, next_events)) =
+            filled.continue_thread(ThreadTitle::new_for_testing(), Message::new_for_testing())?;
+        assert_eq!(continued.version(), filled.version().next()?);
+        assert_eq!(continued.next_thread_id(), Some(next.id()));
+        assert_eq!(continued_events.len(), 1);
+        assert_eq!(next.previous_thread_id.as_ref(), Some(filled.id()));
+        assert_eq!(next.version(), Version::initial());
+        assert_eq!(next_events.len(), 1);
+        assert!(matches!(
+            continued.continue_thread(ThreadTitle::new_for_testing(), Message::new_for_testing()),
+            Err(ThreadError::Continued(_))
+        ));
+
+        events.extend(continued_events);
+        assert_eq!(Thread::replay(&events)?, continued);
+        assert_eq!(Thread::replay(&next_events)?, next);
+
+        Ok(())
+    }
+
     #[test]
     fn test_create() -> anyhow::Result<()> {
         let message = Message::new_for_testing();
-        let (created, _events) = Thread::create(message.clone())?;
+        let title = ThreadTitle::new_for_testing();
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            title.clone(),
+            message.clone(),
+            BTreeSet::new(),
+            None,
+        )?;
         assert!(!created.id().to_string().is_empty());
+        assert_eq!(created.board_id(), &BoardId::default());
+        assert_eq!(created.title(), &title);
         assert_eq!(created.version(), Version::initial());
+
+        // the secret of the poster and the IP address of the client are not persisted
+        let message = Message::create(
+            MessageContent::new_for_testing(),
+            Some(Poster::try_from("Alice#not-persisted".to_owned())?),
+            vec![],
+            Some(ClientKey::from_ip(
+                &std::net::IpAddr::from([192, 0, 2, 1]),
+                "secret",
+            )),
+        );
+        let (created, events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            message.clone(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let json = serde_json::to_string(&events)?;
+        assert!(json.contains(r#""name":"Alice""#));
+        assert!(json.contains(r#""trip":""#));
+        assert!(!json.contains("not-persisted"));
+        assert!(!json.contains("192.0.2.1"));
+        assert!(json.contains(&format!(
+            r#""fingerprint":"{}""#,
+            ContentFingerprint::of(&message.content)
+        )));
+        assert_eq!(Thread::replay(&events)?, created);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_message() -> anyhow::Result<()> {
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let (replied, _events) = created.reply(Message::new_for_testing(), false, 1000)?;
+        let (deleted, events) = replied.delete_message(2, MessageDeletionReason::Moderator)?;
+        assert_eq!(deleted.id(), replied.id());
+        assert_eq!(deleted.messages.len(), 2);
+        assert_eq!(deleted.version(), replied.version().next()?);
+        assert_eq!(events.len(), 1);
+
+        // only existing messages
+        assert!(
+            deleted
+                .delete_message(3, MessageDeletionReason::AuthorRequest)
+                .is_err()
+        );
+        // deleted messages can not be deleted or edited
+        assert!(
+            deleted
+                .delete_message(2, MessageDeletionReason::AuthorRequest)
+                .is_err()
+        );
+        assert!(
+            deleted
+                .edit_message(
+                    2,
+                    MessageContent::new_for_testing(),
+                    std::time::Duration::from_secs(60)
+                )
+                .is_err()
+        );
+        // message numbering is kept
+        let (replied, _events) = deleted.reply(Message::new_for_testing(), false, 1000)?;
+        assert_eq!(replied.messages.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_message() -> anyhow::Result<()> {
+        let edit_window = std::time::Duration::from_secs(60);
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let content = MessageContent::new_for_testing();
+        let (edited, events) = created.edit_message(1, content.clone(), edit_window)?;
+        assert_eq!(edited.id(), created.id());
+        assert_eq!(edited.messages[0].content, content);
+        assert_eq!(
+            edited.messages[0].created_at,
+            created.messages[0].created_at
+        );
+        assert_eq!(edited.version(), created.version().next()?);
+        assert_eq!(events.len(), 1);
+
+        // only existing messages
+        assert!(
+            created
+                .edit_message(0, MessageContent::new_for_testing(), edit_window)
+                .is_err()
+        );
+        assert!(
+            created
+                .edit_message(2, MessageContent::new_for_testing(), edit_window)
+                .is_err()
+        );
+
+        // only within the edit window
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message {
+                attachments: vec![],
+                client_key: None,
+                content: MessageContent::new_for_testing(),
+                created_at: DateTime::from_unix_timestamp_millis(0),
+                poster: None,
+            },
+            BTreeSet::new(),
+            None,
+        )?;
+        assert!(
+            created
+                .edit_message(1, MessageContent::new_for_testing(), edit_window)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_posted_by() -> anyhow::Result<()> {
+        let client_key = ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 1]), "secret");
+        let other_client_key =
+            ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 2]), "secret");
+        let (created, _) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message {
+                client_key: Some(client_key.clone()),
+                ..Message::new_for_testing()
+            },
+            BTreeSet::new(),
+            None,
+        )?;
+        let (replied, _) = created.reply(Message::new_for_testing(), false, 1000)?;
+        assert!(replied.is_posted_by(1, &client_key));
+        assert!(!replied.is_posted_by(1, &other_client_key));
+        // the client of the message is unknown
+        assert!(!replied.is_posted_by(2, &client_key));
+        assert!(!replied.is_posted_by(0, &client_key));
+        assert!(!replied.is_posted_by(3, &client_key));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pin_and_unpin() -> anyhow::Result<()> {
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        assert!(matches!(created.unpin(), Err(ThreadError::NotPinned)));
+
+        let (pinned, pinned_events) = created.pin()?;
+        assert_eq!(pinned.version(), created.version().next()?);
+        assert_eq!(pinned_events.len(), 1);
+        assert!(matches!(pinned.pin(), Err(ThreadError::Pinned)));
+        assert_eq!(
+            Thread::replay(&[created_events, pinned_events].concat())?,
+            pinned
+        );
+
+        let (unpinned, events) = pinned.unpin()?;
+        assert_eq!(unpinned.version(), pinned.version().next()?);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(unpinned.unpin(), Err(ThreadError::NotPinned)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_react_and_unreact() -> anyhow::Result<()> {
+        let alice = ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 1]), "secret");
+        let bob = ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 2]), "secret");
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        assert!(matches!(
+            created.unreact(1, MessageReaction::Heart, alice.clone()),
+            Err(ThreadError::ReactionNotFound(1, MessageReaction::Heart))
+        ));
+        assert!(matches!(
+            created.react(2, MessageReaction::Heart, alice.clone()),
+            Err(ThreadError::MessageNotFound(2))
+        ));
+
+        let (reacted1, reacted1_events) =
+            created.react(1, MessageReaction::Heart, alice.clone())?;
+        assert!(matches!(
+            reacted1.react(1, MessageReaction::Heart, alice.clone()),
+            Err(ThreadError::AlreadyReacted(1, MessageReaction::Heart))
+        ));
+        let (reacted2, reacted2_events) = reacted1.react(1, MessageReaction::Heart, bob.clone())?;
+        assert_eq!(reacted2.reactions[&(1, MessageReaction::Heart)], 2);
+        assert_eq!(reacted2.version(), reacted1.version().next()?);
+
+        let (unreacted, unreacted_events) =
+            reacted2.unreact(1, MessageReaction::Heart, alice.clone())?;
+        assert_eq!(unreacted.reactions[&(1, MessageReaction::Heart)], 1);
+        // only the own reaction can be removed
+        assert!(matches!(
+            unreacted.unreact(1, MessageReaction::Heart, alice.clone()),
+            Err(ThreadError::ReactionNotFound(1, MessageReaction::Heart))
+        ));
+        let (unreacted, _) = unreacted.unreact(1, MessageReaction::Heart, bob.clone())?;
+        assert!(unreacted.reactions.is_empty());
+
+        let replayed = Thread::replay(
+            &created_events
+                .into_iter()
+                .chain(reacted1_events)
+                .chain(reacted2_events)
+                .chain(unreacted_events)
+                .collect::<Vec<_>>(),
+        )?;
+        assert_eq!(replayed.reactions[&(1, MessageReaction::Heart)], 1);
+        assert_eq!(
+            replayed.reactors,
+            BTreeSet::from([(1, MessageReaction::Heart, bob.clone())])
+        );
+
+        let (closed, _) = replayed.close()?;
+        assert!(matches!(
+            closed.react(1, MessageReaction::Heart, alice),
+            Err(ThreadError::Closed)
+        ));
+
         Ok(())
     }
 
     #[test]
     fn test_replay() -> anyhow::Result<()> {
         let message = Message::new_for_testing();
-        let (created, created_events) = Thread::create(message.clone())?;
-        let (replied, replied_events) = created.reply(Message::new_for_testing())?;
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            message.clone(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let (replied, replied_events) = created.reply(Message::new_for_testing(), false, 1000)?;
+        let (edited, edited_events) = replied.edit_message(
+            2,
+            MessageContent::new_for_testing(),
+            std::time::Duration::from_secs(60),
+        )?;
+        let (deleted, deleted_events) =
+            edited.delete_message(1, MessageDeletionReason::AuthorRequest)?;
+        let (changed, changed_events) = deleted.change_title(ThreadTitle::new_for_testing())?;
+        let (closed, closed_events) = changed.close()?;
         let replayed = Thread::replay(
             &created_events
                 .into_iter()
                 .chain(replied_events.into_iter())
+                .chain(edited_events)
+                .chain(deleted_events)
+                .chain(changed_events)
+                .chain(closed_events)
                 .collect::<Vec<_>>(),
+        )?;
+
+        assert_eq!(replayed.id(), closed.id());
+        assert_eq!(replayed.version(), closed.version());
+        assert_eq!(replayed.messages, closed.messages);
+        assert_eq!(
+            replayed.deleted_message_numbers,
+            closed.deleted_message_numbers
         );
+        assert_eq!(replayed.title(), closed.title());
+        assert!(replayed.closed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_corrupted() -> anyhow::Result<()> {
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let (_, closed_events) = created.close()?;
+        let created_event = created_events[0].clone();
+        let closed_event = closed_events[0].clone();
+
+        assert!(matches!(Thread::replay(&[]), Err(ReplayError::Empty)));
+        assert!(matches!(
+            Thread::replay(std::slice::from_ref(&closed_event)),
+            Err(ReplayError::NotCreatedFirst { kind: "closed", .. })
+        ));
+        assert!(matches!(
+            Thread::replay(&[created_event.clone(), created_event.clone()]),
+            Err(ReplayError::DuplicateCreated { version: 1, .. })
+        ));
+        assert!(matches!(
+            Thread::replay(&[created_event.clone(), closed_event.clone(), closed_event]),
+            Err(ReplayError::VersionOutOfOrder {
+                actual: 2,
+                expected: 3,
+                ..
+            })
+        ));
+        let ThreadEvent::Created(created_data) = created_event else {
+            unreachable!()
+        };
+        let invalid = ThreadEvent::from(ThreadCreated {
+            content: " ".to_owned(),
+            ..created_data
+        });
+        assert!(matches!(
+            Thread::replay(&[invalid]),
+            Err(ReplayError::InvalidField {
+                field: "content",
+                version: 1,
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot() -> anyhow::Result<()> {
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::from([ThreadTag::from_str("rust")?]),
+            Some(Poll::new_for_testing()),
+        )?;
+        let (replied, replied_events) = created.reply(Message::new_for_testing(), false, 1000)?;
+        let (reacted, reacted_events) = replied.react(
+            2,
+            MessageReaction::Heart,
+            ClientKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 1]), "secret"),
+        )?;
+        let (voted, voted_events) = reacted.vote(
+            1,
+            VoterKey::from_ip(&std::net::IpAddr::from([192, 0, 2, 1]), "secret"),
+        )?;
+        let (deleted, deleted_events) =
+            voted.delete_message(2, MessageDeletionReason::AuthorRequest)?;
 
-        assert_eq!(replayed.id(), replied.id());
-        assert_eq!(replayed.version(), replied.version());
-        assert_eq!(replayed.message_count, replied.message_count);
+        let snapshot = voted.snapshot();
+        assert_eq!(snapshot.version, u32::from(voted.version()));
+        let restored =
+            Thread::from_snapshot(serde_json::from_str(&serde_json::to_string(&snapshot)?)?)?;
+        assert_eq!(restored, voted);
+        assert_eq!(restored.replay_from(&deleted_events)?, deleted);
+        assert_eq!(
+            Thread::replay(
+                &[
+                    created_events,
+                    replied_events,
+                    reacted_events,
+                    voted_events,
+                    deleted_events.clone()
+                ]
+                .concat()
+            )?,
+            deleted
+        );
 
+        // the events before the snapshot are not applied again
+        assert!(matches!(
+            Thread::from_snapshot(deleted.snapshot())?.replay_from(&deleted_events),
+            Err(ReplayError::VersionOutOfOrder { .. })
+        ));
+        assert!(matches!(
+            Thread::from_snapshot(ThreadSnapshot {
+                title: String::new(),
+                ..deleted.snapshot()
+            }),
+            Err(ThreadSnapshotError::InvalidField("title"))
+        ));
         Ok(())
     }
 
     #[test]
     fn test_reply() -> anyhow::Result<()> {
         let root_message = Message::new_for_testing();
-        let (created, _events) = Thread::create(root_message.clone())?;
+        let (created, _events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            root_message.clone(),
+            BTreeSet::new(),
+            None,
+        )?;
         let reply_message = Message::new_for_testing();
-        let (replied, _events) = created.reply(reply_message.clone())?;
+        let (replied, _events) = created.reply(reply_message.clone(), false, 1000)?;
 
         assert_eq!(replied.id(), created.id());
-        assert_eq!(replied.version(), created.version().next());
+        assert_eq!(replied.version(), created.version().next()?);
+
+        let (filled, events) = replied.reply(Message::new_for_testing(), false, 3)?;
+        assert!(filled.filled);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], ThreadEvent::Filled(_)));
 
         // 1000 messages limit
         let mut t = replied;
-        for _ in 0..998 {
-            (t, _) = t.reply(Message::new_for_testing())?;
+        for _ in 0..997 {
+            (t, _) = t.reply(Message::new_for_testing(), false, 1000)?;
+        }
+        let (t, events) = t.reply(Message::new_for_testing(), false, 1000)?;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], ThreadEvent::Filled(_)));
+        assert!(matches!(
+            t.reply(Message::new_for_testing(), false, 1000),
+            Err(ThreadError::Filled)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply_with_attachments() -> anyhow::Result<()> {
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        let attachments = |n: usize| vec![MessageAttachment::new_for_testing(); n];
+        let (replied, replied_events) = created.reply(
+            Message {
+                attachments: attachments(Thread::MAX_ATTACHMENTS),
+                ..Message::new_for_testing()
+            },
+            false,
+            1000,
+        )?;
+        let replayed = Thread::replay(
+            &created_events
+                .into_iter()
+                .chain(replied_events)
+                .collect::<Vec<_>>(),
+        )?;
+        assert_eq!(replayed.messages, replied.messages);
+        assert_eq!(replayed.messages[1].attachments.len(), 4);
+
+        assert!(matches!(
+            created.reply(
+                Message {
+                    attachments: attachments(5),
+                    ..Message::new_for_testing()
+                },
+                false,
+                1000,
+            ),
+            Err(ThreadError::TooManyAttachments(5))
+        ));
+        assert!(matches!(
+            Thread::create(
+                BoardId::default(),
+                ThreadTitle::new_for_testing(),
+                Message {
+                    attachments: attachments(5),
+                    ..Message::new_for_testing()
+                },
+                BTreeSet::new(),
+                None,
+            ),
+            Err(ThreadError::TooManyAttachments(5))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag_and_untag() -> anyhow::Result<()> {
+        let tag = |s: &str| ThreadTag::from_str(s);
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::from([tag("rust")?]),
+            None,
+        )?;
+        assert_eq!(created.tags, BTreeSet::from([tag("rust")?]));
+        assert!(matches!(
+            created.tag(tag("rust")?),
+            Err(ThreadError::Tagged(_))
+        ));
+        assert!(matches!(
+            created.untag(tag("web")?),
+            Err(ThreadError::NotTagged(_))
+        ));
+
+        let (tagged, tagged_events) = created.tag(tag("web")?)?;
+        assert_eq!(tagged.version(), created.version().next()?);
+        assert_eq!(tagged.tags, BTreeSet::from([tag("rust")?, tag("web")?]));
+        let (untagged, untagged_events) = tagged.untag(tag("rust")?)?;
+        assert_eq!(untagged.tags, BTreeSet::from([tag("web")?]));
+        assert_eq!(
+            Thread::replay(&[created_events, tagged_events, untagged_events].concat())?,
+            untagged
+        );
+
+        let mut tagged = untagged;
+        for s in ["a", "b", "c", "d"] {
+            (tagged, _) = tagged.tag(tag(s)?)?;
+        }
+        assert!(matches!(
+            tagged.tag(tag("e")?),
+            Err(ThreadError::TooManyTags(6))
+        ));
+        assert!(matches!(
+            Thread::create(
+                BoardId::default(),
+                ThreadTitle::new_for_testing(),
+                Message::new_for_testing(),
+                ThreadTag::parse_list("a b c d e f")?,
+                None,
+            ),
+            Err(ThreadError::TooManyTags(6))
+        ));
+
+        // the next thread inherits the tags
+        let mut filled = tagged;
+        for _ in 0..(1000 - filled.messages.len()) {
+            (filled, _) = filled.reply(Message::new_for_testing(), false, 1000)?;
         }
-        assert!(t.reply(Message::new_for_testing()).is_err());
+        let (_, (next, _)) =
+            filled.continue_thread(ThreadTitle::new_for_testing(), Message::new_for_testing())?;
+        assert_eq!(next.tags, filled.tags);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vote() -> anyhow::Result<()> {
+        let voter =
+            |last: u8| VoterKey::from_ip(&std::net::IpAddr::from([192, 0, 2, last]), "secret");
+        let (without_poll, _) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            None,
+        )?;
+        assert!(matches!(
+            without_poll.vote(0, voter(1)),
+            Err(ThreadError::PollNotFound)
+        ));
+
+        let (created, created_events) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            Some(Poll::new_for_testing()),
+        )?;
+        assert!(matches!(
+            created.vote(2, voter(1)),
+            Err(ThreadError::PollOptionNotFound(2))
+        ));
+        let (voted, voted_events) = created.vote(0, voter(1))?;
+        assert_eq!(voted.version(), created.version().next()?);
+        assert!(matches!(
+            voted.vote(1, voter(1)),
+            Err(ThreadError::PollAlreadyVoted)
+        ));
+        let (voted, voted_events2) = voted.vote(1, voter(2))?;
+        assert_eq!(
+            Thread::replay(&[created_events, voted_events, voted_events2].concat())?,
+            voted
+        );
+
+        let (closed, _) = voted.close()?;
+        assert!(matches!(closed.vote(0, voter(3)), Err(ThreadError::Closed)));
 
+        let (expired, _) = Thread::create(
+            BoardId::default(),
+            ThreadTitle::new_for_testing(),
+            Message::new_for_testing(),
+            BTreeSet::new(),
+            Some(Poll::new(
+                "Do you agree?".to_owned(),
+                vec!["yes".to_owned(), "no".to_owned()],
+                Some(DateTime::from_unix_timestamp_millis(0)),
+            )?),
+        )?;
+        assert!(matches!(
+            expired.vote(0, voter(1)),
+            Err(ThreadError::PollClosed)
+        ));
         Ok(())
     }
 }
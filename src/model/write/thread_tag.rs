@@ -0,0 +1,92 @@
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadTagError {
+    #[error("empty")]
+    Empty,
+    #[error("invalid character: {0:?}")]
+    InvalidCharacter(char),
+    #[error("too long: {0}")]
+    TooLong(usize),
+}
+
+/// A topic label of a thread (e.g. `rust`, `help-wanted`)
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ThreadTag(String);
+
+impl ThreadTag {
+    const MAX_LEN: usize = 32;
+
+    /// Parses a comma or whitespace separated list of tags, ignoring case and duplicates
+    pub fn parse_list(s: &str) -> Result<std::collections::BTreeSet<Self>, ThreadTagError> {
+        s.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|it| !it.is_empty())
+            .map(|it| <Self as std::str::FromStr>::from_str(&it.to_lowercase()))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ThreadTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for ThreadTag {
+    type Err = ThreadTagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ThreadTagError::Empty);
+        }
+        let len = s.chars().count();
+        if len > Self::MAX_LEN {
+            return Err(ThreadTagError::TooLong(len));
+        }
+        if let Some(c) = s
+            .chars()
+            .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-' || *c == '_'))
+        {
+            return Err(ThreadTagError::InvalidCharacter(c));
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_impl_from_str() -> anyhow::Result<()> {
+        assert_eq!(ThreadTag::from_str("rust")?.to_string(), "rust");
+        assert_eq!(
+            ThreadTag::from_str("help-wanted_2")?.to_string(),
+            "help-wanted_2"
+        );
+        assert!(ThreadTag::from_str(&"x".repeat(32)).is_ok());
+        assert!(ThreadTag::from_str(&"x".repeat(33)).is_err());
+        assert!(ThreadTag::from_str("").is_err());
+        assert!(ThreadTag::from_str("Rust").is_err());
+        assert_eq!(
+            ThreadTag::from_str("c++").unwrap_err().to_string(),
+            "invalid character: '+'"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_list() -> anyhow::Result<()> {
+        assert!(ThreadTag::parse_list("")?.is_empty());
+        assert!(ThreadTag::parse_list(" , ")?.is_empty());
+        assert_eq!(
+            ThreadTag::parse_list("Rust, web rust")?
+                .into_iter()
+                .map(|it| it.to_string())
+                .collect::<Vec<String>>(),
+            vec!["rust", "web"]
+        );
+        assert!(ThreadTag::parse_list("rust c++").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+#[derive(Debug, thiserror::Error)]
+pub enum ThreadTitleError {
+    #[error("contains newline")]
+    ContainsNewline,
+    #[error("empty")]
+    Empty,
+    #[error("too long: {0}")]
+    TooLong(usize),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThreadTitle(String);
+
+impl ThreadTitle {
+    #[cfg(test)]
+    pub fn new_for_testing() -> Self {
+        use rand::Rng;
+        let mut rng = rand::rng();
+        let len = rng.random_range(1..=64);
+        let s = rng
+            .sample_iter(rand::distr::Alphanumeric)
+            .map(char::from)
+            .take(len)
+            .collect::<String>();
+        Self(s)
+    }
+}
+
+impl From<ThreadTitle> for String {
+    fn from(value: ThreadTitle) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for ThreadTitle {
+    type Error = ThreadTitleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let len = value.trim().chars().count();
+        if len == 0 {
+            Err(ThreadTitleError::Empty)
+        } else if len > 64 {
+            Err(ThreadTitleError::TooLong(len))
+        } else if value.contains(['\r', '\n']) {
+            Err(ThreadTitleError::ContainsNewline)
+        } else {
+            assert!((1..=64).contains(&len));
+            Ok(Self(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_conversion() -> anyhow::Result<()> {
+        let s = "Hello, World!".to_owned();
+        let title = ThreadTitle::try_from(s.clone())?;
+        assert_eq!(String::from(title), s);
+
+        let s = String::default();
+        assert!(ThreadTitle::try_from(s).is_err());
+
+        let s = " 　".to_owned();
+        assert!(ThreadTitle::try_from(s).is_err());
+
+        let s = "x".repeat(64);
+        assert_eq!(String::from(ThreadTitle::try_from(s.clone())?), s);
+        let s = "x".repeat(65);
+        assert!(ThreadTitle::try_from(s).is_err());
+
+        let s = "Hello,\nWorld!".to_owned();
+        assert!(ThreadTitle::try_from(s).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_for_testing() {
+        let title1 = ThreadTitle::new_for_testing();
+        let title2 = ThreadTitle::new_for_testing();
+        assert_ne!(title1, title2);
+    }
+}
@@ -1,3 +1,7 @@
+#[derive(Debug, thiserror::Error)]
+#[error("version overflow")]
+pub struct VersionError;
+
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Version(u32);
 
@@ -6,9 +10,8 @@ impl Version {
         Self(1)
     }
 
-    pub fn next(&self) -> Self {
-        // TODO: overflow
-        Self(self.0 + 1)
+    pub fn next(&self) -> Result<Self, VersionError> {
+        self.0.checked_add(1).map(Self).ok_or(VersionError)
     }
 }
 
@@ -23,3 +26,15 @@ impl From<Version> for u32 {
         value.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next() -> anyhow::Result<()> {
+        assert_eq!(Version::initial().next()?, Version::from(2));
+        assert!(Version::from(u32::MAX).next().is_err());
+        Ok(())
+    }
+}
@@ -1,3 +1,119 @@
+#[derive(Debug, thiserror::Error)]
+#[error("blob reference reader error")]
+pub struct BlobReferenceReaderError(#[source] pub Box<dyn std::error::Error + Send + Sync>);
+
+#[async_trait::async_trait]
+pub trait BlobReferenceReader {
+    /// Returns whether the blob is an image or a thumbnail attached to a message
+    ///
+    /// The attachments of the deleted messages are not references.
+    async fn is_blob_referenced(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<bool, BlobReferenceReaderError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("blob store error")]
+pub struct BlobStoreError(#[source] pub Box<dyn std::error::Error + Send + Sync>);
+
+/// A content-addressed store of binary data such as attached images
+#[async_trait::async_trait]
+pub trait BlobStore {
+    async fn get_blob(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<Option<Vec<u8>>, BlobStoreError>;
+
+    /// Stores the bytes under their hash, doing nothing if they are already stored
+    async fn put_blob(
+        &self,
+        bytes: &[u8],
+    ) -> Result<crate::model::shared::id::BlobHash, BlobStoreError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("board reader error")]
+pub struct BoardReaderError(#[source] pub Box<dyn std::error::Error + Send + Sync>);
+
+#[async_trait::async_trait]
+pub trait BoardReader {
+    async fn get_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::read::Board>, BoardReaderError>;
+
+    async fn list_boards(&self) -> Result<Vec<crate::model::read::Board>, BoardReaderError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BoardRepositoryError {
+    #[error("already exists {0:?}")]
+    AlreadyExists(crate::model::shared::id::BoardId),
+
+    #[error("internal error: {0}")]
+    InternalError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[async_trait::async_trait]
+pub trait BoardRepository {
+    async fn find_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::write::Board>, BoardRepositoryError>;
+
+    async fn store_board(
+        &self,
+        board: &crate::model::write::Board,
+    ) -> Result<(), BoardRepositoryError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("content policy reader error")]
+pub struct ContentPolicyReaderError(#[source] pub Box<dyn std::error::Error + Send + Sync>);
+
+#[async_trait::async_trait]
+pub trait ContentPolicyReader {
+    /// Returns the current content policy, which may change while the server is running
+    async fn get_content_policy(
+        &self,
+    ) -> Result<std::sync::Arc<crate::model::write::ContentPolicy>, ContentPolicyReaderError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("posted message reader error")]
+pub struct PostedMessageReaderError(#[source] pub Box<dyn std::error::Error + Send + Sync>);
+
+#[async_trait::async_trait]
+pub trait PostedMessageReader {
+    /// Lists the messages posted at or after `since` with the content fingerprint
+    async fn list_posted_messages(
+        &self,
+        fingerprint: &crate::model::write::ContentFingerprint,
+        since: crate::utils::date_time::DateTime,
+    ) -> Result<Vec<crate::model::write::PostedMessage>, PostedMessageReaderError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimiterError {
+    #[error("exceeded")]
+    Exceeded(#[source] crate::model::write::RateLimitExceeded),
+
+    #[error("internal error: {0}")]
+    InternalError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[async_trait::async_trait]
+pub trait RateLimiter {
+    /// Counts a request of the client for the action, or fails if the limit is exceeded
+    async fn consume_rate_limit(
+        &self,
+        action: crate::model::write::RateLimitAction,
+        client: &crate::model::write::ClientKey,
+        limit: &crate::model::write::RateLimit,
+    ) -> Result<(), RateLimiterError>;
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("thread reader error")]
 pub struct ThreadReaderError(#[source] pub Box<dyn std::error::Error + Send + Sync>);
@@ -9,13 +125,28 @@ pub trait ThreadReader {
         id: &crate::model::shared::id::ThreadId,
     ) -> Result<Option<crate::model::read::Thread>, ThreadReaderError>;
 
+    /// Lists the tags used by at least one thread, sorted by name
+    async fn list_tags(&self) -> Result<Vec<crate::model::read::ThreadTag>, ThreadReaderError>;
+
+    /// Lists the threads of the board, or of all boards if `board_id` is `None`
+    ///
+    /// Only the threads with the tag are listed if `tag` is specified.
     async fn list_threads(
         &self,
+        board_id: Option<&crate::model::shared::id::BoardId>,
+        tag: Option<&crate::model::write::ThreadTag>,
     ) -> Result<Vec<crate::model::read::ThreadWithoutMessages>, ThreadReaderError>;
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum ThreadRepositoryError {
+    /// The stored events of the thread cannot be replayed
+    #[error("corrupted {thread_id:?}")]
+    Corrupted {
+        source: crate::model::shared::replay::ReplayError,
+        thread_id: crate::model::shared::id::ThreadId,
+    },
+
     #[error("internal error: {0}")]
     InternalError(Box<dyn std::error::Error + Send + Sync>),
 
@@ -39,6 +170,17 @@ pub trait ThreadRepository {
     async fn store(
         &self,
         version: Option<crate::model::write::Version>,
-        events: &[crate::model::shared::event::ThreadEvent],
+        events: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+    ) -> Result<(), ThreadRepositoryError>;
+
+    /// Stores the events of several threads at once
+    ///
+    /// None of the events are stored if the version of any thread does not match.
+    async fn store_all(
+        &self,
+        streams: &[(
+            Option<crate::model::write::Version>,
+            &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+        )],
     ) -> Result<(), ThreadRepositoryError>;
 }
@@ -1,16 +1,54 @@
+mod file_content_policy_reader;
 #[cfg(feature = "firestore")]
 mod firestore_store;
 mod in_memory_store;
+mod local_blob_store;
 #[cfg(feature = "sqlite")]
 mod sqlite_store;
 
+pub use self::file_content_policy_reader::FileContentPolicyReader;
 #[allow(unused_imports)]
 #[cfg(feature = "firestore")]
 pub use self::firestore_store::FirestoreStore;
 #[allow(unused_imports)]
 pub use self::in_memory_store::InMemoryStore;
+pub use self::local_blob_store::LocalBlobStore;
 #[allow(unused_imports)]
 #[cfg(feature = "sqlite")]
 pub use self::sqlite_store::SqliteStore;
 
-pub trait Store: crate::port::ThreadReader + crate::port::ThreadRepository {}
+pub trait Store:
+    crate::port::BlobReferenceReader
+    + crate::port::BoardReader
+    + crate::port::BoardRepository
+    + crate::port::PostedMessageReader
+    + crate::port::RateLimiter
+    + crate::port::ThreadReader
+    + crate::port::ThreadRepository
+{
+}
+
+/// Restores the thread from the snapshot, or returns `None` to replay all the events
+fn restore_snapshot(
+    id: &crate::model::shared::id::ThreadId,
+    stored: &crate::model::shared::thread_snapshot::StoredThreadSnapshot,
+) -> Option<crate::model::write::Thread> {
+    match stored
+        .deserialize()
+        .and_then(crate::model::write::Thread::from_snapshot)
+    {
+        Ok(thread) if thread.id() == id => Some(thread),
+        Ok(_) => {
+            tracing::warn!(thread_id = %id, "thread snapshot of another thread discarded");
+            None
+        }
+        // the snapshot in an old format is rebuilt
+        Err(
+            crate::model::shared::thread_snapshot::ThreadSnapshotError::UnsupportedFormatVersion(_),
+        ) => None,
+        Err(e) => {
+            tracing::warn!(thread_id = %id, error = %e, "thread snapshot discarded");
+            None
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use crate::model::write::ContentPolicy;
+
+/// A content policy reader that loads the policy from a JSON file
+///
+/// The file is loaded again when its modification time changes, so the rules can be updated
+/// without restarting the server. When the updated file is invalid, the previously loaded policy
+/// is kept. Without a file, every message content is allowed.
+///
+/// ```json
+/// {
+///   "banned_patterns": ["(?i)buy\\s+now"],
+///   "banned_words": ["spam"],
+///   "forbidden_characters": ["bidi_control", "zero_width"],
+///   "max_lines": 20,
+///   "max_urls": 3
+/// }
+/// ```
+#[derive(Clone)]
+pub struct FileContentPolicyReader {
+    cache: Arc<tokio::sync::Mutex<Option<Cached>>>,
+    path: Option<std::path::PathBuf>,
+}
+
+struct Cached {
+    modified: std::time::SystemTime,
+    policy: Arc<ContentPolicy>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContentPolicyFile {
+    #[serde(default)]
+    banned_patterns: Vec<String>,
+    #[serde(default)]
+    banned_words: Vec<String>,
+    #[serde(default)]
+    forbidden_characters: Vec<String>,
+    #[serde(default)]
+    max_lines: Option<usize>,
+    #[serde(default)]
+    max_urls: Option<usize>,
+}
+
+impl FileContentPolicyReader {
+    pub fn new(path: Option<std::path::PathBuf>) -> Self {
+        Self {
+            cache: Arc::new(tokio::sync::Mutex::new(None)),
+            path,
+        }
+    }
+
+    async fn load(path: &std::path::Path) -> Result<ContentPolicy, FileContentPolicyReaderError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(FileContentPolicyReaderError::Read)?;
+        let file = serde_json::from_slice::<ContentPolicyFile>(&bytes)
+            .map_err(FileContentPolicyReaderError::Deserialize)?;
+        ContentPolicy::new(
+            &file.banned_patterns,
+            &file.banned_words,
+            &file.forbidden_characters,
+            file.max_lines,
+            file.max_urls,
+        )
+        .map_err(FileContentPolicyReaderError::InvalidContentPolicy)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum FileContentPolicyReaderError {
+    #[error("deserialize")]
+    Deserialize(#[source] serde_json::Error),
+    #[error("invalid content policy")]
+    InvalidContentPolicy(#[source] crate::model::write::ContentPolicyError),
+    #[error("metadata")]
+    Metadata(#[source] std::io::Error),
+    #[error("read")]
+    Read(#[source] std::io::Error),
+}
+
+impl From<FileContentPolicyReaderError> for crate::port::ContentPolicyReaderError {
+    fn from(err: FileContentPolicyReaderError) -> Self {
+        Self(err.into())
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::ContentPolicyReader for FileContentPolicyReader {
+    async fn get_content_policy(
+        &self,
+    ) -> Result<Arc<ContentPolicy>, crate::port::ContentPolicyReaderError> {
+        let Some(path) = &self.path else {
+            return Ok(Arc::new(ContentPolicy::default()));
+        };
+        let modified = tokio::fs::metadata(path)
+            .await
+            .and_then(|it| it.modified())
+            .map_err(FileContentPolicyReaderError::Metadata)?;
+        let mut cache = self.cache.lock().await;
+        if let Some(cached) = cache.as_ref()
+            && cached.modified == modified
+        {
+            return Ok(cached.policy.clone());
+        }
+        match Self::load(path).await {
+            Ok(policy) => {
+                tracing::info!(path = %path.display(), "content policy loaded");
+                let policy = Arc::new(policy);
+                *cache = Some(Cached {
+                    modified,
+                    policy: policy.clone(),
+                });
+                Ok(policy)
+            }
+            Err(e) => match cache.as_mut() {
+                Some(cached) => {
+                    tracing::warn!(path = %path.display(), error = ?e, "content policy not reloaded");
+                    // not to retry until the file is modified again
+                    cached.modified = modified;
+                    Ok(cached.policy.clone())
+                }
+                None => Err(e)?,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::port::ContentPolicyReader as _;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_content_policy() -> anyhow::Result<()> {
+        let content = |s: &str| crate::model::write::MessageContent::try_from(s.to_owned());
+
+        let reader = FileContentPolicyReader::new(None);
+        let policy = reader.get_content_policy().await?;
+        assert!(policy.check(&content("spam")?).is_ok());
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("content_policy.json");
+        let reader = FileContentPolicyReader::new(Some(path.clone()));
+        assert!(reader.get_content_policy().await.is_err());
+
+        std::fs::write(&path, r#"{"banned_words":["spam"]}"#)?;
+        let policy = reader.get_content_policy().await?;
+        assert!(policy.check(&content("spam")?).is_err());
+        assert!(policy.check(&content("ham")?).is_ok());
+
+        // reloaded when modified
+        std::fs::write(&path, r#"{"banned_words":["ham"]}"#)?;
+        let file = std::fs::File::options().write(true).open(&path)?;
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(1))?;
+        let policy = reader.get_content_policy().await?;
+        assert!(policy.check(&content("spam")?).is_ok());
+        assert!(policy.check(&content("ham")?).is_err());
+
+        // the previous policy is kept when the file is invalid
+        std::fs::write(&path, r#"{"banned_patterns":["("]}"#)?;
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(2))?;
+        let policy = reader.get_content_policy().await?;
+        assert!(policy.check(&content("ham")?).is_err());
+        Ok(())
+    }
+}
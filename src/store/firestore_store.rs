@@ -2,6 +2,16 @@ mod firestore_client;
 // TODO
 pub struct FirestoreStore;
 
+#[async_trait::async_trait]
+impl crate::port::BlobReferenceReader for FirestoreStore {
+    async fn is_blob_referenced(
+        &self,
+        _hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<bool, crate::port::BlobReferenceReaderError> {
+        todo!()
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::port::ThreadReader for FirestoreStore {
     async fn get_thread(
@@ -11,8 +21,16 @@ impl crate::port::ThreadReader for FirestoreStore {
         todo!()
     }
 
+    async fn list_tags(
+        &self,
+    ) -> Result<Vec<crate::model::read::ThreadTag>, crate::port::ThreadReaderError> {
+        todo!()
+    }
+
     async fn list_threads(
         &self,
+        _board_id: Option<&crate::model::shared::id::BoardId>,
+        _tag: Option<&crate::model::write::ThreadTag>,
     ) -> Result<Vec<crate::model::read::ThreadWithoutMessages>, crate::port::ThreadReaderError>
     {
         todo!()
@@ -31,7 +49,17 @@ impl crate::port::ThreadRepository for FirestoreStore {
     async fn store(
         &self,
         version: Option<crate::model::write::Version>,
-        events: &[crate::model::shared::event::ThreadEvent],
+        events: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+    ) -> Result<(), crate::port::ThreadRepositoryError> {
+        todo!()
+    }
+
+    async fn store_all(
+        &self,
+        _streams: &[(
+            Option<crate::model::write::Version>,
+            &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+        )],
     ) -> Result<(), crate::port::ThreadRepositoryError> {
         todo!()
     }
@@ -1,14 +1,35 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     sync::{Arc, Mutex},
 };
 
 use crate::model::read::ThreadWithoutMessages;
 
 struct InMemoryStoreInner {
+    boards: BTreeMap<crate::model::shared::id::BoardId, crate::model::write::Board>,
+    /// The posted message index: the messages posted with each content fingerprint
+    posted:
+        BTreeMap<crate::model::write::ContentFingerprint, Vec<crate::model::write::PostedMessage>>,
+    rate_limits: BTreeMap<
+        (
+            crate::model::write::RateLimitAction,
+            crate::model::write::ClientKey,
+        ),
+        crate::model::write::RateLimitWindow,
+    >,
     read: BTreeMap<crate::model::shared::id::ThreadId, crate::model::read::Thread>,
-    write:
-        BTreeMap<crate::model::shared::id::ThreadId, Vec<crate::model::shared::event::ThreadEvent>>,
+    /// The latest snapshots of the write model
+    snapshots: BTreeMap<
+        crate::model::shared::id::ThreadId,
+        crate::model::shared::thread_snapshot::StoredThreadSnapshot,
+    >,
+    /// The tag index: the ids of the threads with each tag
+    tags: BTreeMap<String, BTreeSet<crate::model::shared::id::ThreadId>>,
+    /// The events as they are stored, so that they are upcasted when loaded like in the other stores
+    write: BTreeMap<
+        crate::model::shared::id::ThreadId,
+        Vec<crate::model::shared::event_schema::StoredThreadEvent>,
+    >,
 }
 
 #[derive(Clone)]
@@ -16,8 +37,14 @@ pub struct InMemoryStore(Arc<Mutex<InMemoryStoreInner>>);
 
 impl InMemoryStore {
     pub fn new() -> Self {
+        let default_board = crate::model::write::Board::default_board();
         InMemoryStore(Arc::new(Mutex::new(InMemoryStoreInner {
+            boards: BTreeMap::from([(default_board.id().clone(), default_board)]),
+            posted: BTreeMap::new(),
+            rate_limits: BTreeMap::new(),
             read: BTreeMap::new(),
+            snapshots: BTreeMap::new(),
+            tags: BTreeMap::new(),
             write: BTreeMap::new(),
         })))
     }
@@ -25,6 +52,133 @@ impl InMemoryStore {
 
 impl crate::store::Store for InMemoryStore {}
 
+fn deserialize_events(
+    stored_events: &[crate::model::shared::event_schema::StoredThreadEvent],
+) -> Result<
+    Vec<crate::model::shared::event::ThreadEvent>,
+    crate::model::shared::event_schema::EventSchemaError,
+> {
+    stored_events
+        .iter()
+        .map(crate::model::shared::event_schema::StoredThreadEvent::deserialize)
+        .collect()
+}
+
+fn board_to_read_model(board: &crate::model::write::Board) -> crate::model::read::Board {
+    crate::model::read::Board {
+        description: board.description().to_owned(),
+        id: board.id().to_string(),
+        max_content_length: board.max_content_length(),
+        max_threads: board.max_threads(),
+        name: board.name().to_owned(),
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BlobReferenceReader for InMemoryStore {
+    async fn is_blob_referenced(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<bool, crate::port::BlobReferenceReaderError> {
+        let store = self.0.lock().unwrap();
+        let hash = hash.to_string();
+        // the attachments of the deleted messages are removed from the read model
+        Ok(store
+            .read
+            .values()
+            .flat_map(|thread| &thread.messages)
+            .flat_map(|message| &message.attachments)
+            .any(|attachment| attachment.hash == hash || attachment.thumbnail_hash == hash))
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BoardReader for InMemoryStore {
+    async fn get_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::read::Board>, crate::port::BoardReaderError> {
+        let store = self.0.lock().unwrap();
+        Ok(store.boards.get(id).map(board_to_read_model))
+    }
+
+    async fn list_boards(
+        &self,
+    ) -> Result<Vec<crate::model::read::Board>, crate::port::BoardReaderError> {
+        let store = self.0.lock().unwrap();
+        Ok(store.boards.values().map(board_to_read_model).collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BoardRepository for InMemoryStore {
+    async fn find_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::write::Board>, crate::port::BoardRepositoryError> {
+        let store = self.0.lock().unwrap();
+        Ok(store.boards.get(id).cloned())
+    }
+
+    async fn store_board(
+        &self,
+        board: &crate::model::write::Board,
+    ) -> Result<(), crate::port::BoardRepositoryError> {
+        let mut store = self.0.lock().unwrap();
+        if store.boards.contains_key(board.id()) {
+            return Err(crate::port::BoardRepositoryError::AlreadyExists(
+                board.id().clone(),
+            ));
+        }
+        store.boards.insert(board.id().clone(), board.clone());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::PostedMessageReader for InMemoryStore {
+    async fn list_posted_messages(
+        &self,
+        fingerprint: &crate::model::write::ContentFingerprint,
+        since: crate::utils::date_time::DateTime,
+    ) -> Result<Vec<crate::model::write::PostedMessage>, crate::port::PostedMessageReaderError>
+    {
+        let store = self.0.lock().unwrap();
+        Ok(store
+            .posted
+            .get(fingerprint)
+            .into_iter()
+            .flatten()
+            .filter(|it| it.at >= since)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::RateLimiter for InMemoryStore {
+    async fn consume_rate_limit(
+        &self,
+        action: crate::model::write::RateLimitAction,
+        client: &crate::model::write::ClientKey,
+        limit: &crate::model::write::RateLimit,
+    ) -> Result<(), crate::port::RateLimiterError> {
+        let mut store = self
+            .0
+            .lock()
+            .map_err(|_| crate::port::RateLimiterError::InternalError("lock poisoned".into()))?;
+        let key = (action, client.clone());
+        let window = limit
+            .consume(
+                store.rate_limits.get(&key).cloned(),
+                crate::utils::date_time::DateTime::now(),
+            )
+            .map_err(crate::port::RateLimiterError::Exceeded)?;
+        store.rate_limits.insert(key, window);
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::port::ThreadReader for InMemoryStore {
     async fn get_thread(
@@ -35,17 +189,49 @@ impl crate::port::ThreadReader for InMemoryStore {
         Ok(store.read.get(id).cloned())
     }
 
+    async fn list_tags(
+        &self,
+    ) -> Result<Vec<crate::model::read::ThreadTag>, crate::port::ThreadReaderError> {
+        let store = self.0.lock().unwrap();
+        Ok(store
+            .tags
+            .iter()
+            .map(|(name, thread_ids)| crate::model::read::ThreadTag {
+                count: u32::try_from(thread_ids.len()).expect("thread count to fit in u32"),
+                name: name.clone(),
+            })
+            .collect())
+    }
+
     async fn list_threads(
         &self,
+        board_id: Option<&crate::model::shared::id::BoardId>,
+        tag: Option<&crate::model::write::ThreadTag>,
     ) -> Result<Vec<crate::model::read::ThreadWithoutMessages>, crate::port::ThreadReaderError>
     {
         let store = self.0.lock().unwrap();
-        Ok(store
+        let tagged = tag.map(|tag| store.tags.get(&tag.to_string()));
+        let mut threads = store
             .read
-            .values()
+            .iter()
+            .filter(|(thread_id, _)| {
+                tagged.is_none_or(|thread_ids| {
+                    thread_ids.is_some_and(|thread_ids| thread_ids.contains(thread_id))
+                })
+            })
+            .map(|(_, thread)| thread)
+            .filter(|thread| {
+                board_id.is_none_or(|board_id| thread.board_id == board_id.to_string())
+            })
             .cloned()
             .map(ThreadWithoutMessages::from)
-            .collect())
+            .collect::<Vec<ThreadWithoutMessages>>();
+        threads.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| b.bumped_at.cmp(&a.bumped_at))
+        });
+        Ok(threads)
     }
 }
 
@@ -56,69 +242,205 @@ impl crate::port::ThreadRepository for InMemoryStore {
         id: &crate::model::shared::id::ThreadId,
     ) -> Result<Option<crate::model::write::Thread>, crate::port::ThreadRepositoryError> {
         let store = self.0.lock().unwrap();
-        Ok(store
-            .write
-            .get(id)
-            .map(|events| crate::model::write::Thread::replay(events)))
+        find_in(&store, id)
     }
 
     async fn store(
         &self,
         version: Option<crate::model::write::Version>,
-        events: &[crate::model::shared::event::ThreadEvent],
+        envelopes: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
     ) -> Result<(), crate::port::ThreadRepositoryError> {
         let mut store = self.0.lock().unwrap();
-        if events.is_empty() {
-            return Ok(());
+        store_events(&mut store, version, envelopes)
+    }
+
+    async fn store_all(
+        &self,
+        streams: &[(
+            Option<crate::model::write::Version>,
+            &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+        )],
+    ) -> Result<(), crate::port::ThreadRepositoryError> {
+        let mut store = self.0.lock().unwrap();
+        // all versions are checked before any stream is changed, like a transaction
+        for (version, envelopes) in streams {
+            if let Some(envelope) = envelopes.first() {
+                check_version(&store, envelope.event.thread_id(), *version)?;
+            }
         }
-        let thread_id = events[0].thread_id();
-
-        match version {
-            None => match store.write.get_mut(&thread_id) {
-                Some(stored_events) => {
-                    let stored_version = stored_events
-                        .last()
-                        .map(|last_event| last_event.version())
-                        .expect("stored_events not to be empty");
-                    return Err(crate::port::ThreadRepositoryError::VersionMismatch {
-                        actual: stored_version,
-                        expected: version,
-                    });
-                }
-                None => {
-                    store.write.insert(thread_id.clone(), events.to_vec());
-                }
-            },
-            Some(version) => match store.write.get_mut(&thread_id) {
-                Some(stored_events) => {
-                    let stored_version = stored_events
-                        .last()
-                        .map(|last_event| last_event.version())
-                        .expect("stored_events not to be empty");
-                    if stored_version != version {
-                        return Err(crate::port::ThreadRepositoryError::VersionMismatch {
-                            actual: stored_version,
-                            expected: Some(version),
-                        });
-                    }
-                    stored_events.extend_from_slice(events);
-                }
-                None => return Err(crate::port::ThreadRepositoryError::NotFound(thread_id)),
-            },
+        for (version, envelopes) in streams {
+            store_events(&mut store, *version, envelopes)?;
         }
+        Ok(())
+    }
+}
+
+/// Loads the thread from the latest snapshot and the events after it
+fn find_in(
+    store: &InMemoryStoreInner,
+    id: &crate::model::shared::id::ThreadId,
+) -> Result<Option<crate::model::write::Thread>, crate::port::ThreadRepositoryError> {
+    let Some(stored_events) = store.write.get(id) else {
+        return Ok(None);
+    };
+
+    let snapshot = store
+        .snapshots
+        .get(id)
+        .and_then(|stored| super::restore_snapshot(id, stored));
+    // the versions of the events start at 1 without gaps
+    let tail = snapshot
+        .as_ref()
+        .and_then(|it| usize::try_from(u32::from(it.version())).ok())
+        .and_then(|index| stored_events.get(index..))
+        .unwrap_or(stored_events);
+    let events = deserialize_events(tail)
+        .map_err(|e| crate::port::ThreadRepositoryError::InternalError(e.into()))?;
+    let thread = match snapshot {
+        Some(snapshot) => snapshot.replay_from(&events),
+        None => crate::model::write::Thread::replay(&events),
+    }
+    .map_err(|e| {
+        tracing::error!(thread_id = %id, error = %e, "thread events cannot be replayed");
+        crate::port::ThreadRepositoryError::Corrupted {
+            source: e,
+            thread_id: id.clone(),
+        }
+    })?;
+    Ok(Some(thread))
+}
+
+/// Fails unless the thread is stored at `version`, or is not stored yet if `version` is `None`
+fn check_version(
+    store: &InMemoryStoreInner,
+    thread_id: &crate::model::shared::id::ThreadId,
+    version: Option<crate::model::write::Version>,
+) -> Result<(), crate::port::ThreadRepositoryError> {
+    let stored_version = store
+        .write
+        .get(thread_id)
+        .map(|stored_events| {
+            stored_events
+                .last()
+                .expect("stored_events not to be empty")
+                .deserialize()
+                .map(|event| event.version())
+                .map_err(|e| crate::port::ThreadRepositoryError::InternalError(e.into()))
+        })
+        .transpose()?;
+    match (version, stored_version) {
+        (None, None) => Ok(()),
+        (Some(_), None) => Err(crate::port::ThreadRepositoryError::NotFound(
+            thread_id.clone(),
+        )),
+        (expected, Some(actual)) if expected == Some(actual) => Ok(()),
+        (expected, Some(actual)) => {
+            Err(crate::port::ThreadRepositoryError::VersionMismatch { actual, expected })
+        }
+    }
+}
+
+/// Appends the events to the stream of the thread and updates the read model
+fn store_events(
+    store: &mut InMemoryStoreInner,
+    version: Option<crate::model::write::Version>,
+    envelopes: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+) -> Result<(), crate::port::ThreadRepositoryError> {
+    if envelopes.is_empty() {
+        return Ok(());
+    }
+    // the metadata is not kept in memory
+    let events = envelopes
+        .iter()
+        .map(|envelope| envelope.event.clone())
+        .collect::<Vec<crate::model::shared::event::ThreadEvent>>();
+    let thread_id = events[0].thread_id().clone();
+    let new_stored_events = events
+        .iter()
+        .map(crate::model::shared::event_schema::StoredThreadEvent::serialize)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| crate::port::ThreadRepositoryError::InternalError(e.into()))?;
 
-        match store.read.get_mut(&thread_id) {
-            Some(thread) => {
-                for event in events {
-                    thread.apply(event.clone());
+    check_version(store, &thread_id, version)?;
+    store
+        .write
+        .entry(thread_id.clone())
+        .or_default()
+        .extend(new_stored_events);
+
+    let last_version = events.last().expect("events not to be empty").version();
+    if crate::model::shared::thread_snapshot::ThreadSnapshot::is_due(version, last_version) {
+        let thread = find_in(store, &thread_id)?
+            .ok_or_else(|| crate::port::ThreadRepositoryError::NotFound(thread_id.clone()))?;
+        let stored = crate::model::shared::thread_snapshot::StoredThreadSnapshot::serialize(
+            &thread.snapshot(),
+        )
+        .map_err(|e| crate::port::ThreadRepositoryError::InternalError(e.into()))?;
+        store.snapshots.insert(thread_id.clone(), stored);
+    }
+
+    for event in &events {
+        let posted = match event {
+            crate::model::shared::event::ThreadEvent::Created(event) => event
+                .fingerprint
+                .as_ref()
+                .map(|fingerprint| (fingerprint, event.at, &event.client_key)),
+            crate::model::shared::event::ThreadEvent::Replied(event) => event
+                .fingerprint
+                .as_ref()
+                .map(|fingerprint| (fingerprint, event.at, &event.client_key)),
+            _ => None,
+        };
+        if let Some((fingerprint, at, client_key)) = posted {
+            store.posted.entry(fingerprint.clone()).or_default().push(
+                crate::model::write::PostedMessage {
+                    at,
+                    client_key: client_key.clone(),
+                    thread_id: thread_id.clone(),
+                },
+            );
+        }
+
+        match event {
+            crate::model::shared::event::ThreadEvent::Created(event) => {
+                for tag in &event.tags {
+                    store
+                        .tags
+                        .entry(tag.clone())
+                        .or_default()
+                        .insert(thread_id.clone());
                 }
             }
-            None => {
-                let thread = crate::model::read::Thread::replay(events.to_vec());
-                store.read.insert(thread_id, thread);
+            crate::model::shared::event::ThreadEvent::Tagged(event) => {
+                store
+                    .tags
+                    .entry(event.tag.clone())
+                    .or_default()
+                    .insert(thread_id.clone());
+            }
+            crate::model::shared::event::ThreadEvent::Untagged(event) => {
+                if let Some(thread_ids) = store.tags.get_mut(&event.tag) {
+                    thread_ids.remove(&thread_id);
+                    if thread_ids.is_empty() {
+                        store.tags.remove(&event.tag);
+                    }
+                }
             }
+            _ => {}
         }
-
-        Ok(())
     }
+
+    let replayed = match store.read.get_mut(&thread_id) {
+        Some(thread) => events.into_iter().try_for_each(|event| thread.apply(event)),
+        None => crate::model::read::Thread::replay(events).map(|thread| {
+            store.read.insert(thread_id.clone(), thread);
+        }),
+    };
+    replayed.map_err(|e| {
+        tracing::error!(thread_id = %thread_id, error = %e, "thread events cannot be replayed");
+        crate::port::ThreadRepositoryError::Corrupted {
+            source: e,
+            thread_id,
+        }
+    })
 }
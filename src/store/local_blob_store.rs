@@ -0,0 +1,99 @@
+/// A blob store that keeps each blob in a file named by its hash
+///
+/// The files are sharded into subdirectories by the first two characters of the hash
+/// (e.g. `{root}/e3/e3b0c442...`).
+#[derive(Clone)]
+pub struct LocalBlobStore {
+    root: std::path::PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, hash: &crate::model::shared::id::BlobHash) -> std::path::PathBuf {
+        let s = hash.to_string();
+        self.root.join(&s[..2]).join(s)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum LocalBlobStoreError {
+    #[error("get blob read")]
+    GetBlobRead(#[source] std::io::Error),
+    #[error("put blob create dir")]
+    PutBlobCreateDir(#[source] std::io::Error),
+    #[error("put blob rename")]
+    PutBlobRename(#[source] std::io::Error),
+    #[error("put blob write")]
+    PutBlobWrite(#[source] std::io::Error),
+}
+
+impl From<LocalBlobStoreError> for crate::port::BlobStoreError {
+    fn from(err: LocalBlobStoreError) -> Self {
+        Self(err.into())
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BlobStore for LocalBlobStore {
+    async fn get_blob(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<Option<Vec<u8>>, crate::port::BlobStoreError> {
+        match tokio::fs::read(self.path(hash)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(LocalBlobStoreError::GetBlobRead(e))?,
+        }
+    }
+
+    async fn put_blob(
+        &self,
+        bytes: &[u8],
+    ) -> Result<crate::model::shared::id::BlobHash, crate::port::BlobStoreError> {
+        let hash = crate::model::shared::id::BlobHash::of(bytes);
+        let path = self.path(&hash);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(hash);
+        }
+        let dir = path.parent().expect("blob path to have a parent");
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(LocalBlobStoreError::PutBlobCreateDir)?;
+        // write to a temporary file first so that a blob is never read half-written
+        let temp_path = dir.join(format!(".{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&temp_path, bytes)
+            .await
+            .map_err(LocalBlobStoreError::PutBlobWrite)?;
+        tokio::fs::rename(&temp_path, &path)
+            .await
+            .map_err(LocalBlobStoreError::PutBlobRename)?;
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::port::BlobStore as _;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_and_put() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = LocalBlobStore::new(dir.path());
+
+        let hash = crate::model::shared::id::BlobHash::of(b"hello");
+        assert_eq!(store.get_blob(&hash).await?, None);
+        assert_eq!(store.put_blob(b"hello").await?, hash);
+        assert_eq!(store.get_blob(&hash).await?, Some(b"hello".to_vec()));
+        // storing the same content again is a no-op
+        assert_eq!(store.put_blob(b"hello").await?, hash);
+        let s = hash.to_string();
+        assert!(dir.path().join(&s[..2]).join(&s).is_file());
+        assert_eq!(std::fs::read_dir(dir.path().join(&s[..2]))?.count(), 1);
+        Ok(())
+    }
+}
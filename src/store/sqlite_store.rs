@@ -1,79 +1,319 @@
+use std::str::FromStr as _;
+
 use sqlx::Row as _;
 
 #[derive(Clone)]
 pub struct SqliteStore(sqlx::SqlitePool);
 
+/// The migrations of the schema, applied in order and counted in `PRAGMA user_version`
+///
+/// A released migration must never be changed. Add a new one instead.
+const MIGRATIONS: &[&str] = &[
+    include_str!("sqlite_store/migrations/0001_baseline.sql"),
+    include_str!("sqlite_store/migrations/0002_thread_events_data.sql"),
+    include_str!("sqlite_store/migrations/0003_thread_titles.sql"),
+    include_str!("sqlite_store/migrations/0004_message_revisions.sql"),
+    include_str!("sqlite_store/migrations/0005_message_deletions.sql"),
+    include_str!("sqlite_store/migrations/0006_thread_closed.sql"),
+    include_str!("sqlite_store/migrations/0007_thread_continuations.sql"),
+    include_str!("sqlite_store/migrations/0008_poster_names.sql"),
+    include_str!("sqlite_store/migrations/0009_message_anchors.sql"),
+    include_str!("sqlite_store/migrations/0010_message_reactions.sql"),
+    include_str!("sqlite_store/migrations/0011_boards.sql"),
+    include_str!("sqlite_store/migrations/0012_thread_bumped_at.sql"),
+    include_str!("sqlite_store/migrations/0013_thread_pinned.sql"),
+    include_str!("sqlite_store/migrations/0014_thread_tags.sql"),
+    include_str!("sqlite_store/migrations/0015_polls.sql"),
+    include_str!("sqlite_store/migrations/0016_message_attachments.sql"),
+    include_str!("sqlite_store/migrations/0017_rate_limits.sql"),
+    include_str!("sqlite_store/migrations/0018_thread_events_fingerprint.sql"),
+    include_str!("sqlite_store/migrations/0019_thread_events_schema_version.sql"),
+    include_str!("sqlite_store/migrations/0020_thread_events_metadata.sql"),
+    include_str!("sqlite_store/migrations/0021_thread_snapshots.sql"),
+    include_str!("sqlite_store/migrations/0022_default_board_max_content_length.sql"),
+];
+
 impl SqliteStore {
     pub async fn new() -> Self {
+        Self::connect("sqlite:./bbbs.sqlite?mode=rwc").await
+    }
+
+    async fn connect(url: &str) -> Self {
         let pool = sqlx::sqlite::SqlitePoolOptions::new()
-            .connect("sqlite:./bbbs.sqlite?mode=rwc")
+            .connect(url)
             .await
             .unwrap();
 
-        // write
-        sqlx::query(
-            r#"
-CREATE TABLE IF NOT EXISTS thread_event_streams (
-    id      TEXT    NOT NULL    PRIMARY KEY,
-    version INTEGER NOT NULL
-)"#,
-        )
-        .execute(&pool)
+        migrate(&pool).await.unwrap();
+
+        let default_board = crate::model::write::Board::default_board();
+        sqlx::query(include_str!("sqlite_store/insert_boards.sql"))
+            .bind(default_board.description())
+            .bind(default_board.id().to_string())
+            .bind(default_board.max_content_length())
+            .bind(default_board.max_threads())
+            .bind(default_board.name())
+            .execute(&pool)
+            .await
+            .unwrap();
+        Self(pool)
+    }
+}
+
+/// Applies the migrations that have not been applied yet
+async fn migrate(pool: &sqlx::SqlitePool) -> Result<(), SqliteStoreError> {
+    let mut tx = pool
+        .begin()
         .await
-        .unwrap();
-        sqlx::query(
-            r#"
-CREATE TABLE IF NOT EXISTS thread_events (
-    at          INTEGER NOT NULL,
-    content     TEXT    NOT NULL,
-    id          TEXT    NOT NULL   PRIMARY KEY,
-    kind        TEXT    NOT NULL,
-    thread_id   TEXT    NOT NULL,
-    version     INTEGER NOT NULL,
-    UNIQUE (thread_id, version)
-)"#,
-        )
-        .execute(&pool)
+        .map_err(SqliteStoreError::MigrateBeginTransaction)?;
+    let user_version = sqlx::query_scalar::<_, i64>("PRAGMA user_version")
+        .fetch_one(&mut *tx)
         .await
-        .unwrap();
+        .map_err(SqliteStoreError::MigrateSelectUserVersion)?;
+    let applied = usize::try_from(user_version).unwrap_or_default();
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        sqlx::raw_sql(migration)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| SqliteStoreError::MigrateApply(index + 1, e))?;
+        // PRAGMA does not accept bound parameters
+        sqlx::raw_sql(&format!("PRAGMA user_version = {}", index + 1))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| SqliteStoreError::MigrateApply(index + 1, e))?;
+    }
+    tx.commit().await.map_err(SqliteStoreError::MigrateCommit)?;
+    Ok(())
+}
 
-        // read
-        sqlx::query(
-            r#"
-CREATE TABLE IF NOT EXISTS threads (
-    created_at                  TEXT    NOT NULL,
-    first_message_content       TEXT    NOT NULL,
-    first_message_created_at    TEXT    NOT NULL,
-    first_message_number        INTEGER NOT NULL,
-    id                          TEXT    NOT NULL   PRIMARY KEY,
-    last_message_content        TEXT    NOT NULL,
-    last_message_created_at     TEXT    NOT NULL,
-    last_message_number         INTEGER NOT NULL,
-    replies_count               INTEGER NOT NULL,
-    version                     INTEGER NOT NULL
-)"#,
-        )
-        .execute(&pool)
+impl crate::store::Store for SqliteStore {}
+
+fn board_from_row(
+    row: &sqlx::sqlite::SqliteRow,
+) -> Result<crate::model::write::Board, SqliteStoreError> {
+    let id = crate::model::shared::id::BoardId::from_str(row.get("id"))
+        .map_err(SqliteStoreError::BoardFromRowInvalidId)?;
+    crate::model::write::Board::new(
+        id,
+        row.get("name"),
+        row.get("description"),
+        row.try_get("max_content_length")
+            .map_err(SqliteStoreError::BoardFromRowDecode)?,
+        row.try_get("max_threads")
+            .map_err(SqliteStoreError::BoardFromRowDecode)?,
+    )
+    .map_err(SqliteStoreError::BoardFromRowInvalidBoard)
+}
+
+fn stored_thread_event_from_row(
+    row: &sqlx::sqlite::SqliteRow,
+) -> crate::model::shared::event_schema::StoredThreadEvent {
+    crate::model::shared::event_schema::StoredThreadEvent {
+        data: row.get("data"),
+        kind: row.get("kind"),
+        schema_version: row.get("schema_version"),
+    }
+}
+
+fn stored_thread_snapshot_from_row(
+    row: &sqlx::sqlite::SqliteRow,
+) -> crate::model::shared::thread_snapshot::StoredThreadSnapshot {
+    crate::model::shared::thread_snapshot::StoredThreadSnapshot {
+        data: row.get("data"),
+        format_version: row.get("format_version"),
+        version: row.get("version"),
+    }
+}
+
+fn board_to_read_model(board: &crate::model::write::Board) -> crate::model::read::Board {
+    crate::model::read::Board {
+        description: board.description().to_owned(),
+        id: board.id().to_string(),
+        max_content_length: board.max_content_length(),
+        max_threads: board.max_threads(),
+        name: board.name().to_owned(),
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BlobReferenceReader for SqliteStore {
+    async fn is_blob_referenced(
+        &self,
+        hash: &crate::model::shared::id::BlobHash,
+    ) -> Result<bool, crate::port::BlobReferenceReaderError> {
+        // the attachments of the deleted messages are deleted from the read model
+        let row = sqlx::query(include_str!(
+            "sqlite_store/select_message_attachments_count.sql"
+        ))
+        .bind(hash.to_string())
+        .bind(hash.to_string())
+        .fetch_one(&self.0)
         .await
-        .unwrap();
-        sqlx::query(
-            r#"
-CREATE TABLE IF NOT EXISTS messages (
-    content     TEXT    NOT NULL,
-    created_at  TEXT    NOT NULL,
-    thread_id   TEXT    NOT NULL,
-    number      INTEGER NOT NULL,
-    PRIMARY KEY (thread_id, number)
-)"#,
-        )
-        .execute(&pool)
+        .map_err(SqliteStoreError::IsBlobReferencedSelectMessageAttachments)?;
+        let count = row
+            .try_get::<i64, _>("count")
+            .map_err(SqliteStoreError::IsBlobReferencedDecode)?;
+        Ok(count > 0)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BoardReader for SqliteStore {
+    async fn get_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::read::Board>, crate::port::BoardReaderError> {
+        let row = sqlx::query(include_str!("sqlite_store/select_boards.sql"))
+            .bind(id.to_string())
+            .fetch_optional(&self.0)
+            .await
+            .map_err(SqliteStoreError::GetBoardSelectBoards)?;
+        Ok(row
+            .as_ref()
+            .map(board_from_row)
+            .transpose()?
+            .as_ref()
+            .map(board_to_read_model))
+    }
+
+    async fn list_boards(
+        &self,
+    ) -> Result<Vec<crate::model::read::Board>, crate::port::BoardReaderError> {
+        let rows = sqlx::query(include_str!("sqlite_store/select_boards_all.sql"))
+            .fetch_all(&self.0)
+            .await
+            .map_err(SqliteStoreError::ListBoardsSelectBoards)?;
+        let mut boards = vec![];
+        for row in rows {
+            boards.push(board_to_read_model(&board_from_row(&row)?));
+        }
+        Ok(boards)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::BoardRepository for SqliteStore {
+    async fn find_board(
+        &self,
+        id: &crate::model::shared::id::BoardId,
+    ) -> Result<Option<crate::model::write::Board>, crate::port::BoardRepositoryError> {
+        let row = sqlx::query(include_str!("sqlite_store/select_boards.sql"))
+            .bind(id.to_string())
+            .fetch_optional(&self.0)
+            .await
+            .map_err(SqliteStoreError::FindBoardSelectBoards)?;
+        Ok(row.as_ref().map(board_from_row).transpose()?)
+    }
+
+    async fn store_board(
+        &self,
+        board: &crate::model::write::Board,
+    ) -> Result<(), crate::port::BoardRepositoryError> {
+        let result = sqlx::query(include_str!("sqlite_store/insert_boards.sql"))
+            .bind(board.description())
+            .bind(board.id().to_string())
+            .bind(board.max_content_length())
+            .bind(board.max_threads())
+            .bind(board.name())
+            .execute(&self.0)
+            .await
+            .map_err(SqliteStoreError::StoreBoardInsertBoards)?;
+        if result.rows_affected() == 0 {
+            return Err(crate::port::BoardRepositoryError::AlreadyExists(
+                board.id().clone(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::port::PostedMessageReader for SqliteStore {
+    async fn list_posted_messages(
+        &self,
+        fingerprint: &crate::model::write::ContentFingerprint,
+        since: crate::utils::date_time::DateTime,
+    ) -> Result<Vec<crate::model::write::PostedMessage>, crate::port::PostedMessageReaderError>
+    {
+        let rows = sqlx::query(include_str!(
+            "sqlite_store/select_thread_events_fingerprint.sql"
+        ))
+        .bind(fingerprint.to_string())
+        .bind(since.to_string())
+        .fetch_all(&self.0)
         .await
-        .unwrap();
-        Self(pool)
+        .map_err(SqliteStoreError::ListPostedMessagesSelectEvents)?;
+        let mut posted = vec![];
+        for row in rows {
+            let event = stored_thread_event_from_row(&row)
+                .deserialize()
+                .map_err(SqliteStoreError::ListPostedMessagesDeserializeEvent)?;
+            let (at, client_key) = match &event {
+                crate::model::shared::event::ThreadEvent::Created(event) => {
+                    (&event.at, &event.client_key)
+                }
+                crate::model::shared::event::ThreadEvent::Replied(event) => {
+                    (&event.at, &event.client_key)
+                }
+                _ => continue,
+            };
+            posted.push(crate::model::write::PostedMessage {
+                at: *at,
+                client_key: client_key.clone(),
+                thread_id: event.thread_id().clone(),
+            });
+        }
+        Ok(posted)
     }
 }
 
-impl crate::store::Store for SqliteStore {}
+#[async_trait::async_trait]
+impl crate::port::RateLimiter for SqliteStore {
+    async fn consume_rate_limit(
+        &self,
+        action: crate::model::write::RateLimitAction,
+        client: &crate::model::write::ClientKey,
+        limit: &crate::model::write::RateLimit,
+    ) -> Result<(), crate::port::RateLimiterError> {
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .map_err(SqliteStoreError::ConsumeRateLimitBeginTransaction)?;
+        let row = sqlx::query(include_str!("sqlite_store/select_rate_limits.sql"))
+            .bind(action.to_string())
+            .bind(client.to_string())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::ConsumeRateLimitSelectRateLimits)?;
+        let window = row
+            .map(|row| {
+                Ok(crate::model::write::RateLimitWindow {
+                    count: row.try_get("count")?,
+                    started_at: crate::utils::date_time::DateTime::from_unix_timestamp_millis(
+                        row.get("started_at"),
+                    ),
+                })
+            })
+            .transpose()
+            .map_err(SqliteStoreError::ConsumeRateLimitDecode)?;
+        let window = limit
+            .consume(window, crate::utils::date_time::DateTime::now())
+            .map_err(crate::port::RateLimiterError::Exceeded)?;
+        sqlx::query(include_str!("sqlite_store/insert_rate_limits.sql"))
+            .bind(action.to_string())
+            .bind(client.to_string())
+            .bind(i64::from(window.count))
+            .bind(window.started_at.to_unix_timestamp_millis())
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::ConsumeRateLimitInsertRateLimits)?;
+        tx.commit()
+            .await
+            .map_err(SqliteStoreError::ConsumeRateLimitCommit)?;
+        Ok(())
+    }
+}
 
 #[async_trait::async_trait]
 impl crate::port::ThreadReader for SqliteStore {
@@ -91,14 +331,143 @@ impl crate::port::ThreadReader for SqliteStore {
             .fetch_all(&mut *tx)
             .await
             .map_err(SqliteStoreError::GetThreadSelectMessages)?;
-        let messages = rows
+        let mut messages = rows
             .into_iter()
-            .map(|row| crate::model::read::Message {
-                content: row.get("content"),
-                created_at: row.get("created_at"),
-                number: row.get::<i64, _>("number") as u16,
+            .map(|row| {
+                Ok(crate::model::read::Message {
+                    anchors: vec![],
+                    attachments: vec![],
+                    content: row.get("content"),
+                    created_at: row.get("created_at"),
+                    deleted_at: row.get("deleted_at"),
+                    deletion_reason: row.get("deletion_reason"),
+                    edited_at: row.get("edited_at"),
+                    name: row.get("name"),
+                    number: row.try_get("number")?,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: row.get("trip"),
+                })
             })
-            .collect::<Vec<crate::model::read::Message>>();
+            .collect::<Result<Vec<crate::model::read::Message>, sqlx::Error>>()
+            .map_err(SqliteStoreError::GetThreadDecode)?;
+        let rows = sqlx::query(include_str!("sqlite_store/select_message_revisions.sql"))
+            .bind(id.to_string())
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::GetThreadSelectMessageRevisions)?;
+        for row in rows {
+            let number = row
+                .try_get::<u16, _>("number")
+                .map_err(SqliteStoreError::GetThreadDecode)?;
+            if let Some(message) = messages.iter_mut().find(|it| it.number == number) {
+                message.revisions.push(crate::model::read::MessageRevision {
+                    content: row.get("content"),
+                    created_at: row.get("created_at"),
+                });
+            }
+        }
+        let rows = sqlx::query(include_str!("sqlite_store/select_message_anchors.sql"))
+            .bind(id.to_string())
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::GetThreadSelectMessageAnchors)?;
+        for row in rows {
+            let anchor = row
+                .try_get::<u16, _>("anchor")
+                .map_err(SqliteStoreError::GetThreadDecode)?;
+            let number = row
+                .try_get::<u16, _>("number")
+                .map_err(SqliteStoreError::GetThreadDecode)?;
+            if let Some(message) = messages.iter_mut().find(|it| it.number == number) {
+                message.anchors.push(anchor);
+            }
+            if let Some(message) = messages.iter_mut().find(|it| it.number == anchor) {
+                message.replied_by.push(number);
+            }
+        }
+        for message in messages.iter_mut() {
+            message.replied_by.sort_unstable();
+        }
+        let rows = sqlx::query(include_str!("sqlite_store/select_message_attachments.sql"))
+            .bind(id.to_string())
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::GetThreadSelectMessageAttachments)?;
+        for row in rows {
+            let number = row
+                .try_get::<u16, _>("number")
+                .map_err(SqliteStoreError::GetThreadDecode)?;
+            if let Some(message) = messages.iter_mut().find(|it| it.number == number) {
+                message
+                    .attachments
+                    .push(crate::model::read::MessageAttachment {
+                        hash: row.get("hash"),
+                        mime: row.get("mime"),
+                        size: row
+                            .try_get::<u32, _>("size")
+                            .map_err(SqliteStoreError::GetThreadDecode)?,
+                        thumbnail_hash: row.get("thumbnail_hash"),
+                    });
+            }
+        }
+        let rows = sqlx::query(include_str!("sqlite_store/select_message_reactions.sql"))
+            .bind(id.to_string())
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::GetThreadSelectMessageReactions)?;
+        for row in rows {
+            let number = row
+                .try_get::<u16, _>("number")
+                .map_err(SqliteStoreError::GetThreadDecode)?;
+            if let Some(message) = messages.iter_mut().find(|it| it.number == number) {
+                message.reactions.push(crate::model::read::MessageReaction {
+                    code: row.get("code"),
+                    count: row
+                        .try_get::<u32, _>("count")
+                        .map_err(SqliteStoreError::GetThreadDecode)?,
+                });
+            }
+        }
+        let poll = sqlx::query(include_str!("sqlite_store/select_polls.sql"))
+            .bind(id.to_string())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::GetThreadSelectPolls)?;
+        let poll = match poll {
+            None => None,
+            Some(row) => {
+                let options = sqlx::query(include_str!("sqlite_store/select_poll_options.sql"))
+                    .bind(id.to_string())
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::GetThreadSelectPollOptions)?
+                    .into_iter()
+                    .map(|row| {
+                        Ok(crate::model::read::PollOption {
+                            count: row.try_get("count")?,
+                            label: row.get("label"),
+                        })
+                    })
+                    .collect::<Result<Vec<crate::model::read::PollOption>, sqlx::Error>>()
+                    .map_err(SqliteStoreError::GetThreadDecode)?;
+                Some(crate::model::read::Poll {
+                    closes_at: row.get("closes_at"),
+                    votes_count: options.iter().map(|it| it.count).sum(),
+                    options,
+                    question: row.get("question"),
+                })
+            }
+        };
+        let tags = sqlx::query(include_str!("sqlite_store/select_thread_tags.sql"))
+            .bind(id.to_string())
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::GetThreadSelectThreadTags)?
+            .into_iter()
+            .map(|row| row.get("tag"))
+            .collect::<Vec<String>>();
         let row = sqlx::query(include_str!("sqlite_store/select_threads.sql"))
             .bind(id.to_string())
             .fetch_optional(&mut *tx)
@@ -107,20 +476,56 @@ impl crate::port::ThreadReader for SqliteStore {
         let thread = match row {
             None => None,
             Some(row) => Some(crate::model::read::Thread {
+                board_id: row.get("board_id"),
+                bumped_at: row.get("bumped_at"),
+                closed: row.get("closed"),
                 created_at: row.get("created_at"),
+                filled: row.get("filled"),
                 first_message: crate::model::read::Message {
+                    anchors: vec![],
+                    attachments: vec![],
                     content: row.get("first_message_content"),
                     created_at: row.get("first_message_created_at"),
-                    number: row.get::<i64, _>("first_message_number") as u16,
+                    deleted_at: row.get("first_message_deleted_at"),
+                    deletion_reason: row.get("first_message_deletion_reason"),
+                    edited_at: row.get("first_message_edited_at"),
+                    name: row.get("first_message_name"),
+                    number: row
+                        .try_get::<u16, _>("first_message_number")
+                        .map_err(SqliteStoreError::GetThreadDecode)?,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: row.get("first_message_trip"),
                 },
                 id: row.get("id"),
                 last_message: crate::model::read::Message {
+                    anchors: vec![],
+                    attachments: vec![],
                     content: row.get("last_message_content"),
                     created_at: row.get("last_message_created_at"),
-                    number: row.get::<i64, _>("last_message_number") as u16,
+                    deleted_at: row.get("last_message_deleted_at"),
+                    deletion_reason: row.get("last_message_deletion_reason"),
+                    edited_at: row.get("last_message_edited_at"),
+                    name: row.get("last_message_name"),
+                    number: row
+                        .try_get::<u16, _>("last_message_number")
+                        .map_err(SqliteStoreError::GetThreadDecode)?,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: row.get("last_message_trip"),
                 },
                 messages,
-                replies_count: row.get::<i64, _>("replies_count") as u16,
+                next_thread_id: row.get("next_thread_id"),
+                pinned: row.get("pinned"),
+                poll,
+                previous_thread_id: row.get("previous_thread_id"),
+                replies_count: row
+                    .try_get::<u16, _>("replies_count")
+                    .map_err(SqliteStoreError::GetThreadDecode)?,
+                tags,
+                title: row.get("title"),
                 version: row.get("version"),
             }),
         };
@@ -130,35 +535,106 @@ impl crate::port::ThreadReader for SqliteStore {
         Ok(thread)
     }
 
+    async fn list_tags(
+        &self,
+    ) -> Result<Vec<crate::model::read::ThreadTag>, crate::port::ThreadReaderError> {
+        let rows = sqlx::query(include_str!("sqlite_store/select_thread_tags_count.sql"))
+            .fetch_all(&self.0)
+            .await
+            .map_err(SqliteStoreError::ListTagsSelectThreadTags)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                Ok(crate::model::read::ThreadTag {
+                    count: row.try_get("count")?,
+                    name: row.get("tag"),
+                })
+            })
+            .collect::<Result<Vec<crate::model::read::ThreadTag>, sqlx::Error>>()
+            .map_err(SqliteStoreError::ListTagsDecode)?)
+    }
+
     async fn list_threads(
         &self,
+        board_id: Option<&crate::model::shared::id::BoardId>,
+        tag: Option<&crate::model::write::ThreadTag>,
     ) -> Result<Vec<crate::model::read::ThreadWithoutMessages>, crate::port::ThreadReaderError>
     {
+        let board_id = board_id.map(crate::model::shared::id::BoardId::to_string);
+        let tag = tag.map(crate::model::write::ThreadTag::to_string);
         let mut tx = self
             .0
             .begin()
             .await
             .map_err(SqliteStoreError::ListThreadsBeginTransaction)?;
         let rows = sqlx::query(include_str!("sqlite_store/select_threads_all.sql"))
+            .bind(board_id.clone())
+            .bind(board_id)
+            .bind(tag.clone())
+            .bind(tag)
             .fetch_all(&mut *tx)
             .await
             .map_err(SqliteStoreError::ListThreadsSelectThread)?;
         let mut threads = vec![];
         for row in rows {
             let thread = crate::model::read::ThreadWithoutMessages {
+                board_id: row.get("board_id"),
+                bumped_at: row.get("bumped_at"),
+                closed: row.get("closed"),
                 created_at: row.get("created_at"),
+                filled: row.get("filled"),
                 first_message: crate::model::read::Message {
+                    anchors: vec![],
+                    attachments: vec![],
                     content: row.get("first_message_content"),
                     created_at: row.get("first_message_created_at"),
-                    number: row.get::<i64, _>("first_message_number") as u16,
+                    deleted_at: row.get("first_message_deleted_at"),
+                    deletion_reason: row.get("first_message_deletion_reason"),
+                    edited_at: row.get("first_message_edited_at"),
+                    name: row.get("first_message_name"),
+                    number: row
+                        .try_get::<u16, _>("first_message_number")
+                        .map_err(SqliteStoreError::ListThreadsDecode)?,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: row.get("first_message_trip"),
                 },
                 id: row.get("id"),
                 last_message: crate::model::read::Message {
+                    anchors: vec![],
+                    attachments: vec![],
                     content: row.get("last_message_content"),
                     created_at: row.get("last_message_created_at"),
-                    number: row.get("last_message_number"),
+                    deleted_at: row.get("last_message_deleted_at"),
+                    deletion_reason: row.get("last_message_deletion_reason"),
+                    edited_at: row.get("last_message_edited_at"),
+                    name: row.get("last_message_name"),
+                    number: row
+                        .try_get("last_message_number")
+                        .map_err(SqliteStoreError::ListThreadsDecode)?,
+                    reactions: vec![],
+                    replied_by: vec![],
+                    revisions: vec![],
+                    trip: row.get("last_message_trip"),
                 },
-                replies_count: row.get("replies_count"),
+                next_thread_id: row.get("next_thread_id"),
+                pinned: row.get("pinned"),
+                previous_thread_id: row.get("previous_thread_id"),
+                replies_count: row
+                    .try_get("replies_count")
+                    .map_err(SqliteStoreError::ListThreadsDecode)?,
+                tags: {
+                    let tags = row.get::<Option<String>, _>("tags").unwrap_or_default();
+                    let mut tags = tags
+                        .split(' ')
+                        .filter(|it| !it.is_empty())
+                        .map(str::to_owned)
+                        .collect::<Vec<String>>();
+                    tags.sort();
+                    tags
+                },
+                title: row.get("title"),
                 version: row.get("version"),
             };
             threads.push(thread);
@@ -172,51 +648,206 @@ impl crate::port::ThreadReader for SqliteStore {
 
 #[derive(Debug, thiserror::Error)]
 enum SqliteStoreError {
+    #[error("board from row decode")]
+    BoardFromRowDecode(#[source] sqlx::Error),
+    #[error("board from row invalid board")]
+    BoardFromRowInvalidBoard(#[source] crate::model::write::BoardError),
+    #[error("board from row invalid id")]
+    BoardFromRowInvalidId(#[source] crate::model::shared::id::BoardIdError),
+    #[error("consume rate limit begin transaction")]
+    ConsumeRateLimitBeginTransaction(#[source] sqlx::Error),
+    #[error("consume rate limit commit")]
+    ConsumeRateLimitCommit(#[source] sqlx::Error),
+    #[error("consume rate limit decode")]
+    ConsumeRateLimitDecode(#[source] sqlx::Error),
+    #[error("consume rate limit insert rate limits")]
+    ConsumeRateLimitInsertRateLimits(#[source] sqlx::Error),
+    #[error("consume rate limit select rate limits")]
+    ConsumeRateLimitSelectRateLimits(#[source] sqlx::Error),
+    #[error("find board select boards")]
+    FindBoardSelectBoards(#[source] sqlx::Error),
     #[error("find begin transaction")]
     FindBeginTransaction(#[source] sqlx::Error),
+    #[error("find commit")]
+    FindCommit(#[source] sqlx::Error),
+    #[error("find deserialize event")]
+    FindDeserializeEvent(#[source] crate::model::shared::event_schema::EventSchemaError),
     #[error("find select event streams")]
     FindSelectEventStreams(#[source] sqlx::Error),
     #[error("find select events")]
     FindSelectEvents(#[source] sqlx::Error),
+    #[error("find select snapshots")]
+    FindSelectSnapshots(#[source] sqlx::Error),
+    #[error("get board select boards")]
+    GetBoardSelectBoards(#[source] sqlx::Error),
     #[error("get thread begin transaction")]
     GetThreadBeginTransaction(#[source] sqlx::Error),
+    #[error("get thread decode")]
+    GetThreadDecode(#[source] sqlx::Error),
     #[error("get thread rollback")]
     GetThreadRollback(#[source] sqlx::Error),
+    #[error("get thread select message anchors")]
+    GetThreadSelectMessageAnchors(#[source] sqlx::Error),
+    #[error("get thread select message attachments")]
+    GetThreadSelectMessageAttachments(#[source] sqlx::Error),
+    #[error("get thread select message reactions")]
+    GetThreadSelectMessageReactions(#[source] sqlx::Error),
+    #[error("get thread select message revisions")]
+    GetThreadSelectMessageRevisions(#[source] sqlx::Error),
     #[error("get thread select messages")]
     GetThreadSelectMessages(#[source] sqlx::Error),
     #[error("get thread select thread")]
     GetThreadSelectThread(#[source] sqlx::Error),
+    #[error("get thread select poll options")]
+    GetThreadSelectPollOptions(#[source] sqlx::Error),
+    #[error("is blob referenced decode")]
+    IsBlobReferencedDecode(#[source] sqlx::Error),
+    #[error("is blob referenced select message attachments")]
+    IsBlobReferencedSelectMessageAttachments(#[source] sqlx::Error),
+    #[error("get thread select polls")]
+    GetThreadSelectPolls(#[source] sqlx::Error),
+    #[error("get thread select thread tags")]
+    GetThreadSelectThreadTags(#[source] sqlx::Error),
+    #[error("list boards select boards")]
+    ListBoardsSelectBoards(#[source] sqlx::Error),
+    #[error("list posted messages deserialize event")]
+    ListPostedMessagesDeserializeEvent(
+        #[source] crate::model::shared::event_schema::EventSchemaError,
+    ),
+    #[error("list posted messages select events")]
+    ListPostedMessagesSelectEvents(#[source] sqlx::Error),
+    #[error("list tags decode")]
+    ListTagsDecode(#[source] sqlx::Error),
+    #[error("list tags select thread tags")]
+    ListTagsSelectThreadTags(#[source] sqlx::Error),
     #[error("list threads begin transaction")]
     ListThreadsBeginTransaction(#[source] sqlx::Error),
+    #[error("list threads decode")]
+    ListThreadsDecode(#[source] sqlx::Error),
     #[error("list threads select thread")]
     ListThreadsSelectThread(#[source] sqlx::Error),
     #[error("list threads rollback")]
     ListThreadsRollback(#[source] sqlx::Error),
+    #[error("migrate apply (migration: {0})")]
+    MigrateApply(usize, #[source] sqlx::Error),
+    #[error("migrate begin transaction")]
+    MigrateBeginTransaction(#[source] sqlx::Error),
+    #[error("migrate commit")]
+    MigrateCommit(#[source] sqlx::Error),
+    #[error("migrate select user version")]
+    MigrateSelectUserVersion(#[source] sqlx::Error),
     #[error("store begin transaction")]
     StoreBeginTransaction(#[source] sqlx::Error),
+    #[error("store board insert boards")]
+    StoreBoardInsertBoards(#[source] sqlx::Error),
     #[error("store commit")]
     StoreCommit(#[source] sqlx::Error),
+    #[error("store serialize event")]
+    StoreSerializeEvent(#[source] crate::model::shared::event_schema::EventSchemaError),
+    #[error("store serialize metadata")]
+    StoreSerializeMetadata(#[source] serde_json::Error),
+    #[error("store serialize snapshot")]
+    StoreSerializeSnapshot(#[source] crate::model::shared::thread_snapshot::ThreadSnapshotError),
+    #[error("store insert snapshots")]
+    StoreInsertSnapshots(#[source] sqlx::Error),
     #[error("store insert event streams")]
     StoreInsertEventStreams(#[source] sqlx::Error),
+    #[error("store select event streams")]
+    StoreSelectEventStreams(#[source] sqlx::Error),
     #[error("store update event streams")]
     StoreUpdateEventStreams(#[source] sqlx::Error),
-    #[error(
-        "store update event streams conflict (expected version: {expected_version:?}, thread id: {thread_id})"
-    )]
-    StoreUpdateEventStreamsConflict {
-        expected_version: crate::model::write::Version,
-        thread_id: crate::model::shared::id::ThreadId,
-    },
+    #[error("store update read model decode")]
+    StoreUpdateReadModelDecode(#[source] sqlx::Error),
+    #[error("store update read model delete message anchors")]
+    StoreUpdateReadModelDeleteMessageAnchors(#[source] sqlx::Error),
+    #[error("store update read model delete message attachments")]
+    StoreUpdateReadModelDeleteMessageAttachments(#[source] sqlx::Error),
+    #[error("store update read model delete message reactions")]
+    StoreUpdateReadModelDeleteMessageReactions(#[source] sqlx::Error),
+    #[error("store update read model delete message revisions")]
+    StoreUpdateReadModelDeleteMessageRevisions(#[source] sqlx::Error),
+    #[error("store update read model delete thread tags")]
+    StoreUpdateReadModelDeleteThreadTags(#[source] sqlx::Error),
+    #[error("store update read model insert message anchors")]
+    StoreUpdateReadModelInsertMessageAnchors(#[source] sqlx::Error),
+    #[error("store update read model insert message attachments")]
+    StoreUpdateReadModelInsertMessageAttachments(#[source] sqlx::Error),
+    #[error("store update read model insert message reactions")]
+    StoreUpdateReadModelInsertMessageReactions(#[source] sqlx::Error),
+    #[error("store update read model insert message revisions")]
+    StoreUpdateReadModelInsertMessageRevisions(#[source] sqlx::Error),
     #[error("store update read model insert messages")]
     StoreUpdateReadModelInsertMessages(#[source] sqlx::Error),
+    #[error("store update read model insert poll options")]
+    StoreUpdateReadModelInsertPollOptions(#[source] sqlx::Error),
+    #[error("store update read model insert polls")]
+    StoreUpdateReadModelInsertPolls(#[source] sqlx::Error),
+    #[error("store update read model insert thread tags")]
+    StoreUpdateReadModelInsertThreadTags(#[source] sqlx::Error),
     #[error("store update read model insert threads")]
     StoreUpdateReadModelInsertThreads(#[source] sqlx::Error),
+    #[error("store update read model select threads last message number")]
+    StoreUpdateReadModelSelectThreadsLastMessageNumber(#[source] sqlx::Error),
+    #[error("store update read model update message reactions")]
+    StoreUpdateReadModelUpdateMessageReactions(#[source] sqlx::Error),
+    #[error("store update read model update messages deleted")]
+    StoreUpdateReadModelUpdateMessagesDeleted(#[source] sqlx::Error),
+    #[error("store update read model update messages")]
+    StoreUpdateReadModelUpdateMessages(#[source] sqlx::Error),
+    #[error("store update read model update poll options")]
+    StoreUpdateReadModelUpdatePollOptions(#[source] sqlx::Error),
     #[error("store update read model update threads")]
     StoreUpdateReadModelUpdateThreads(#[source] sqlx::Error),
+    #[error("store update read model update threads closed")]
+    StoreUpdateReadModelUpdateThreadsClosed(#[source] sqlx::Error),
+    #[error("store update read model update threads filled")]
+    StoreUpdateReadModelUpdateThreadsFilled(#[source] sqlx::Error),
+    #[error("store update read model update threads message deleted")]
+    StoreUpdateReadModelUpdateThreadsMessageDeleted(#[source] sqlx::Error),
+    #[error("store update read model update threads message")]
+    StoreUpdateReadModelUpdateThreadsMessage(#[source] sqlx::Error),
+    #[error("store update read model update threads next thread id")]
+    StoreUpdateReadModelUpdateThreadsNextThreadId(#[source] sqlx::Error),
+    #[error("store update read model update threads pinned")]
+    StoreUpdateReadModelUpdateThreadsPinned(#[source] sqlx::Error),
+    #[error("store update read model update threads title")]
+    StoreUpdateReadModelUpdateThreadsTitle(#[source] sqlx::Error),
+    #[error("store update read model update threads version")]
+    StoreUpdateReadModelUpdateThreadsVersion(#[source] sqlx::Error),
     #[error("store insert events")]
     StoreInsertEvents(#[source] sqlx::Error),
 }
 
+impl From<SqliteStoreError> for crate::port::BlobReferenceReaderError {
+    fn from(err: SqliteStoreError) -> Self {
+        Self(err.into())
+    }
+}
+
+impl From<SqliteStoreError> for crate::port::BoardReaderError {
+    fn from(err: SqliteStoreError) -> Self {
+        Self(err.into())
+    }
+}
+
+impl From<SqliteStoreError> for crate::port::BoardRepositoryError {
+    fn from(err: SqliteStoreError) -> Self {
+        Self::InternalError(err.into())
+    }
+}
+
+impl From<SqliteStoreError> for crate::port::PostedMessageReaderError {
+    fn from(err: SqliteStoreError) -> Self {
+        Self(err.into())
+    }
+}
+
+impl From<SqliteStoreError> for crate::port::RateLimiterError {
+    fn from(err: SqliteStoreError) -> Self {
+        Self::InternalError(err.into())
+    }
+}
+
 impl From<SqliteStoreError> for crate::port::ThreadReaderError {
     fn from(err: SqliteStoreError) -> Self {
         Self(err.into())
@@ -241,183 +872,708 @@ impl crate::port::ThreadRepository for SqliteStore {
             .begin()
             .await
             .map_err(SqliteStoreError::FindBeginTransaction)?;
-        let row = sqlx::query(include_str!("sqlite_store/select_thread_event_streams.sql"))
-            .bind(id.to_string())
-            .fetch_optional(&mut *tx)
-            .await
-            .map_err(SqliteStoreError::FindSelectEventStreams)?;
-        match row {
-            None => Ok(None),
-            Some(_) => {
-                let events = sqlx::query(include_str!("sqlite_store/select_thread_events.sql"))
-                    .bind(id.to_string())
-                    .fetch_all(&mut *tx)
-                    .await
-                    .map_err(SqliteStoreError::FindSelectEvents)
-                    .map(|events| {
-                        events
-                            .into_iter()
-                            .map(|row| match row.get("kind") {
-                                "created" => crate::model::shared::event::ThreadEvent::Created(
-                                    crate::model::shared::event::ThreadCreated {
-                                        at: row.get("at"),
-                                        content: row.get("content"),
-                                        id: row.get("id"),
-                                        thread_id: row.get("thread_id"),
-                                        version: row.get("version"),
-                                    },
-                                ),
-                                "replied" => crate::model::shared::event::ThreadEvent::Replied(
-                                    crate::model::shared::event::ThreadReplied {
-                                        at: row.get("at"),
-                                        content: row.get("content"),
-                                        id: row.get("id"),
-                                        thread_id: row.get("thread_id"),
-                                        version: row.get("version"),
-                                    },
-                                ),
-                                _ => unreachable!(
-                                    "Unknown event kind: {}",
-                                    row.get::<String, _>("kind")
-                                ),
-                            })
-                            .collect::<Vec<crate::model::shared::event::ThreadEvent>>()
-                    });
-                Ok(Some(crate::model::write::Thread::replay(&events?)))
-            }
-        }
+        let thread = find_in(&mut tx, id).await?;
+        tx.commit().await.map_err(SqliteStoreError::FindCommit)?;
+        Ok(thread)
     }
 
     async fn store(
         &self,
         version: Option<crate::model::write::Version>,
-        events: &[crate::model::shared::event::ThreadEvent],
+        envelopes: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
     ) -> Result<(), crate::port::ThreadRepositoryError> {
-        if events.is_empty() {
-            return Ok(());
-        }
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .map_err(SqliteStoreError::StoreBeginTransaction)?;
+        store_events(&mut tx, version, envelopes).await?;
+        tx.commit().await.map_err(SqliteStoreError::StoreCommit)?;
+        Ok(())
+    }
 
-        let last_event = events.last().expect("events should not be empty");
-        let thread_id = last_event.thread_id();
-        let last_event_version = last_event.version();
+    async fn store_all(
+        &self,
+        streams: &[(
+            Option<crate::model::write::Version>,
+            &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+        )],
+    ) -> Result<(), crate::port::ThreadRepositoryError> {
         let mut tx = self
             .0
             .begin()
             .await
             .map_err(SqliteStoreError::StoreBeginTransaction)?;
-        match version {
-            None => {
-                sqlx::query(include_str!("sqlite_store/insert_thread_event_streams.sql"))
-                    .bind(thread_id.to_string())
-                    .bind(u32::from(last_event_version))
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(SqliteStoreError::StoreInsertEventStreams)?;
-            }
-            Some(version) => {
-                let result =
-                    sqlx::query(include_str!("sqlite_store/update_thread_event_streams.sql"))
-                        .bind(u32::from(last_event_version))
-                        .bind(thread_id.to_string())
-                        .bind(u32::from(version))
-                        .execute(&mut *tx)
-                        .await
-                        .map_err(SqliteStoreError::StoreUpdateEventStreams)?;
-                if result.rows_affected() == 0 {
-                    return Err(SqliteStoreError::StoreUpdateEventStreamsConflict {
-                        expected_version: version,
-                        thread_id,
-                    })?;
-                }
-            }
+        for (version, envelopes) in streams {
+            store_events(&mut tx, *version, envelopes).await?;
         }
+        tx.commit().await.map_err(SqliteStoreError::StoreCommit)?;
+        Ok(())
+    }
+}
 
-        for event in events {
-            let (at, content, id, kind, thread_id, version) = match event {
-                crate::model::shared::event::ThreadEvent::Created(event) => (
-                    event.at.clone(),
-                    event.content.clone(),
-                    event.id.clone(),
-                    "created",
-                    event.thread_id.clone(),
-                    event.version,
-                ),
-                crate::model::shared::event::ThreadEvent::Replied(event) => (
-                    event.at.clone(),
-                    event.content.clone(),
-                    event.id.clone(),
-                    "replied",
-                    event.thread_id.clone(),
-                    event.version,
-                ),
-            };
-            sqlx::query(include_str!("sqlite_store/insert_thread_events.sql"))
-                .bind(at)
-                .bind(content)
-                .bind(id)
-                .bind(kind)
-                .bind(thread_id)
+/// Loads the thread from the latest snapshot and the events after it in the transaction
+async fn find_in(
+    tx: &mut sqlx::SqliteConnection,
+    id: &crate::model::shared::id::ThreadId,
+) -> Result<Option<crate::model::write::Thread>, crate::port::ThreadRepositoryError> {
+    let row = sqlx::query(include_str!("sqlite_store/select_thread_event_streams.sql"))
+        .bind(id.to_string())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(SqliteStoreError::FindSelectEventStreams)?;
+    if row.is_none() {
+        return Ok(None);
+    }
+
+    let snapshot = sqlx::query(include_str!("sqlite_store/select_thread_snapshots.sql"))
+        .bind(id.to_string())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(SqliteStoreError::FindSelectSnapshots)?
+        .and_then(|row| super::restore_snapshot(id, &stored_thread_snapshot_from_row(&row)));
+    let rows = sqlx::query(include_str!("sqlite_store/select_thread_events.sql"))
+        .bind(id.to_string())
+        .bind(snapshot.as_ref().map_or(0, |it| u32::from(it.version())))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(SqliteStoreError::FindSelectEvents)?;
+    let events = rows
+        .into_iter()
+        .map(|row| {
+            stored_thread_event_from_row(&row)
+                .deserialize()
+                .map_err(SqliteStoreError::FindDeserializeEvent)
+        })
+        .collect::<Result<Vec<crate::model::shared::event::ThreadEvent>, _>>()?;
+    let thread = match snapshot {
+        Some(snapshot) => snapshot.replay_from(&events),
+        None => crate::model::write::Thread::replay(&events),
+    }
+    .map_err(|e| {
+        tracing::error!(thread_id = %id, error = %e, "thread events cannot be replayed");
+        crate::port::ThreadRepositoryError::Corrupted {
+            source: e,
+            thread_id: id.clone(),
+        }
+    })?;
+    Ok(Some(thread))
+}
+
+/// Appends the events to the stream of the thread and updates the read model in the transaction
+async fn store_events(
+    tx: &mut sqlx::SqliteConnection,
+    version: Option<crate::model::write::Version>,
+    envelopes: &[crate::model::shared::event_envelope::ThreadEventEnvelope],
+) -> Result<(), crate::port::ThreadRepositoryError> {
+    if envelopes.is_empty() {
+        return Ok(());
+    }
+
+    let last_event = &envelopes.last().expect("events should not be empty").event;
+    let thread_id = last_event.thread_id().clone();
+    let last_event_version = last_event.version();
+    match version {
+        None => {
+            sqlx::query(include_str!("sqlite_store/insert_thread_event_streams.sql"))
+                .bind(thread_id.to_string())
+                .bind(u32::from(last_event_version))
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteStoreError::StoreInsertEventStreams)?;
+        }
+        Some(version) => {
+            let result = sqlx::query(include_str!("sqlite_store/update_thread_event_streams.sql"))
+                .bind(u32::from(last_event_version))
+                .bind(thread_id.to_string())
                 .bind(u32::from(version))
                 .execute(&mut *tx)
                 .await
-                .map_err(SqliteStoreError::StoreInsertEvents)?;
+                .map_err(SqliteStoreError::StoreUpdateEventStreams)?;
+            if result.rows_affected() == 0 {
+                let row = sqlx::query(include_str!("sqlite_store/select_thread_event_streams.sql"))
+                    .bind(thread_id.to_string())
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreSelectEventStreams)?;
+                return Err(match row {
+                    Some(row) => crate::port::ThreadRepositoryError::VersionMismatch {
+                        actual: crate::model::write::Version::from(
+                            row.try_get::<u32, _>("version")
+                                .map_err(SqliteStoreError::StoreSelectEventStreams)?,
+                        ),
+                        expected: Some(version),
+                    },
+                    None => crate::port::ThreadRepositoryError::NotFound(thread_id),
+                });
+            }
         }
+    }
 
-        // Update read model
-        for event in events {
-            match event {
-                crate::model::shared::event::ThreadEvent::Created(event) => {
-                    sqlx::query(include_str!("sqlite_store/insert_threads.sql"))
-                        .bind(event.at.clone())
-                        .bind(event.content.clone())
-                        .bind(event.at.clone())
-                        .bind(1_i64)
-                        .bind(event.thread_id.clone())
-                        .bind(event.content.clone())
-                        .bind(event.at.clone())
-                        .bind(1_i64)
-                        .bind(0_i64)
-                        .bind(event.version)
+    for crate::model::shared::event_envelope::ThreadEventEnvelope { event, metadata } in envelopes {
+        let (at, id, thread_id, version) = match event {
+            crate::model::shared::event::ThreadEvent::Closed(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Continued(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Created(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Filled(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::MessageDeleted(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::MessageEdited(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::MessageReacted(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::MessageUnreacted(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Pinned(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::PollVoted(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Reopened(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Replied(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Tagged(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::TitleChanged(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Unpinned(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+            crate::model::shared::event::ThreadEvent::Untagged(event) => (
+                event.at.to_string(),
+                event.id.to_string(),
+                event.thread_id.to_string(),
+                event.version,
+            ),
+        };
+        let fingerprint = match event {
+            crate::model::shared::event::ThreadEvent::Created(event) => {
+                event.fingerprint.as_ref().map(ToString::to_string)
+            }
+            crate::model::shared::event::ThreadEvent::Replied(event) => {
+                event.fingerprint.as_ref().map(ToString::to_string)
+            }
+            _ => None,
+        };
+        let stored = crate::model::shared::event_schema::StoredThreadEvent::serialize(event)
+            .map_err(SqliteStoreError::StoreSerializeEvent)?;
+        let metadata =
+            serde_json::to_string(metadata).map_err(SqliteStoreError::StoreSerializeMetadata)?;
+        sqlx::query(include_str!("sqlite_store/insert_thread_events.sql"))
+            .bind(at)
+            .bind(stored.data)
+            .bind(fingerprint)
+            .bind(id)
+            .bind(stored.kind)
+            .bind(metadata)
+            .bind(stored.schema_version)
+            .bind(thread_id)
+            .bind(version)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::StoreInsertEvents)?;
+    }
+
+    // Update read model
+    for event in envelopes.iter().map(|envelope| &envelope.event) {
+        match event {
+            crate::model::shared::event::ThreadEvent::Closed(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_closed.sql"))
+                    .bind(true)
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsClosed)?;
+            }
+            crate::model::shared::event::ThreadEvent::Continued(event) => {
+                sqlx::query(include_str!(
+                    "sqlite_store/update_threads_next_thread_id.sql"
+                ))
+                .bind(event.next_thread_id.to_string())
+                .bind(event.version)
+                .bind(event.thread_id.to_string())
+                .bind(event.version - 1)
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsNextThreadId)?;
+            }
+            crate::model::shared::event::ThreadEvent::Created(event) => {
+                sqlx::query(include_str!("sqlite_store/insert_threads.sql"))
+                    .bind(event.board_id.clone().unwrap_or_default().to_string())
+                    .bind(event.at.to_string())
+                    .bind(false)
+                    .bind(event.at.to_string())
+                    .bind(false)
+                    .bind(event.content.clone())
+                    .bind(event.at.to_string())
+                    .bind(event.name.clone())
+                    .bind(1_i64)
+                    .bind(event.trip.clone())
+                    .bind(event.thread_id.to_string())
+                    .bind(event.content.clone())
+                    .bind(event.at.to_string())
+                    .bind(event.name.clone())
+                    .bind(1_i64)
+                    .bind(event.trip.clone())
+                    .bind(false)
+                    .bind(event.previous_thread_id.as_ref().map(ToString::to_string))
+                    .bind(0_i64)
+                    .bind(event.title.clone())
+                    .bind(event.version)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelInsertThreads)?;
+
+                sqlx::query(include_str!("sqlite_store/insert_messages.sql"))
+                    .bind(event.content.clone())
+                    .bind(event.at.to_string())
+                    .bind(event.name.clone())
+                    .bind(event.thread_id.to_string())
+                    .bind(event.trip.clone())
+                    .bind(1_i64)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessages)?;
+
+                for (position, attachment) in (0_i64..).zip(event.attachments.iter()) {
+                    sqlx::query(include_str!("sqlite_store/insert_message_attachments.sql"))
+                        .bind(attachment.hash.to_string())
+                        .bind(attachment.mime.to_string())
+                        .bind(position)
+                        .bind(attachment.size)
+                        .bind(attachment.thumbnail_hash.to_string())
+                        .bind(event.thread_id.to_string())
+                        .bind(1_i64)
                         .execute(&mut *tx)
                         .await
-                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertThreads)?;
+                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessageAttachments)?;
+                }
 
-                    sqlx::query(include_str!("sqlite_store/insert_messages.sql"))
-                        .bind(event.content.clone())
-                        .bind(event.at.clone())
-                        .bind(event.thread_id.clone())
-                        .bind(1_i64)
+                for tag in &event.tags {
+                    sqlx::query(include_str!("sqlite_store/insert_thread_tags.sql"))
+                        .bind(tag.clone())
+                        .bind(event.thread_id.to_string())
                         .execute(&mut *tx)
                         .await
-                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessages)?;
+                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertThreadTags)?;
                 }
-                crate::model::shared::event::ThreadEvent::Replied(event) => {
-                    sqlx::query(include_str!("sqlite_store/update_threads.sql"))
-                        .bind(event.content.clone())
-                        .bind(event.at.clone())
-                        .bind(event.version)
-                        .bind(event.thread_id.clone())
-                        .bind(event.version - 1)
+
+                if let Some(poll) = &event.poll {
+                    sqlx::query(include_str!("sqlite_store/insert_polls.sql"))
+                        .bind(poll.closes_at.map(|it| it.to_string()))
+                        .bind(poll.question.clone())
+                        .bind(event.thread_id.to_string())
                         .execute(&mut *tx)
                         .await
-                        .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreads)?;
+                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertPolls)?;
+                    for (number, label) in (0_i64..).zip(poll.options.iter()) {
+                        sqlx::query(include_str!("sqlite_store/insert_poll_options.sql"))
+                            .bind(label.clone())
+                            .bind(number)
+                            .bind(event.thread_id.to_string())
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(SqliteStoreError::StoreUpdateReadModelInsertPollOptions)?;
+                    }
+                }
+            }
+            crate::model::shared::event::ThreadEvent::Filled(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_filled.sql"))
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsFilled)?;
+            }
+            crate::model::shared::event::ThreadEvent::MessageDeleted(event) => {
+                sqlx::query(include_str!(
+                    "sqlite_store/update_threads_message_deleted.sql"
+                ))
+                .bind(event.number)
+                .bind(event.number)
+                .bind(event.at.to_string())
+                .bind(event.number)
+                .bind(event.reason.to_string())
+                .bind(event.number)
+                .bind(event.number)
+                .bind(event.at.to_string())
+                .bind(event.number)
+                .bind(event.reason.to_string())
+                .bind(event.version)
+                .bind(event.thread_id.to_string())
+                .bind(event.version - 1)
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsMessageDeleted)?;
+
+                sqlx::query(include_str!("sqlite_store/delete_message_revisions.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelDeleteMessageRevisions)?;
+
+                sqlx::query(include_str!("sqlite_store/delete_message_reactions.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelDeleteMessageReactions)?;
 
-                    sqlx::query(include_str!("sqlite_store/insert_messages.sql"))
-                        .bind(event.content.clone())
-                        .bind(event.at.clone())
-                        .bind(event.thread_id.clone())
-                        .bind(event.version)
+                sqlx::query(include_str!("sqlite_store/delete_message_attachments.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelDeleteMessageAttachments)?;
+
+                // a tombstone links to nothing
+                sqlx::query(include_str!("sqlite_store/delete_message_anchors.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelDeleteMessageAnchors)?;
+
+                sqlx::query(include_str!("sqlite_store/update_messages_deleted.sql"))
+                    .bind(event.at.to_string())
+                    .bind(event.reason.to_string())
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateMessagesDeleted)?;
+            }
+            crate::model::shared::event::ThreadEvent::MessageEdited(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_message.sql"))
+                    .bind(event.number)
+                    .bind(event.content.clone())
+                    .bind(event.number)
+                    .bind(event.at.to_string())
+                    .bind(event.number)
+                    .bind(event.content.clone())
+                    .bind(event.number)
+                    .bind(event.at.to_string())
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsMessage)?;
+
+                sqlx::query(include_str!("sqlite_store/insert_message_revisions.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessageRevisions)?;
+
+                sqlx::query(include_str!("sqlite_store/delete_message_anchors.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelDeleteMessageAnchors)?;
+                for anchor in crate::model::read::parse_anchors(&event.content, event.number) {
+                    sqlx::query(include_str!("sqlite_store/insert_message_anchors.sql"))
+                        .bind(anchor)
+                        .bind(event.thread_id.to_string())
+                        .bind(event.number)
                         .execute(&mut *tx)
                         .await
-                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessages)?;
+                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessageAnchors)?;
                 }
+
+                sqlx::query(include_str!("sqlite_store/update_messages.sql"))
+                    .bind(event.content.clone())
+                    .bind(event.at.to_string())
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateMessages)?;
             }
-        }
+            crate::model::shared::event::ThreadEvent::MessageReacted(event) => {
+                // reactions do not touch last_message_created_at to keep the list order
+                sqlx::query(include_str!("sqlite_store/update_threads_version.sql"))
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsVersion)?;
 
-        tx.commit().await.map_err(SqliteStoreError::StoreCommit)?;
+                sqlx::query(include_str!("sqlite_store/insert_message_reactions.sql"))
+                    .bind(event.reaction.to_string())
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessageReactions)?;
+            }
+            crate::model::shared::event::ThreadEvent::MessageUnreacted(event) => {
+                // reactions do not touch last_message_created_at to keep the list order
+                sqlx::query(include_str!("sqlite_store/update_threads_version.sql"))
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsVersion)?;
 
-        Ok(())
+                sqlx::query(include_str!("sqlite_store/update_message_reactions.sql"))
+                    .bind(event.reaction.to_string())
+                    .bind(event.thread_id.to_string())
+                    .bind(event.number)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateMessageReactions)?;
+
+                sqlx::query(include_str!(
+                    "sqlite_store/delete_message_reactions_empty.sql"
+                ))
+                .bind(event.thread_id.to_string())
+                .bind(event.number)
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteStoreError::StoreUpdateReadModelDeleteMessageReactions)?;
+            }
+            crate::model::shared::event::ThreadEvent::Pinned(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_pinned.sql"))
+                    .bind(true)
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsPinned)?;
+            }
+            crate::model::shared::event::ThreadEvent::PollVoted(event) => {
+                // votes do not touch last_message_created_at to keep the list order
+                sqlx::query(include_str!("sqlite_store/update_threads_version.sql"))
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsVersion)?;
+
+                sqlx::query(include_str!("sqlite_store/update_poll_options.sql"))
+                    .bind(event.thread_id.to_string())
+                    .bind(event.option)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdatePollOptions)?;
+            }
+            crate::model::shared::event::ThreadEvent::Reopened(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_closed.sql"))
+                    .bind(false)
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsClosed)?;
+            }
+            crate::model::shared::event::ThreadEvent::Replied(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads.sql"))
+                    .bind(event.sage)
+                    .bind(event.at.to_string())
+                    .bind(event.content.clone())
+                    .bind(event.at.to_string())
+                    .bind(event.name.clone())
+                    .bind(event.trip.clone())
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreads)?;
+
+                sqlx::query(include_str!(
+                    "sqlite_store/insert_messages_last_message_number.sql"
+                ))
+                .bind(event.content.clone())
+                .bind(event.at.to_string())
+                .bind(event.name.clone())
+                .bind(event.trip.clone())
+                .bind(event.thread_id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessages)?;
+
+                let number = sqlx::query(include_str!(
+                    "sqlite_store/select_threads_last_message_number.sql"
+                ))
+                .bind(event.thread_id.to_string())
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(SqliteStoreError::StoreUpdateReadModelSelectThreadsLastMessageNumber)?
+                .try_get::<u16, _>("last_message_number")
+                .map_err(SqliteStoreError::StoreUpdateReadModelDecode)?;
+                for (position, attachment) in (0_i64..).zip(event.attachments.iter()) {
+                    sqlx::query(include_str!("sqlite_store/insert_message_attachments.sql"))
+                        .bind(attachment.hash.to_string())
+                        .bind(attachment.mime.to_string())
+                        .bind(position)
+                        .bind(attachment.size)
+                        .bind(attachment.thumbnail_hash.to_string())
+                        .bind(event.thread_id.to_string())
+                        .bind(number)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessageAttachments)?;
+                }
+                for anchor in crate::model::read::parse_anchors(&event.content, number) {
+                    sqlx::query(include_str!("sqlite_store/insert_message_anchors.sql"))
+                        .bind(anchor)
+                        .bind(event.thread_id.to_string())
+                        .bind(number)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(SqliteStoreError::StoreUpdateReadModelInsertMessageAnchors)?;
+                }
+            }
+            crate::model::shared::event::ThreadEvent::Tagged(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_version.sql"))
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsVersion)?;
+
+                sqlx::query(include_str!("sqlite_store/insert_thread_tags.sql"))
+                    .bind(event.tag.clone())
+                    .bind(event.thread_id.to_string())
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelInsertThreadTags)?;
+            }
+            crate::model::shared::event::ThreadEvent::TitleChanged(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_title.sql"))
+                    .bind(event.title.clone())
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsTitle)?;
+            }
+            crate::model::shared::event::ThreadEvent::Unpinned(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_pinned.sql"))
+                    .bind(false)
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsPinned)?;
+            }
+            crate::model::shared::event::ThreadEvent::Untagged(event) => {
+                sqlx::query(include_str!("sqlite_store/update_threads_version.sql"))
+                    .bind(event.version)
+                    .bind(event.thread_id.to_string())
+                    .bind(event.version - 1)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelUpdateThreadsVersion)?;
+
+                sqlx::query(include_str!("sqlite_store/delete_thread_tags.sql"))
+                    .bind(event.tag.clone())
+                    .bind(event.thread_id.to_string())
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(SqliteStoreError::StoreUpdateReadModelDeleteThreadTags)?;
+            }
+        }
     }
+
+    if crate::model::shared::thread_snapshot::ThreadSnapshot::is_due(version, last_event_version) {
+        let thread = find_in(tx, &thread_id)
+            .await?
+            .ok_or_else(|| crate::port::ThreadRepositoryError::NotFound(thread_id.clone()))?;
+        let stored = crate::model::shared::thread_snapshot::StoredThreadSnapshot::serialize(
+            &thread.snapshot(),
+        )
+        .map_err(SqliteStoreError::StoreSerializeSnapshot)?;
+        sqlx::query(include_str!("sqlite_store/insert_thread_snapshots.sql"))
+            .bind(stored.data)
+            .bind(stored.format_version)
+            .bind(thread_id.to_string())
+            .bind(stored.version)
+            .execute(&mut *tx)
+            .await
+            .map_err(SqliteStoreError::StoreInsertSnapshots)?;
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -426,30 +1582,1284 @@ mod tests {
 
     use super::*;
 
+    fn envelop(
+        events: &[crate::model::shared::event::ThreadEvent],
+    ) -> Vec<crate::model::shared::event_envelope::ThreadEventEnvelope> {
+        crate::model::shared::event_envelope::ThreadEventMetadata::default().envelop(events)
+    }
+
+    const BASELINE_THREAD_ID: &str = "b8392399-53a3-4f8e-8288-875448037455";
+
+    /// Creates a database with the tables and the rows as they were stored before the migrations
+    async fn baseline_database(dir: &std::path::Path) -> anyhow::Result<String> {
+        let url = format!("sqlite:{}?mode=rwc", dir.join("bbbs.sqlite").display());
+        let pool = sqlx::sqlite::SqlitePoolOptions::new().connect(&url).await?;
+        sqlx::raw_sql(
+            r#"
+CREATE TABLE thread_event_streams (
+    id      TEXT    NOT NULL    PRIMARY KEY,
+    version INTEGER NOT NULL
+);
+CREATE TABLE thread_events (
+    at          INTEGER NOT NULL,
+    content     TEXT    NOT NULL,
+    id          TEXT    NOT NULL   PRIMARY KEY,
+    kind        TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    version     INTEGER NOT NULL,
+    UNIQUE (thread_id, version)
+);
+CREATE TABLE threads (
+    created_at                  TEXT    NOT NULL,
+    first_message_content       TEXT    NOT NULL,
+    first_message_created_at    TEXT    NOT NULL,
+    first_message_number        INTEGER NOT NULL,
+    id                          TEXT    NOT NULL   PRIMARY KEY,
+    last_message_content        TEXT    NOT NULL,
+    last_message_created_at     TEXT    NOT NULL,
+    last_message_number         INTEGER NOT NULL,
+    replies_count               INTEGER NOT NULL,
+    version                     INTEGER NOT NULL
+);
+CREATE TABLE messages (
+    content     TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    PRIMARY KEY (thread_id, number)
+);
+INSERT INTO thread_event_streams VALUES ('b8392399-53a3-4f8e-8288-875448037455', 2);
+INSERT INTO thread_events VALUES
+    ('2023-10-01T12:00:00.000Z', 'Hello, world!', '0779b098-f41d-404a-b055-36463a7c009b', 'created', 'b8392399-53a3-4f8e-8288-875448037455', 1),
+    ('2023-10-01T12:01:00.000Z', 'Reply to message', '5f0d2b6e-8c1a-4e7b-9d3f-2a6c4b8e1f90', 'replied', 'b8392399-53a3-4f8e-8288-875448037455', 2);
+INSERT INTO threads VALUES (
+    '2023-10-01T12:00:00.000Z',
+    'Hello, world!',
+    '2023-10-01T12:00:00.000Z',
+    1,
+    'b8392399-53a3-4f8e-8288-875448037455',
+    'Reply to message',
+    '2023-10-01T12:01:00.000Z',
+    2,
+    1,
+    2
+);
+INSERT INTO messages VALUES
+    ('Hello, world!', '2023-10-01T12:00:00.000Z', 'b8392399-53a3-4f8e-8288-875448037455', 1),
+    ('Reply to message', '2023-10-01T12:01:00.000Z', 'b8392399-53a3-4f8e-8288-875448037455', 2);
+"#,
+        )
+        .execute(&pool)
+        .await?;
+        pool.close().await;
+        Ok(url)
+    }
+
+    #[tokio::test]
+    async fn test_migrate_baseline_database() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let url = baseline_database(dir.path()).await?;
+        let store = SqliteStore::connect(&url).await;
+        let thread_id = crate::model::shared::id::ThreadId::from_str(BASELINE_THREAD_ID)?;
+
+        let found = store.find(&thread_id).await?.expect("thread to be found");
+        assert_eq!(
+            found.title(),
+            &crate::model::write::ThreadTitle::try_from("Untitled".to_owned())?
+        );
+        assert_eq!(u32::from(found.version()), 2);
+
+        let thread = crate::port::ThreadReader::get_thread(&store, &thread_id)
+            .await?
+            .expect("thread to be found");
+        assert_eq!(thread.title, "Untitled");
+        assert_eq!(thread.replies_count, 1);
+        assert_eq!(
+            thread
+                .messages
+                .iter()
+                .map(|it| it.content.as_str())
+                .collect::<Vec<_>>(),
+            ["Hello, world!", "Reply to message"]
+        );
+
+        // the migrated thread accepts new events
+        let (replied, events) =
+            found.reply(crate::model::write::Message::new_for_testing(), false, 1000)?;
+        store
+            .store(Some(found.version()), &envelop(&events))
+            .await?;
+        assert_eq!(store.find(&thread_id).await?, Some(replied));
+
+        // the applied migrations are not applied again
+        let store = SqliteStore::connect(&url).await;
+        assert!(store.find(&thread_id).await?.is_some());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_new() -> anyhow::Result<()> {
         let store = SqliteStore::new().await;
 
-        let (created, created_events) =
-            crate::model::write::Thread::create(crate::model::write::Message::new_for_testing())?;
+        let (created, created_events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
 
         let found = store.find(created.id()).await?;
         assert!(found.is_none());
 
-        store.store(None, &created_events).await?;
+        store.store(None, &envelop(&created_events)).await?;
 
         let found = store.find(created.id()).await?;
         assert_eq!(found, Some(created.clone()));
 
         let (replied, replied_events) =
-            created.reply(crate::model::write::Message::new_for_testing())?;
+            created.reply(crate::model::write::Message::new_for_testing(), false, 1000)?;
         store
-            .store(Some(created.version()), &replied_events)
+            .store(Some(created.version()), &envelop(&replied_events))
             .await?;
 
         let found = store.find(replied.id()).await?;
-        assert_eq!(found, Some(replied));
+        assert_eq!(found, Some(replied.clone()));
+
+        let title = crate::model::write::ThreadTitle::new_for_testing();
+        let (changed, changed_events) = replied.change_title(title.clone())?;
+        store
+            .store(Some(replied.version()), &envelop(&changed_events))
+            .await?;
+
+        let found = store.find(changed.id()).await?;
+        assert_eq!(found, Some(changed.clone()));
+
+        let content = crate::model::write::MessageContent::new_for_testing();
+        let (edited, edited_events) =
+            changed.edit_message(2, content.clone(), std::time::Duration::from_secs(60))?;
+        store
+            .store(Some(changed.version()), &envelop(&edited_events))
+            .await?;
+
+        let found = store.find(edited.id()).await?;
+        assert_eq!(found, Some(edited.clone()));
+
+        let (deleted, deleted_events) =
+            edited.delete_message(1, crate::model::write::MessageDeletionReason::Moderator)?;
+        store
+            .store(Some(edited.version()), &envelop(&deleted_events))
+            .await?;
+
+        let found = store.find(deleted.id()).await?;
+        assert_eq!(found, Some(deleted.clone()));
+
+        let (closed, closed_events) = deleted.close()?;
+        store
+            .store(Some(deleted.version()), &envelop(&closed_events))
+            .await?;
+
+        let found = store.find(closed.id()).await?;
+        assert_eq!(found, Some(closed.clone()));
+        let thread = crate::port::ThreadReader::get_thread(&store, closed.id())
+            .await?
+            .expect("thread to be found");
+        assert!(thread.closed);
+
+        let (reopened, reopened_events) = closed.reopen()?;
+        store
+            .store(Some(closed.version()), &envelop(&reopened_events))
+            .await?;
+
+        let found = store.find(reopened.id()).await?;
+        assert_eq!(found, Some(reopened.clone()));
+
+        let thread = crate::port::ThreadReader::get_thread(&store, changed.id())
+            .await?
+            .expect("thread to be found");
+        assert_eq!(thread.title, String::from(title));
+        assert_eq!(thread.messages[0].content, "");
+        assert!(thread.messages[0].deleted_at.is_some());
+        assert_eq!(
+            thread.messages[0].deletion_reason.as_deref(),
+            Some("moderator")
+        );
+        assert!(thread.first_message.deleted_at.is_some());
+        assert!(thread.first_message.trip.is_some());
+        assert!(thread.messages[1].name.is_some());
+        assert!(thread.messages[1].trip.is_some());
+        assert_eq!(thread.last_message.trip, thread.messages[1].trip);
+        assert_eq!(thread.messages[1].content, String::from(content.clone()));
+        assert!(thread.messages[1].edited_at.is_some());
+        assert_eq!(thread.messages[1].revisions.len(), 1);
+        assert_eq!(thread.last_message.content, String::from(content));
+        assert!(!thread.closed);
+        assert_eq!(thread.version, u32::from(reopened.version()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_anchors() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let message = |content: &str| -> anyhow::Result<crate::model::write::Message> {
+            Ok(crate::model::write::Message::create(
+                crate::model::write::MessageContent::try_from(content.to_owned())?,
+                None,
+                vec![],
+                None,
+            ))
+        };
+
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            message(">>1")?,
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let (replied1, events) = created.reply(message(">>1")?, false, 1000)?;
+        store
+            .store(Some(created.version()), &envelop(&events))
+            .await?;
+        let (replied2, events) = replied1.reply(message(">>1-2 >>9")?, false, 1000)?;
+        store
+            .store(Some(replied1.version()), &envelop(&events))
+            .await?;
+
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        assert_eq!(thread.messages[0].anchors, Vec::<u16>::new());
+        assert_eq!(thread.messages[0].replied_by, vec![2, 3]);
+        assert_eq!(thread.messages[1].anchors, vec![1]);
+        assert_eq!(thread.messages[1].replied_by, vec![3]);
+        assert_eq!(thread.messages[2].anchors, vec![1, 2]);
+
+        let (edited, events) = replied2.edit_message(
+            3,
+            crate::model::write::MessageContent::try_from(">>2".to_owned())?,
+            std::time::Duration::from_secs(60),
+        )?;
+        store
+            .store(Some(replied2.version()), &envelop(&events))
+            .await?;
+
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        assert_eq!(thread.messages[0].replied_by, vec![2]);
+        assert_eq!(thread.messages[1].replied_by, vec![3]);
+        assert_eq!(thread.messages[2].anchors, vec![2]);
+
+        let (_, events) =
+            edited.delete_message(3, crate::model::write::MessageDeletionReason::AuthorRequest)?;
+        store
+            .store(Some(edited.version()), &envelop(&events))
+            .await?;
+
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        assert_eq!(thread.messages[1].replied_by, Vec::<u16>::new());
+        assert_eq!(thread.messages[2].anchors, Vec::<u16>::new());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_continue_thread() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+
+        let (created, created_events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&created_events)).await?;
+
+        let mut filled = created.clone();
+        let mut replied_events = vec![];
+        for _ in 0..999 {
+            let (replied, events) =
+                filled.reply(crate::model::write::Message::new_for_testing(), false, 1000)?;
+            filled = replied;
+            replied_events.extend(events);
+        }
+        store
+            .store(Some(created.version()), &envelop(&replied_events))
+            .await?;
 
+        let ((continued, continued_events), (next, next_events)) = filled.continue_thread(
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+        )?;
+        store
+            .store(Some(filled.version()), &envelop(&continued_events))
+            .await?;
+        store.store(None, &envelop(&next_events)).await?;
+
+        let found = store.find(continued.id()).await?;
+        assert_eq!(found, Some(continued.clone()));
+        let found = store.find(next.id()).await?;
+        assert_eq!(found, Some(next.clone()));
+
+        let thread = crate::port::ThreadReader::get_thread(&store, continued.id())
+            .await?
+            .expect("thread to be found");
+        assert!(thread.filled);
+        assert_eq!(thread.next_thread_id, Some(next.id().to_string()));
+        assert_eq!(thread.version, u32::from(continued.version()));
+        let thread = crate::port::ThreadReader::get_thread(&store, next.id())
+            .await?
+            .expect("thread to be found");
+        assert!(!thread.filled);
+        assert_eq!(thread.previous_thread_id, Some(continued.id().to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_continue_thread_concurrently() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+
+        let (created, created_events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&created_events)).await?;
+
+        let mut filled = created.clone();
+        let mut replied_events = vec![];
+        for _ in 0..999 {
+            let (replied, events) =
+                filled.reply(crate::model::write::Message::new_for_testing(), false, 1000)?;
+            filled = replied;
+            replied_events.extend(events);
+        }
+        store
+            .store(Some(created.version()), &envelop(&replied_events))
+            .await?;
+
+        // two replies continue the same filled thread at the same time
+        let ((_, continued_events1), (next1, next_events1)) = filled.continue_thread(
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+        )?;
+        let ((_, continued_events2), (next2, next_events2)) = filled.continue_thread(
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+        )?;
+        store
+            .store_all(&[
+                (None, envelop(&next_events1).as_slice()),
+                (
+                    Some(filled.version()),
+                    envelop(&continued_events1).as_slice(),
+                ),
+            ])
+            .await?;
+        let result = store
+            .store_all(&[
+                (None, envelop(&next_events2).as_slice()),
+                (
+                    Some(filled.version()),
+                    envelop(&continued_events2).as_slice(),
+                ),
+            ])
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::port::ThreadRepositoryError::VersionMismatch { .. })
+        ));
+
+        // the next thread of the loser is not left without a link to it
+        assert_eq!(store.find(next2.id()).await?, None);
+        assert!(
+            crate::port::ThreadReader::get_thread(&store, next2.id())
+                .await?
+                .is_none()
+        );
+        let found = store.find(filled.id()).await?.expect("thread to be found");
+        assert_eq!(found.next_thread_id(), Some(next1.id()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reactions() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let heart = crate::model::write::MessageReaction::Heart;
+        let laugh = crate::model::write::MessageReaction::Laugh;
+        let alice = crate::model::write::ClientKey::from_ip(
+            &std::net::IpAddr::from([192, 0, 2, 1]),
+            "secret",
+        );
+        let bob = crate::model::write::ClientKey::from_ip(
+            &std::net::IpAddr::from([192, 0, 2, 2]),
+            "secret",
+        );
+
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let (reacted1, events) = created.react(1, heart, alice.clone())?;
+        store
+            .store(Some(created.version()), &envelop(&events))
+            .await?;
+        let (reacted2, events) = reacted1.react(1, heart, bob)?;
+        store
+            .store(Some(reacted1.version()), &envelop(&events))
+            .await?;
+        let (reacted3, events) = reacted2.react(1, laugh, alice.clone())?;
+        store
+            .store(Some(reacted2.version()), &envelop(&events))
+            .await?;
+
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        let reactions = thread.messages[0]
+            .reactions
+            .iter()
+            .map(|it| (it.code.as_str(), it.count))
+            .collect::<Vec<(&str, u32)>>();
+        assert_eq!(reactions, vec![("heart", 2), ("laugh", 1)]);
+        assert_eq!(thread.version, u32::from(reacted3.version()));
+        // reactions do not bump the thread
+        assert_eq!(thread.last_message.created_at, thread.created_at);
+
+        let (unreacted, events) = reacted3.unreact(1, laugh, alice)?;
+        store
+            .store(Some(reacted3.version()), &envelop(&events))
+            .await?;
+
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        let reactions = thread.messages[0]
+            .reactions
+            .iter()
+            .map(|it| (it.code.as_str(), it.count))
+            .collect::<Vec<(&str, u32)>>();
+        assert_eq!(reactions, vec![("heart", 2)]);
+        assert_eq!(thread.version, u32::from(unreacted.version()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_boards() -> anyhow::Result<()> {
+        use crate::port::BoardReader as _;
+        use crate::port::BoardRepository as _;
+
+        let store = SqliteStore::new().await;
+        let default_board_id = crate::model::shared::id::BoardId::default();
+        assert!(store.find_board(&default_board_id).await?.is_some());
+
+        let board_id = crate::model::shared::id::BoardId::from_str(
+            &uuid::Uuid::new_v4().simple().to_string(),
+        )?;
+        let board = crate::model::write::Board::new(
+            board_id.clone(),
+            "Development".to_owned(),
+            "talk about development".to_owned(),
+            100,
+            50,
+        )?;
+        store.store_board(&board).await?;
+        assert!(matches!(
+            store.store_board(&board).await,
+            Err(crate::port::BoardRepositoryError::AlreadyExists(_))
+        ));
+        assert_eq!(store.find_board(&board_id).await?, Some(board));
+        let read = store
+            .get_board(&board_id)
+            .await?
+            .expect("board to be found");
+        assert_eq!(read.name, "Development");
+        assert_eq!(read.max_threads, 50);
+        assert!(
+            store
+                .list_boards()
+                .await?
+                .iter()
+                .any(|it| it.id == board_id.to_string())
+        );
+
+        let (created, events) = crate::model::write::Thread::create(
+            board_id.clone(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let threads =
+            crate::port::ThreadReader::list_threads(&store, Some(&board_id), None).await?;
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, created.id().to_string());
+        assert_eq!(threads[0].board_id, board_id.to_string());
+        let threads = crate::port::ThreadReader::list_threads(&store, None, None).await?;
+        assert!(threads.iter().any(|it| it.id == created.id().to_string()));
+        let threads =
+            crate::port::ThreadReader::list_threads(&store, Some(&default_board_id), None).await?;
+        assert!(!threads.iter().any(|it| it.id == created.id().to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sage() -> anyhow::Result<()> {
+        use crate::port::BoardRepository as _;
+
+        let store = SqliteStore::new().await;
+        let board_id = crate::model::shared::id::BoardId::from_str(
+            &uuid::Uuid::new_v4().simple().to_string(),
+        )?;
+        store
+            .store_board(&crate::model::write::Board::new(
+                board_id.clone(),
+                "Sage".to_owned(),
+                String::default(),
+                255,
+                10,
+            )?)
+            .await?;
+        let create = || {
+            crate::model::write::Thread::create(
+                board_id.clone(),
+                crate::model::write::ThreadTitle::new_for_testing(),
+                crate::model::write::Message::new_for_testing(),
+                std::collections::BTreeSet::new(),
+                None,
+            )
+        };
+        let (older, events) = create()?;
+        store.store(None, &envelop(&events)).await?;
+        let (newer, events) = create()?;
+        store.store(None, &envelop(&events)).await?;
+        let list = async || -> anyhow::Result<Vec<String>> {
+            Ok(
+                crate::port::ThreadReader::list_threads(&store, Some(&board_id), None)
+                    .await?
+                    .into_iter()
+                    .map(|it| it.id)
+                    .collect(),
+            )
+        };
+        assert_eq!(
+            list().await?,
+            vec![newer.id().to_string(), older.id().to_string()]
+        );
+
+        let (saged, events) =
+            older.reply(crate::model::write::Message::new_for_testing(), true, 1000)?;
+        store
+            .store(Some(older.version()), &envelop(&events))
+            .await?;
+        assert_eq!(
+            list().await?,
+            vec![newer.id().to_string(), older.id().to_string()]
+        );
+
+        let (_, events) =
+            saged.reply(crate::model::write::Message::new_for_testing(), false, 1000)?;
+        store
+            .store(Some(saged.version()), &envelop(&events))
+            .await?;
+        assert_eq!(
+            list().await?,
+            vec![older.id().to_string(), newer.id().to_string()]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pinned() -> anyhow::Result<()> {
+        use crate::port::BoardRepository as _;
+
+        let store = SqliteStore::new().await;
+        let board_id = crate::model::shared::id::BoardId::from_str(
+            &uuid::Uuid::new_v4().simple().to_string(),
+        )?;
+        store
+            .store_board(&crate::model::write::Board::new(
+                board_id.clone(),
+                "Pinned".to_owned(),
+                String::default(),
+                255,
+                10,
+            )?)
+            .await?;
+        let create = || {
+            crate::model::write::Thread::create(
+                board_id.clone(),
+                crate::model::write::ThreadTitle::new_for_testing(),
+                crate::model::write::Message::new_for_testing(),
+                std::collections::BTreeSet::new(),
+                None,
+            )
+        };
+        let (older, events) = create()?;
+        store.store(None, &envelop(&events)).await?;
+        let (newer, events) = create()?;
+        store.store(None, &envelop(&events)).await?;
+        let list = async || -> anyhow::Result<Vec<(String, bool)>> {
+            Ok(
+                crate::port::ThreadReader::list_threads(&store, Some(&board_id), None)
+                    .await?
+                    .into_iter()
+                    .map(|it| (it.id, it.pinned))
+                    .collect(),
+            )
+        };
+
+        let (pinned, events) = older.pin()?;
+        store
+            .store(Some(older.version()), &envelop(&events))
+            .await?;
+        assert_eq!(
+            list().await?,
+            vec![
+                (older.id().to_string(), true),
+                (newer.id().to_string(), false)
+            ]
+        );
+
+        let (_, events) = pinned.unpin()?;
+        store
+            .store(Some(pinned.version()), &envelop(&events))
+            .await?;
+        assert_eq!(
+            list().await?,
+            vec![
+                (newer.id().to_string(), false),
+                (older.id().to_string(), false)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attachments() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let message = crate::model::write::Message {
+            attachments: vec![crate::model::write::MessageAttachment::new_for_testing()],
+            ..crate::model::write::Message::new_for_testing()
+        };
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            message.clone(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let (replied, events) = created.reply(message.clone(), false, 1000)?;
+        store
+            .store(Some(created.version()), &envelop(&events))
+            .await?;
+
+        let found = store.find(created.id()).await?;
+        assert_eq!(found, Some(replied.clone()));
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        let attachment = &message.attachments[0];
+        for message in &thread.messages {
+            let attachments = message
+                .attachments
+                .iter()
+                .map(|it| {
+                    (
+                        it.hash.clone(),
+                        it.mime.clone(),
+                        it.size,
+                        it.thumbnail_hash.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                attachments,
+                vec![(
+                    attachment.hash.to_string(),
+                    attachment.mime.to_string(),
+                    attachment.size,
+                    attachment.thumbnail_hash.to_string(),
+                )]
+            );
+        }
+
+        let is_blob_referenced = async |hash: &crate::model::shared::id::BlobHash| {
+            crate::port::BlobReferenceReader::is_blob_referenced(&store, hash).await
+        };
+        assert!(is_blob_referenced(&attachment.hash).await?);
+        assert!(is_blob_referenced(&attachment.thumbnail_hash).await?);
+
+        let (deleted, events) =
+            replied.delete_message(2, crate::model::write::MessageDeletionReason::AuthorRequest)?;
+        store
+            .store(Some(replied.version()), &envelop(&events))
+            .await?;
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        assert!(thread.messages[1].attachments.is_empty());
+        // still attached to the first message
+        assert!(is_blob_referenced(&attachment.hash).await?);
+
+        let (_, events) =
+            deleted.delete_message(1, crate::model::write::MessageDeletionReason::AuthorRequest)?;
+        store
+            .store(Some(deleted.version()), &envelop(&events))
+            .await?;
+        assert!(!is_blob_referenced(&attachment.hash).await?);
+        assert!(!is_blob_referenced(&attachment.thumbnail_hash).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_poll() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            Some(crate::model::write::Poll::new_for_testing()),
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let (voted, events) = created.vote(
+            1,
+            crate::model::write::VoterKey::from_ip(
+                &std::net::IpAddr::from([192, 0, 2, 1]),
+                "secret",
+            ),
+        )?;
+        store
+            .store(Some(created.version()), &envelop(&events))
+            .await?;
+
+        let found = store.find(created.id()).await?;
+        assert_eq!(found, Some(voted.clone()));
+        assert!(
+            voted
+                .vote(
+                    0,
+                    crate::model::write::VoterKey::from_ip(
+                        &std::net::IpAddr::from([192, 0, 2, 1]),
+                        "secret"
+                    )
+                )
+                .is_err()
+        );
+
+        let thread = crate::port::ThreadReader::get_thread(&store, created.id())
+            .await?
+            .expect("thread to be found");
+        let poll = thread.poll.expect("poll to be found");
+        assert_eq!(poll.question, "Do you agree?");
+        let options = poll
+            .options
+            .iter()
+            .map(|it| (it.label.as_str(), it.count))
+            .collect::<Vec<_>>();
+        assert_eq!(options, vec![("yes", 0), ("no", 1)]);
+        assert_eq!(poll.votes_count, 1);
+        assert_eq!(thread.version, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tags() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        // tags are global, so a random tag keeps the test isolated
+        let tag = crate::model::write::ThreadTag::from_str(&format!(
+            "t{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..16]
+        ))?;
+        let other_tag = crate::model::write::ThreadTag::from_str(&format!(
+            "t{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..16]
+        ))?;
+        let (tagged, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::from([tag.clone()]),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let (untagged, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let list = async |tag: &crate::model::write::ThreadTag| -> anyhow::Result<Vec<String>> {
+            Ok(
+                crate::port::ThreadReader::list_threads(&store, None, Some(tag))
+                    .await?
+                    .into_iter()
+                    .map(|it| it.id)
+                    .collect(),
+            )
+        };
+        let count = async |tag: &crate::model::write::ThreadTag| -> anyhow::Result<Option<u32>> {
+            Ok(crate::port::ThreadReader::list_tags(&store)
+                .await?
+                .into_iter()
+                .find(|it| it.name == tag.to_string())
+                .map(|it| it.count))
+        };
+        assert_eq!(list(&tag).await?, vec![tagged.id().to_string()]);
+        assert_eq!(count(&tag).await?, Some(1));
+
+        let (retagged, events) = untagged.tag(tag.clone())?;
+        store
+            .store(Some(untagged.version()), &envelop(&events))
+            .await?;
+        let (retagged, events) = retagged.tag(other_tag.clone())?;
+        store
+            .store(Some(untagged.version().next()?), &envelop(&events))
+            .await?;
+        assert_eq!(count(&tag).await?, Some(2));
+        let thread = crate::port::ThreadReader::get_thread(&store, retagged.id())
+            .await?
+            .expect("thread to be found");
+        let mut expected = vec![tag.to_string(), other_tag.to_string()];
+        expected.sort();
+        assert_eq!(thread.tags, expected);
+
+        let (_, events) = tagged.untag(tag.clone())?;
+        store
+            .store(Some(tagged.version()), &envelop(&events))
+            .await?;
+        assert_eq!(list(&tag).await?, vec![retagged.id().to_string()]);
+        assert_eq!(count(&tag).await?, Some(1));
+        let listed = crate::port::ThreadReader::list_threads(&store, None, Some(&other_tag))
+            .await?
+            .into_iter()
+            .map(|it| it.tags)
+            .collect::<Vec<Vec<String>>>();
+        assert_eq!(listed, vec![expected]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits() -> anyhow::Result<()> {
+        use crate::port::RateLimiter as _;
+
+        // the database is shared between test runs
+        let ip = std::net::IpAddr::V6(std::net::Ipv6Addr::from(rand::random::<u128>()));
+        let client = crate::model::write::ClientKey::from_ip(&ip, "secret");
+        let limit = crate::model::write::RateLimit::new(2, std::time::Duration::from_secs(60));
+        let store = SqliteStore::new().await;
+        store
+            .consume_rate_limit(crate::model::write::RateLimitAction::Reply, &client, &limit)
+            .await?;
+        store
+            .consume_rate_limit(crate::model::write::RateLimitAction::Reply, &client, &limit)
+            .await?;
+
+        // the state survives restarts
+        let store = SqliteStore::new().await;
+        assert!(matches!(
+            store
+                .consume_rate_limit(crate::model::write::RateLimitAction::Reply, &client, &limit)
+                .await,
+            Err(crate::port::RateLimiterError::Exceeded(_))
+        ));
+        // the budgets are separate
+        store
+            .consume_rate_limit(
+                crate::model::write::RateLimitAction::CreateThread,
+                &client,
+                &limit,
+            )
+            .await?;
+
+        // only the client key is stored, not the address
+        let clients = sqlx::query_scalar::<_, String>("SELECT client_key FROM rate_limits")
+            .fetch_all(&store.0)
+            .await?;
+        assert!(clients.contains(&client.to_string()));
+        assert!(!clients.contains(&ip.to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_posted_messages() -> anyhow::Result<()> {
+        use crate::port::PostedMessageReader as _;
+
+        let store = SqliteStore::new().await;
+        let client_key = crate::model::write::ClientKey::from_ip(
+            &std::net::IpAddr::from([192, 0, 2, 1]),
+            "secret",
+        );
+        let message = || crate::model::write::Message {
+            client_key: Some(client_key.clone()),
+            ..crate::model::write::Message::new_for_testing()
+        };
+        let since = crate::utils::date_time::DateTime::now();
+        let created_message = message();
+        let fingerprint = crate::model::write::ContentFingerprint::of(&created_message.content);
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            created_message.clone(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let (_, events) = created.reply(message(), false, 1000)?;
+        store
+            .store(Some(created.version()), &envelop(&events))
+            .await?;
+
+        let posted = store.list_posted_messages(&fingerprint, since).await?;
+        assert_eq!(
+            posted,
+            vec![crate::model::write::PostedMessage {
+                at: created_message.created_at,
+                client_key: Some(client_key),
+                thread_id: created.id().clone(),
+            }]
+        );
+        let later = crate::utils::date_time::DateTime::from_unix_timestamp_millis(
+            created_message.created_at.to_unix_timestamp_millis() + 1,
+        );
+        assert!(
+            store
+                .list_posted_messages(&fingerprint, later)
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_v1_events() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let thread_id = crate::model::shared::id::ThreadId::generate();
+        sqlx::query(include_str!("sqlite_store/insert_thread_event_streams.sql"))
+            .bind(thread_id.to_string())
+            .bind(2_u32)
+            .execute(&store.0)
+            .await?;
+        for (data, version) in [
+            (
+                include_str!("../model/shared/event_schema/v1/created.json"),
+                1_u32,
+            ),
+            (
+                include_str!("../model/shared/event_schema/v1/replied.json"),
+                2_u32,
+            ),
+        ] {
+            // the rows written before the schema version was introduced
+            let mut data = serde_json::from_str::<serde_json::Value>(data)?;
+            data["id"] = serde_json::Value::String(
+                crate::model::shared::id::EventId::generate().to_string(),
+            );
+            data["thread_id"] = serde_json::Value::String(thread_id.to_string());
+            sqlx::query(
+                "INSERT INTO thread_events (at, data, id, kind, thread_id, version) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(data["at"].as_str())
+            .bind(data.to_string())
+            .bind(data["id"].as_str())
+            .bind(data["kind"].as_str())
+            .bind(thread_id.to_string())
+            .bind(version)
+            .execute(&store.0)
+            .await?;
+        }
+
+        let thread = store.find(&thread_id).await?.expect("thread to exist");
+        assert_eq!(thread.id(), &thread_id);
+        assert_eq!(String::from(thread.title().clone()), "Hello");
+        assert_eq!(thread.version(), crate::model::write::Version::from(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_baseline_events() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let url = baseline_database(dir.path()).await?;
+        let store = SqliteStore::connect(&url).await;
+        let thread_id = crate::model::shared::id::ThreadId::from_str(BASELINE_THREAD_ID)?;
+
+        // the events stored by the baseline are upcasted from v1
+        let events = sqlx::query(include_str!("sqlite_store/select_thread_events.sql"))
+            .bind(thread_id.to_string())
+            .bind(0_u32)
+            .fetch_all(&store.0)
+            .await?
+            .iter()
+            .map(|row| stored_thread_event_from_row(row).deserialize())
+            .collect::<Result<Vec<_>, _>>()?;
+        let fingerprint =
+            |content: &str| -> anyhow::Result<Option<crate::model::write::ContentFingerprint>> {
+                Ok(Some(crate::model::write::ContentFingerprint::of(
+                    &crate::model::write::MessageContent::try_from(content.to_owned())?,
+                )))
+            };
+        assert_eq!(
+            events,
+            vec![
+                crate::model::shared::event::ThreadEvent::Created(
+                    crate::model::shared::event::ThreadCreated {
+                        at: crate::utils::date_time::DateTime::from_str(
+                            "2023-10-01T12:00:00.000Z"
+                        )?,
+                        attachments: vec![],
+                        board_id: None,
+                        client_key: None,
+                        content: "Hello, world!".to_owned(),
+                        fingerprint: fingerprint("Hello, world!")?,
+                        id: crate::model::shared::id::EventId::from_str(
+                            "0779b098-f41d-404a-b055-36463a7c009b"
+                        )?,
+                        name: None,
+                        poll: None,
+                        previous_thread_id: None,
+                        tags: vec![],
+                        thread_id: thread_id.clone(),
+                        title: "Untitled".to_owned(),
+                        trip: None,
+                        version: 1,
+                    }
+                ),
+                crate::model::shared::event::ThreadEvent::Replied(
+                    crate::model::shared::event::ThreadReplied {
+                        at: crate::utils::date_time::DateTime::from_str(
+                            "2023-10-01T12:01:00.000Z"
+                        )?,
+                        attachments: vec![],
+                        client_key: None,
+                        content: "Reply to message".to_owned(),
+                        fingerprint: fingerprint("Reply to message")?,
+                        id: crate::model::shared::id::EventId::from_str(
+                            "5f0d2b6e-8c1a-4e7b-9d3f-2a6c4b8e1f90"
+                        )?,
+                        name: None,
+                        sage: false,
+                        thread_id: thread_id.clone(),
+                        trip: None,
+                        version: 2,
+                    }
+                ),
+            ]
+        );
+
+        // and replayed into the thread in the default board
+        let thread = store.find(&thread_id).await?.expect("thread to exist");
+        assert_eq!(
+            thread.board_id(),
+            &crate::model::shared::id::BoardId::default()
+        );
+        assert_eq!(thread.version(), crate::model::write::Version::from(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_malformed_event() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let thread_id = crate::model::shared::id::ThreadId::generate();
+        sqlx::query(include_str!("sqlite_store/insert_thread_event_streams.sql"))
+            .bind(thread_id.to_string())
+            .bind(1_u32)
+            .execute(&store.0)
+            .await?;
+        let mut data = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../model/shared/event_schema/v1/created.json"
+        ))?;
+        let id = crate::model::shared::id::EventId::generate().to_string();
+        data["at"] = serde_json::Value::String("yesterday".to_owned());
+        data["id"] = serde_json::Value::String(id.clone());
+        data["thread_id"] = serde_json::Value::String(thread_id.to_string());
+        sqlx::query(
+            "INSERT INTO thread_events (at, data, id, kind, thread_id, version) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind("yesterday")
+        .bind(data.to_string())
+        .bind(id)
+        .bind("created")
+        .bind(thread_id.to_string())
+        .bind(1_u32)
+        .execute(&store.0)
+        .await?;
+
+        assert!(matches!(
+            store.find(&thread_id).await,
+            Err(crate::port::ThreadRepositoryError::InternalError(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_corrupted() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let thread_id = crate::model::shared::id::ThreadId::generate();
+        sqlx::query(include_str!("sqlite_store/insert_thread_event_streams.sql"))
+            .bind(thread_id.to_string())
+            .bind(1_u32)
+            .execute(&store.0)
+            .await?;
+        let mut data = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../model/shared/event_schema/v1/closed.json"
+        ))?;
+        let id = crate::model::shared::id::EventId::generate().to_string();
+        data["id"] = serde_json::Value::String(id.clone());
+        data["thread_id"] = serde_json::Value::String(thread_id.to_string());
+        data["version"] = serde_json::Value::from(1_u32);
+        sqlx::query(
+            "INSERT INTO thread_events (at, data, id, kind, thread_id, version) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(data["at"].as_str())
+        .bind(data.to_string())
+        .bind(id)
+        .bind("closed")
+        .bind(thread_id.to_string())
+        .bind(1_u32)
+        .execute(&store.0)
+        .await?;
+
+        assert!(matches!(
+            store.find(&thread_id).await,
+            Err(crate::port::ThreadRepositoryError::Corrupted {
+                source: crate::model::shared::replay::ReplayError::NotCreatedFirst { .. },
+                thread_id: corrupted,
+            }) if corrupted == thread_id
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_snapshot() -> anyhow::Result<()> {
+        use crate::model::shared::thread_snapshot::ThreadSnapshot;
+
+        let store = SqliteStore::new().await;
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        store.store(None, &envelop(&events)).await?;
+        let mut thread = created.clone();
+        let mut events = vec![];
+        for _ in 0..ThreadSnapshot::INTERVAL / 2 {
+            let (pinned, pinned_events) = thread.pin()?;
+            let (unpinned, unpinned_events) = pinned.unpin()?;
+            events.extend(pinned_events);
+            events.extend(unpinned_events);
+            thread = unpinned;
+        }
+        store
+            .store(Some(created.version()), &envelop(&events))
+            .await?;
+        let snapshot = async || -> anyhow::Result<(u32, u32)> {
+            let row = sqlx::query(
+                "SELECT format_version, version FROM thread_snapshots WHERE thread_id = ?",
+            )
+            .bind(created.id().to_string())
+            .fetch_one(&store.0)
+            .await?;
+            Ok((row.get("format_version"), row.get("version")))
+        };
+
+        // the snapshot is taken when the events are stored, not when the thread is found
+        assert_eq!(
+            snapshot().await?,
+            (ThreadSnapshot::FORMAT_VERSION, u32::from(thread.version()))
+        );
+        assert_eq!(store.find(created.id()).await?, Some(thread.clone()));
+
+        // the snapshot and the events after it
+        let (closed, events) = thread.close()?;
+        store
+            .store(Some(thread.version()), &envelop(&events))
+            .await?;
+        assert_eq!(store.find(created.id()).await?, Some(closed.clone()));
+        assert_eq!(snapshot().await?.1, u32::from(thread.version()));
+
+        // the snapshot in an old format is discarded, and rebuilt at the next interval
+        sqlx::query("UPDATE thread_snapshots SET format_version = 0 WHERE thread_id = ?")
+            .bind(created.id().to_string())
+            .execute(&store.0)
+            .await?;
+        assert_eq!(store.find(created.id()).await?, Some(closed.clone()));
+        assert_eq!(snapshot().await?, (0, u32::from(thread.version())));
+        let (mut thread, mut events) = closed.reopen()?;
+        for _ in 0..ThreadSnapshot::INTERVAL / 2 {
+            let (pinned, pinned_events) = thread.pin()?;
+            let (unpinned, unpinned_events) = pinned.unpin()?;
+            events.extend(pinned_events);
+            events.extend(unpinned_events);
+            thread = unpinned;
+        }
+        store
+            .store(Some(closed.version()), &envelop(&events))
+            .await?;
+        assert_eq!(
+            snapshot().await?,
+            (ThreadSnapshot::FORMAT_VERSION, u32::from(thread.version()))
+        );
+        assert_eq!(store.find(created.id()).await?, Some(thread));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_event_metadata() -> anyhow::Result<()> {
+        let store = SqliteStore::new().await;
+        let (created, events) = crate::model::write::Thread::create(
+            crate::model::shared::id::BoardId::default(),
+            crate::model::write::ThreadTitle::new_for_testing(),
+            crate::model::write::Message::new_for_testing(),
+            std::collections::BTreeSet::new(),
+            None,
+        )?;
+        let metadata = crate::model::shared::event_envelope::ThreadEventMetadata {
+            actor: Some(crate::model::shared::event_envelope::ThreadEventActor::Moderator),
+            causation_id: Some("request".to_owned()),
+            correlation_id: Some("correlation".to_owned()),
+            request_id: Some("request".to_owned()),
+            user_agent: Some("curl/8.0".to_owned()),
+            ..Default::default()
+        };
+        store.store(None, &metadata.envelop(&events)).await?;
+
+        let rows = sqlx::query("SELECT metadata FROM thread_events WHERE thread_id = ?")
+            .bind(created.id().to_string())
+            .fetch_all(&store.0)
+            .await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            serde_json::from_str::<crate::model::shared::event_envelope::ThreadEventMetadata>(
+                rows[0].get("metadata")
+            )?,
+            metadata
+        );
         Ok(())
     }
 }
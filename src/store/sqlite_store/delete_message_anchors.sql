@@ -0,0 +1,6 @@
+DELETE FROM
+    message_anchors
+WHERE
+    thread_id = ?
+AND
+    number = ?
\ No newline at end of file
@@ -0,0 +1,8 @@
+DELETE FROM
+    message_reactions
+WHERE
+    thread_id = ?
+AND
+    number = ?
+AND
+    count <= 0
\ No newline at end of file
@@ -0,0 +1,6 @@
+DELETE FROM
+    message_revisions
+WHERE
+    thread_id = ?
+AND
+    number = ?
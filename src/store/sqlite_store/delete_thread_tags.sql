@@ -0,0 +1,6 @@
+DELETE FROM
+    thread_tags
+WHERE
+    tag = ?
+AND
+    thread_id = ?
\ No newline at end of file
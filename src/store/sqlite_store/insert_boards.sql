@@ -0,0 +1,14 @@
+INSERT INTO boards (
+      description
+    , id
+    , max_content_length
+    , max_threads
+    , name
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?,
+    ?
+)
+ON CONFLICT (id) DO NOTHING
\ No newline at end of file
@@ -0,0 +1,9 @@
+INSERT INTO message_anchors (
+      anchor
+    , thread_id
+    , number
+) VALUES (
+    ?,
+    ?,
+    ?
+);
\ No newline at end of file
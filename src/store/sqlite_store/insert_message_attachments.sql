@@ -0,0 +1,17 @@
+INSERT INTO message_attachments (
+      hash
+    , mime
+    , position
+    , size
+    , thumbnail_hash
+    , thread_id
+    , number
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?
+);
\ No newline at end of file
@@ -0,0 +1,13 @@
+INSERT INTO message_reactions (
+      code
+    , count
+    , thread_id
+    , number
+) VALUES (
+    ?,
+    1,
+    ?,
+    ?
+)
+ON CONFLICT (thread_id, number, code) DO UPDATE SET
+    count = count + 1
\ No newline at end of file
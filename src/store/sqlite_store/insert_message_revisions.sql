@@ -0,0 +1,17 @@
+INSERT INTO message_revisions (
+      content
+    , created_at
+    , thread_id
+    , number
+)
+SELECT
+      content
+    , COALESCE(edited_at, created_at)
+    , thread_id
+    , number
+FROM
+    messages
+WHERE
+    thread_id = ?
+AND
+    number = ?
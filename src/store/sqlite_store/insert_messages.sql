@@ -1,9 +1,13 @@
 INSERT INTO messages (
       content
     , created_at
+    , name
     , thread_id
+    , trip
     , number
 ) VALUES (
+    ?,
+    ?,
     ?,
     ?,
     ?,
@@ -0,0 +1,19 @@
+INSERT INTO messages (
+      content
+    , created_at
+    , name
+    , thread_id
+    , trip
+    , number
+)
+SELECT
+      ?
+    , ?
+    , ?
+    , id
+    , ?
+    , last_message_number
+FROM
+    threads
+WHERE
+    id = ?
@@ -0,0 +1,11 @@
+INSERT INTO poll_options (
+      count
+    , label
+    , number
+    , thread_id
+) VALUES (
+    0,
+    ?,
+    ?,
+    ?
+)
\ No newline at end of file
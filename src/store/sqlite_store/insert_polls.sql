@@ -0,0 +1,9 @@
+INSERT INTO polls (
+      closes_at
+    , question
+    , thread_id
+) VALUES (
+    ?,
+    ?,
+    ?
+)
\ No newline at end of file
@@ -0,0 +1,14 @@
+INSERT INTO rate_limits (
+      action
+    , client_key
+    , count
+    , started_at
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?
+)
+ON CONFLICT (action, client_key) DO UPDATE SET
+      count = excluded.count
+    , started_at = excluded.started_at
\ No newline at end of file
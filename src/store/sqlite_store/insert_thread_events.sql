@@ -1,8 +1,11 @@
 INSERT INTO thread_events (
     at,
-    content,
+    data,
+    fingerprint,
     id,
     kind,
+    metadata,
+    schema_version,
     thread_id,
     version
 ) VALUES (
@@ -11,5 +14,8 @@ INSERT INTO thread_events (
     ?,
     ?,
     ?,
+    ?,
+    ?,
+    ?,
     ?
 );
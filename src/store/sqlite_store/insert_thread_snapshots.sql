@@ -0,0 +1,15 @@
+INSERT INTO thread_snapshots (
+      data
+    , format_version
+    , thread_id
+    , version
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?
+)
+ON CONFLICT (thread_id) DO UPDATE SET
+      data = excluded.data
+    , format_version = excluded.format_version
+    , version = excluded.version
\ No newline at end of file
@@ -0,0 +1,8 @@
+INSERT INTO thread_tags (
+      tag
+    , thread_id
+) VALUES (
+    ?,
+    ?
+)
+ON CONFLICT (tag, thread_id) DO NOTHING
\ No newline at end of file
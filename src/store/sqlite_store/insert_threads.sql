@@ -1,15 +1,37 @@
 INSERT INTO threads (
-      created_at
+      board_id
+    , bumped_at
+    , closed
+    , created_at
+    , filled
     , first_message_content
     , first_message_created_at
+    , first_message_name
     , first_message_number
+    , first_message_trip
     , id
     , last_message_content
     , last_message_created_at
+    , last_message_name
     , last_message_number
+    , last_message_trip
+    , pinned
+    , previous_thread_id
     , replies_count
+    , title
     , version
 ) VALUES (
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
     ?,
     ?,
     ?,
@@ -0,0 +1,33 @@
+-- The tables created before the migrations were introduced, which may already exist
+CREATE TABLE IF NOT EXISTS thread_event_streams (
+    id      TEXT    NOT NULL    PRIMARY KEY,
+    version INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS thread_events (
+    at          INTEGER NOT NULL,
+    content     TEXT    NOT NULL,
+    id          TEXT    NOT NULL   PRIMARY KEY,
+    kind        TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    version     INTEGER NOT NULL,
+    UNIQUE (thread_id, version)
+);
+CREATE TABLE IF NOT EXISTS threads (
+    created_at                  TEXT    NOT NULL,
+    first_message_content       TEXT    NOT NULL,
+    first_message_created_at    TEXT    NOT NULL,
+    first_message_number        INTEGER NOT NULL,
+    id                          TEXT    NOT NULL   PRIMARY KEY,
+    last_message_content        TEXT    NOT NULL,
+    last_message_created_at     TEXT    NOT NULL,
+    last_message_number         INTEGER NOT NULL,
+    replies_count               INTEGER NOT NULL,
+    version                     INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS messages (
+    content     TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    PRIMARY KEY (thread_id, number)
+);
@@ -0,0 +1,36 @@
+-- The events are stored as a whole in `data` instead of only their `content`
+CREATE TABLE thread_events_new (
+    at          INTEGER NOT NULL,
+    data        TEXT    NOT NULL,
+    id          TEXT    NOT NULL   PRIMARY KEY,
+    kind        TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    version     INTEGER NOT NULL,
+    UNIQUE (thread_id, version)
+);
+INSERT INTO thread_events_new (
+      at
+    , data
+    , id
+    , kind
+    , thread_id
+    , version
+)
+SELECT
+      at
+    , json_object(
+          'at', at
+        , 'content', content
+        , 'id', id
+        , 'kind', kind
+        , 'thread_id', thread_id
+        , 'version', version
+      )
+    , id
+    , kind
+    , thread_id
+    , version
+FROM
+    thread_events;
+DROP TABLE thread_events;
+ALTER TABLE thread_events_new RENAME TO thread_events;
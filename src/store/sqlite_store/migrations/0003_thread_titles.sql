@@ -0,0 +1,9 @@
+-- The threads created before the titles existed are titled "Untitled"
+UPDATE thread_events
+SET
+    data = json_set(data, '$.title', 'Untitled')
+WHERE
+    kind = 'created'
+AND
+    json_extract(data, '$.title') IS NULL;
+ALTER TABLE threads ADD COLUMN title TEXT NOT NULL DEFAULT 'Untitled';
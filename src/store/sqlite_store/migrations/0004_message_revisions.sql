@@ -0,0 +1,11 @@
+-- The edit history of the messages
+ALTER TABLE threads ADD COLUMN first_message_edited_at TEXT;
+ALTER TABLE threads ADD COLUMN last_message_edited_at TEXT;
+ALTER TABLE messages ADD COLUMN edited_at TEXT;
+CREATE TABLE message_revisions (
+    content     TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    PRIMARY KEY (thread_id, number, created_at)
+);
@@ -0,0 +1,7 @@
+-- The tombstones of the deleted messages
+ALTER TABLE threads ADD COLUMN first_message_deleted_at TEXT;
+ALTER TABLE threads ADD COLUMN first_message_deletion_reason TEXT;
+ALTER TABLE threads ADD COLUMN last_message_deleted_at TEXT;
+ALTER TABLE threads ADD COLUMN last_message_deletion_reason TEXT;
+ALTER TABLE messages ADD COLUMN deleted_at TEXT;
+ALTER TABLE messages ADD COLUMN deletion_reason TEXT;
@@ -0,0 +1,2 @@
+-- Whether the threads are closed
+ALTER TABLE threads ADD COLUMN closed INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- The links between the filled threads and their continuations
+ALTER TABLE threads ADD COLUMN filled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE threads ADD COLUMN next_thread_id TEXT;
+ALTER TABLE threads ADD COLUMN previous_thread_id TEXT;
@@ -0,0 +1,7 @@
+-- The names and tripcodes of the posters
+ALTER TABLE threads ADD COLUMN first_message_name TEXT;
+ALTER TABLE threads ADD COLUMN first_message_trip TEXT;
+ALTER TABLE threads ADD COLUMN last_message_name TEXT;
+ALTER TABLE threads ADD COLUMN last_message_trip TEXT;
+ALTER TABLE messages ADD COLUMN name TEXT;
+ALTER TABLE messages ADD COLUMN trip TEXT;
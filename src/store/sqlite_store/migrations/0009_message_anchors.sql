@@ -0,0 +1,7 @@
+-- The reply anchors of the messages
+CREATE TABLE message_anchors (
+    anchor      INTEGER NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    PRIMARY KEY (thread_id, number, anchor)
+);
@@ -0,0 +1,8 @@
+-- The reaction counts of the messages
+CREATE TABLE message_reactions (
+    code        TEXT    NOT NULL,
+    count       INTEGER NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    PRIMARY KEY (thread_id, number, code)
+);
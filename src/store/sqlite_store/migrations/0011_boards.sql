@@ -0,0 +1,9 @@
+-- The boards, which the existing threads belong to as the default board
+CREATE TABLE boards (
+    description         TEXT    NOT NULL,
+    id                  TEXT    NOT NULL    PRIMARY KEY,
+    max_content_length  INTEGER NOT NULL,
+    max_threads         INTEGER NOT NULL,
+    name                TEXT    NOT NULL
+);
+ALTER TABLE threads ADD COLUMN board_id TEXT NOT NULL DEFAULT 'default';
@@ -0,0 +1,3 @@
+-- The time the threads were bumped, which is the last message for the existing threads
+ALTER TABLE threads ADD COLUMN bumped_at TEXT NOT NULL DEFAULT '';
+UPDATE threads SET bumped_at = last_message_created_at;
@@ -0,0 +1,2 @@
+-- Whether the threads are pinned
+ALTER TABLE threads ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- The tags of the threads
+CREATE TABLE thread_tags (
+    tag         TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    PRIMARY KEY (tag, thread_id)
+);
+CREATE INDEX thread_tags_thread_id ON thread_tags (thread_id);
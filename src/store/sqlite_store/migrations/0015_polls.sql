@@ -0,0 +1,13 @@
+-- The polls attached to the threads
+CREATE TABLE polls (
+    closes_at   TEXT,
+    question    TEXT    NOT NULL,
+    thread_id   TEXT    NOT NULL    PRIMARY KEY
+);
+CREATE TABLE poll_options (
+    count       INTEGER NOT NULL,
+    label       TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    PRIMARY KEY (thread_id, number)
+);
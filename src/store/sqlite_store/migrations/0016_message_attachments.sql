@@ -0,0 +1,11 @@
+-- The image attachments of the messages
+CREATE TABLE message_attachments (
+    hash        TEXT    NOT NULL,
+    mime        TEXT    NOT NULL,
+    position    INTEGER NOT NULL,
+    size        INTEGER NOT NULL,
+    thumbnail_hash TEXT NOT NULL,
+    thread_id   TEXT    NOT NULL,
+    number      INTEGER NOT NULL,
+    PRIMARY KEY (thread_id, number, position)
+);
@@ -0,0 +1,8 @@
+-- The posting rate limits of the clients, keyed by their client keys
+CREATE TABLE rate_limits (
+    action      TEXT    NOT NULL,
+    client_key  TEXT    NOT NULL,
+    count       INTEGER NOT NULL,
+    started_at  INTEGER NOT NULL,
+    PRIMARY KEY (action, client_key)
+);
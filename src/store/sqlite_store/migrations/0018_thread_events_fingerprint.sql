@@ -0,0 +1,3 @@
+-- The content fingerprints of the posted messages, unknown for the existing events
+ALTER TABLE thread_events ADD COLUMN fingerprint TEXT;
+CREATE INDEX thread_events_fingerprint ON thread_events (fingerprint, at);
@@ -0,0 +1,2 @@
+-- The schema versions of the event payloads, which are all v1 for the existing events
+ALTER TABLE thread_events ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- The request metadata of the events, which is empty for the existing events
+ALTER TABLE thread_events ADD COLUMN metadata TEXT NOT NULL DEFAULT '{}';
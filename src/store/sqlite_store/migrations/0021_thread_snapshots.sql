@@ -0,0 +1,7 @@
+-- The latest snapshots of the threads
+CREATE TABLE thread_snapshots (
+    data           TEXT    NOT NULL,
+    format_version INTEGER NOT NULL,
+    thread_id      TEXT    NOT NULL   PRIMARY KEY,
+    version        INTEGER NOT NULL
+);
@@ -0,0 +1,2 @@
+-- The default board is limited only by the configured max message content length
+UPDATE boards SET max_content_length = 65535 WHERE id = 'default';
@@ -0,0 +1,10 @@
+SELECT
+      description
+    , id
+    , max_content_length
+    , max_threads
+    , name
+FROM
+    boards
+ORDER BY
+    id ASC
\ No newline at end of file
@@ -0,0 +1,11 @@
+SELECT
+      anchor
+    , thread_id
+    , number
+FROM
+    message_anchors
+WHERE
+    thread_id = ?
+ORDER BY
+    number ASC
+    , anchor ASC
\ No newline at end of file
@@ -0,0 +1,15 @@
+SELECT
+      hash
+    , mime
+    , position
+    , size
+    , thumbnail_hash
+    , thread_id
+    , number
+FROM
+    message_attachments
+WHERE
+    thread_id = ?
+ORDER BY
+    number ASC
+    , position ASC
\ No newline at end of file
@@ -0,0 +1,8 @@
+SELECT
+    COUNT(*) AS count
+FROM
+    message_attachments
+WHERE
+    hash = ?
+OR
+    thumbnail_hash = ?
\ No newline at end of file
@@ -0,0 +1,12 @@
+SELECT
+      code
+    , count
+    , thread_id
+    , number
+FROM
+    message_reactions
+WHERE
+    thread_id = ?
+ORDER BY
+    number ASC
+    , code ASC
\ No newline at end of file
@@ -0,0 +1,12 @@
+SELECT
+      content
+    , created_at
+    , thread_id
+    , number
+FROM
+    message_revisions
+WHERE
+    thread_id = ?
+ORDER BY
+    number ASC
+    , created_at ASC
@@ -1,7 +1,12 @@
 SELECT
       content
     , created_at
+    , deleted_at
+    , deletion_reason
+    , edited_at
+    , name
     , thread_id
+    , trip
     , number
 FROM
     messages
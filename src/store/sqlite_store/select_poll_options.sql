@@ -0,0 +1,11 @@
+SELECT
+      count
+    , label
+    , number
+    , thread_id
+FROM
+    poll_options
+WHERE
+    thread_id = ?
+ORDER BY
+    number ASC
\ No newline at end of file
@@ -0,0 +1,8 @@
+SELECT
+      closes_at
+    , question
+    , thread_id
+FROM
+    polls
+WHERE
+    thread_id = ?
\ No newline at end of file
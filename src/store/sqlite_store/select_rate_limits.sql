@@ -0,0 +1,11 @@
+SELECT
+      action
+    , client_key
+    , count
+    , started_at
+FROM
+    rate_limits
+WHERE
+    action = ?
+AND
+    client_key = ?
\ No newline at end of file
@@ -1,14 +1,16 @@
 SELECT
     at,
-    content,
+    data,
     id,
     kind,
+    schema_version,
     thread_id,
     version
 FROM
     thread_events
 WHERE
     thread_id = ?
+    AND version > ?
 ORDER BY
     version ASC
 
@@ -0,0 +1,14 @@
+SELECT
+    at,
+    data,
+    kind,
+    schema_version,
+    thread_id
+FROM
+    thread_events
+WHERE
+    fingerprint = ?
+AND
+    at >= ?
+ORDER BY
+    at ASC
\ No newline at end of file
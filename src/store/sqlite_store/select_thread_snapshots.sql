@@ -0,0 +1,9 @@
+SELECT
+    data,
+    format_version,
+    thread_id,
+    version
+FROM
+    thread_snapshots
+WHERE
+    thread_id = ?
@@ -0,0 +1,9 @@
+SELECT
+      tag
+    , thread_id
+FROM
+    thread_tags
+WHERE
+    thread_id = ?
+ORDER BY
+    tag ASC
\ No newline at end of file
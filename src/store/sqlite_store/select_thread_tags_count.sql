@@ -0,0 +1,9 @@
+SELECT
+      tag
+    , COUNT(*) AS count
+FROM
+    thread_tags
+GROUP BY
+    tag
+ORDER BY
+    tag ASC
\ No newline at end of file
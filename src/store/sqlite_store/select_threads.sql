@@ -1,13 +1,31 @@
 SELECT
-      created_at
+      board_id
+    , bumped_at
+    , closed
+    , created_at
+    , filled
     , first_message_content
     , first_message_created_at
+    , first_message_deleted_at
+    , first_message_deletion_reason
+    , first_message_edited_at
+    , first_message_name
     , first_message_number
+    , first_message_trip
     , id
     , last_message_content
     , last_message_created_at
+    , last_message_deleted_at
+    , last_message_deletion_reason
+    , last_message_edited_at
+    , last_message_name
     , last_message_number
+    , last_message_trip
+    , next_thread_id
+    , pinned
+    , previous_thread_id
     , replies_count
+    , title
     , version
 FROM
     threads
@@ -1,15 +1,54 @@
 SELECT
-      created_at
+      board_id
+    , bumped_at
+    , closed
+    , created_at
+    , filled
     , first_message_content
     , first_message_created_at
+    , first_message_deleted_at
+    , first_message_deletion_reason
+    , first_message_edited_at
+    , first_message_name
     , first_message_number
+    , first_message_trip
     , id
     , last_message_content
     , last_message_created_at
+    , last_message_deleted_at
+    , last_message_deletion_reason
+    , last_message_edited_at
+    , last_message_name
     , last_message_number
+    , last_message_trip
+    , next_thread_id
+    , pinned
+    , previous_thread_id
     , replies_count
+    , (
+        SELECT
+            GROUP_CONCAT(tag, ' ')
+        FROM
+            thread_tags
+        WHERE
+            thread_tags.thread_id = threads.id
+    ) AS tags
+    , title
     , version
 FROM
     threads
+WHERE
+    (
+        ? IS NULL
+    OR
+        board_id = ?
+    )
+AND
+    (
+        ? IS NULL
+    OR
+        id IN (SELECT thread_id FROM thread_tags WHERE tag = ?)
+    )
 ORDER BY
-    last_message_created_at DESC
+    pinned DESC
+    , bumped_at DESC
@@ -0,0 +1,6 @@
+SELECT
+      last_message_number
+FROM
+    threads
+WHERE
+    id = ?
\ No newline at end of file
@@ -0,0 +1,10 @@
+UPDATE
+    message_reactions
+SET
+    count = count - 1
+WHERE
+    code = ?
+AND
+    thread_id = ?
+AND
+    number = ?
\ No newline at end of file
@@ -0,0 +1,9 @@
+UPDATE
+    messages
+SET
+    content = ?
+    , edited_at = ?
+WHERE
+    thread_id = ?
+AND
+    number = ?
@@ -0,0 +1,10 @@
+UPDATE
+    messages
+SET
+    content = ''
+    , deleted_at = ?
+    , deletion_reason = ?
+WHERE
+    thread_id = ?
+AND
+    number = ?
@@ -0,0 +1,8 @@
+UPDATE
+    poll_options
+SET
+    count = count + 1
+WHERE
+    thread_id = ?
+AND
+    number = ?
\ No newline at end of file
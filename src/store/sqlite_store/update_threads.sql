@@ -1,9 +1,15 @@
 UPDATE
     threads
 SET
-    last_message_content = ?
+    bumped_at = CASE WHEN ? THEN bumped_at ELSE ? END
+    , last_message_content = ?
     , last_message_created_at = ?
+    , last_message_deleted_at = NULL
+    , last_message_deletion_reason = NULL
+    , last_message_edited_at = NULL
+    , last_message_name = ?
     , last_message_number = last_message_number + 1
+    , last_message_trip = ?
     , replies_count = replies_count + 1
     , version = ?
 WHERE
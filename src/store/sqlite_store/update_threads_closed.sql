@@ -0,0 +1,9 @@
+UPDATE
+    threads
+SET
+    closed = ?
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
\ No newline at end of file
@@ -0,0 +1,9 @@
+UPDATE
+    threads
+SET
+    filled = 1
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
\ No newline at end of file
@@ -0,0 +1,12 @@
+UPDATE
+    threads
+SET
+    first_message_content = CASE WHEN first_message_number = ? THEN ? ELSE first_message_content END
+    , first_message_edited_at = CASE WHEN first_message_number = ? THEN ? ELSE first_message_edited_at END
+    , last_message_content = CASE WHEN last_message_number = ? THEN ? ELSE last_message_content END
+    , last_message_edited_at = CASE WHEN last_message_number = ? THEN ? ELSE last_message_edited_at END
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
@@ -0,0 +1,14 @@
+UPDATE
+    threads
+SET
+    first_message_content = CASE WHEN first_message_number = ? THEN '' ELSE first_message_content END
+    , first_message_deleted_at = CASE WHEN first_message_number = ? THEN ? ELSE first_message_deleted_at END
+    , first_message_deletion_reason = CASE WHEN first_message_number = ? THEN ? ELSE first_message_deletion_reason END
+    , last_message_content = CASE WHEN last_message_number = ? THEN '' ELSE last_message_content END
+    , last_message_deleted_at = CASE WHEN last_message_number = ? THEN ? ELSE last_message_deleted_at END
+    , last_message_deletion_reason = CASE WHEN last_message_number = ? THEN ? ELSE last_message_deletion_reason END
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
@@ -0,0 +1,9 @@
+UPDATE
+    threads
+SET
+    next_thread_id = ?
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
\ No newline at end of file
@@ -0,0 +1,9 @@
+UPDATE
+    threads
+SET
+    pinned = ?
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
\ No newline at end of file
@@ -0,0 +1,9 @@
+UPDATE
+    threads
+SET
+    title = ?
+    , version = ?
+WHERE
+    id = ?
+AND
+    version = ?
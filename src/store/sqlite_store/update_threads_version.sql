@@ -0,0 +1,8 @@
+UPDATE
+    threads
+SET
+    version = ?
+WHERE
+    id = ?
+AND
+    version = ?
\ No newline at end of file